@@ -1,17 +1,27 @@
 use crate::config::Config;
 use crate::factory::DependencyFactory;
 use crate::state::AppState;
+use crate::workers;
 use application::HasObservability;
 use axum::{middleware, routing::get, Router};
 use hyper::header::{HeaderName, HeaderValue};
 use infra_telemetry::{config::TelemetryConfig, telemetry};
-use pres_web_axum::{handlers, middleware::telemetry_middleware};
+use pres_graphql::SchemaLimits;
+use pres_web_axum::{
+    handlers,
+    middleware::{
+        api_key_middleware, auth_middleware, idempotency, rate_limit, request_limits,
+        telemetry_middleware, tenant,
+    },
+    openapi, versioning,
+};
 use tower::ServiceBuilder;
 
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
-use tower_governor::{governor::GovernorConfigBuilder, GovernorLayer};
+use tokio::task::JoinHandle;
 use tower_http::{
     request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
     set_header::SetResponseHeaderLayer,
@@ -21,6 +31,7 @@ use tower_http::{
 pub struct Application {
     router: Router,
     listener: TcpListener,
+    workers: Vec<JoinHandle<()>>,
 }
 
 impl Application {
@@ -39,20 +50,35 @@ impl Application {
         // 使用依賴工廠創建容器
         let container = DependencyFactory::create_container(&config).await?;
 
+        let mut background_workers = Vec::new();
+        if let Some(relay) = container.outbox_relay() {
+            background_workers.push(workers::spawn_outbox_relay(
+                relay,
+                Duration::from_millis(config.outbox_relay_interval_ms),
+            ));
+        }
+        if let Some(purger) = container.user_purger() {
+            background_workers.push(workers::spawn_user_purger(
+                purger,
+                Duration::from_secs(config.user_purge_interval_secs),
+            ));
+        }
+        if let Some(store) = container
+            .idempotency_store()
+            .filter(|_| config.idempotency.enabled)
+        {
+            background_workers.push(workers::spawn_idempotency_purger(
+                store,
+                Duration::from_secs(config.idempotency.purge_interval_secs),
+            ));
+        }
+
         let app_state = AppState {
             config: Arc::new(config.clone()),
             registry: Arc::new(registry),
             container: Arc::new(container),
         };
 
-        // Configure Governor for rate limiting using values from Config
-        let governor_config = Arc::new(
-            GovernorConfigBuilder::default()
-                .per_second(config.rate_limit_per_second)
-                .burst_size(config.rate_limit_burst_size)
-                .finish()
-                .unwrap(),
-        );
         let common_layers = ServiceBuilder::new()
             .layer(axum::extract::Extension(
                 app_state.container.observability(),
@@ -62,26 +88,152 @@ impl Application {
             ))
             .layer(TraceLayer::new_for_http())
             .layer(PropagateRequestIdLayer::x_request_id())
-            .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
-            .layer(GovernorLayer {
-                config: governor_config,
-            });
+            .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid));
+
+        // 業務 API 掛載在 /v1、/v2，原路徑依 `Accept` 協商版本
+        let mut api_routes = Router::new()
+            .route("/me", get(handlers::me_handler))
+            // User routes
+            .route(
+                "/users",
+                get(handlers::list_users_handler::<AppState>)
+                    .post(handlers::create_user_handler::<AppState>),
+            )
+            .route(
+                "/users/import",
+                axum::routing::post(handlers::import_users_handler::<AppState>),
+            )
+            .route(
+                "/users/export",
+                get(handlers::export_users_handler::<AppState>),
+            )
+            .route(
+                "/users/{id}",
+                get(handlers::get_user_handler::<AppState>)
+                    .put(handlers::update_user_handler::<AppState>)
+                    .delete(handlers::delete_user_handler::<AppState>),
+            )
+            .route(
+                "/users/{id}/restore",
+                axum::routing::post(handlers::restore_user_handler::<AppState>),
+            )
+            // API key administration
+            .route(
+                "/api-keys",
+                axum::routing::post(handlers::create_api_key_handler::<AppState>),
+            )
+            .route(
+                "/api-keys/{id}",
+                axum::routing::delete(handlers::revoke_api_key_handler::<AppState>),
+            )
+            .route(
+                "/api-keys/{id}/rotate",
+                axum::routing::post(handlers::rotate_api_key_handler::<AppState>),
+            );
+
+        // 冪等鍵只套用在 REST API，並在驗證層之內以便依呼叫者區隔鍵
+        if let Some(guard) = DependencyFactory::create_idempotency_guard(
+            &config,
+            app_state.container.idempotency_store(),
+        ) {
+            api_routes = api_routes.layer(middleware::from_fn_with_state(
+                guard,
+                idempotency::idempotency_middleware,
+            ));
+        }
+
+        // 存取租戶資料的路由：REST API、事件串流、gRPC 與 GraphQL
+        let mut tenant_routes = Router::new()
+            .route(
+                "/events/users",
+                get(handlers::user_events_handler::<AppState>),
+            )
+            .merge(versioning::versioned(
+                api_routes,
+                DependencyFactory::create_version_policies(&config)?,
+            ));
+
+        // gRPC 與 HTTP 共用連接埠（h2c），並經過相同的驗證與限流層
+        if config.grpc.enabled {
+            tenant_routes = tenant_routes.merge(
+                pres_grpc_tonic::router(
+                    app_state.clone(),
+                    app_state.container.observability(),
+                    config.grpc.reflection,
+                )
+                .await?,
+            );
+        }
+
+        // GraphQL 同樣在驗證與限流層之內，呼叫者取自驗證中介層放入的身份
+        if config.graphql.enabled {
+            tenant_routes = tenant_routes.merge(pres_graphql::router(
+                Arc::new(app_state.clone()),
+                SchemaLimits {
+                    max_depth: config.graphql.max_depth,
+                    max_complexity: config.graphql.max_complexity,
+                },
+                config.graphql.graphiql,
+            ));
+        }
 
-        let tracked_routes = Router::new()
+        // 租戶層在驗證層之內，才能讀取 JWT claim；健康檢查與服務資訊不需要租戶
+        if let Some(resolver) = DependencyFactory::create_tenant_resolver(&config)? {
+            tenant_routes = tenant_routes.layer(middleware::from_fn_with_state(
+                resolver,
+                tenant::tenant_middleware,
+            ));
+        }
+
+        let mut tracked_routes = Router::new()
             .route("/", get(handlers::main_handler::<AppState>))
             .route("/test_error", get(handlers::test_error_handler))
             .route("/test_panic", get(handlers::panic_handler))
             .route("/healthz/live", get(handlers::live_handler))
             .route("/healthz/ready", get(handlers::ready_handler))
             .route("/info", get(handlers::info_handler))
-            // User routes
-            .route(
-                "/users",
-                axum::routing::post(handlers::create_user_handler::<AppState>),
-            );
+            .merge(tenant_routes);
 
-        let untracked_routes =
-            Router::new().route("/metrics", get(handlers::metrics_handler::<AppState>));
+        // 限流層在驗證層之內，才能依已驗證的 API 金鑰或用戶計算配額
+        if let Some(limiter) =
+            DependencyFactory::create_rate_limiter(&config, app_state.container.observability())
+                .await?
+        {
+            tracked_routes = tracked_routes.layer(middleware::from_fn_with_state(
+                limiter,
+                rate_limit::rate_limit_middleware,
+            ));
+        }
+        tracked_routes = tracked_routes.layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            api_key_middleware::api_key_auth_middleware::<AppState>,
+        ));
+
+        // JWT 驗證：預先載入 JWKS，失敗時僅記錄警告並在首次請求時重試
+        if let Some(authenticator) = DependencyFactory::create_authenticator(&config) {
+            if let Err(e) = authenticator.refresh_keys().await {
+                tracing::warn!(error = %e, "Failed to load JWKS at startup");
+            }
+            tracked_routes = tracked_routes.layer(middleware::from_fn_with_state(
+                authenticator,
+                auth_middleware::jwt_auth_middleware,
+            ));
+        }
+
+        // 請求保護在最外層：過載或主體過大的請求不必先經過驗證與限流
+        if let Some(limits) =
+            DependencyFactory::create_request_limits(&config, app_state.container.observability())
+        {
+            tracked_routes = tracked_routes.layer(middleware::from_fn_with_state(
+                limits,
+                request_limits::request_limits_middleware,
+            ));
+        }
+
+        // API 文件不需驗證，前端以 /openapi.json 產生客戶端
+        let untracked_routes = Router::new()
+            .route("/metrics", get(handlers::metrics_handler::<AppState>))
+            .merge(openapi::router());
 
         // ✅ 將兩個 Router 合併，並應用最終的 state
         let mut router = Router::new()
@@ -109,24 +261,45 @@ impl Application {
             }
         }
 
+        // CORS 在驗證層之外，預檢請求直接回應；錯誤回應同樣帶有 CORS 標頭
+        if let Some(cors) = DependencyFactory::create_cors_layer(&config)? {
+            router = router.layer(cors);
+        }
+
+        // 壓縮在最外層，涵蓋上述各層加入的標頭與錯誤回應
+        if let Some(compression) = DependencyFactory::create_compression_layer(&config) {
+            router = router.layer(compression);
+        }
+
         let router = router.with_state(app_state);
 
         let addr = SocketAddr::from(([127, 0, 0, 1], config.port));
         let listener = TcpListener::bind(addr).await?;
         tracing::info!("Listening on {}", addr);
 
-        Ok(Application { router, listener })
+        Ok(Application {
+            router,
+            listener,
+            workers: background_workers,
+        })
     }
 
     pub async fn run_until_stopped(self) -> Result<(), std::io::Error> {
         tracing::info!("Application started. Press Ctrl+C to shut down.");
-        axum::serve(
+        let result = axum::serve(
             self.listener,
             self.router
                 .into_make_service_with_connect_info::<SocketAddr>(),
         )
         .with_graceful_shutdown(shutdown_signal()) // Pass hooks
-        .await
+        .await;
+
+        // 停止背景工作（未轉發的事件會留在發件箱，下次啟動再處理）
+        for worker in self.workers {
+            worker.abort();
+        }
+
+        result
     }
 }
 
@@ -1,10 +1,16 @@
 // src/config.rs
 
+use application::{outbox_relay::DEFAULT_MAX_ATTEMPTS, user_events::DEFAULT_REPLAY_CAPACITY};
 use figment::{
     providers::{Env, Format, Toml},
     Figment,
 };
+use pres_web_axum::{
+    middleware::cors::OriginPattern,
+    versioning::{ApiVersion, Deprecation},
+};
 use serde::Deserialize;
+use std::collections::HashMap;
 use validator::Validate;
 
 #[derive(Deserialize, Validate, Debug, Clone)]
@@ -16,6 +22,8 @@ pub struct HttpHeader {
 }
 
 #[derive(Deserialize, Validate, Debug, Clone)]
+#[validate(schema(function = "validate_api_versions"))]
+#[validate(schema(function = "validate_tenant_binding"))]
 pub struct Config {
     #[validate(range(min = 1024, max = 65535))]
     pub port: u16,
@@ -29,21 +37,1134 @@ pub struct Config {
     #[validate(length(min = 1))]
     pub otel_service_name: String,
 
-    // <-- 新增: 限流器每秒允許的請求數量
-    #[validate(range(min = 1))]
-    pub rate_limit_per_second: u64,
+    // 限流策略表
+    #[serde(default)]
+    #[validate(nested)]
+    pub rate_limit: RateLimitConfig,
 
-    // <-- 新增: 限流器允許的突發請求數量
-    #[validate(range(min = 1))]
-    pub rate_limit_burst_size: u32,
+    // POST 請求的 `Idempotency-Key` 處理
+    #[serde(default)]
+    #[validate(nested)]
+    pub idempotency: IdempotencyConfig,
+
+    // Redis；未設定時快取與限流計數只保存在本機記憶體
+    #[serde(default)]
+    #[validate(nested)]
+    pub redis: RedisConfig,
+
+    // /events/users 的事件重播緩衝
+    #[serde(default)]
+    #[validate(nested)]
+    pub user_events: UserEventsConfig,
+
+    // 用戶讀取快取；設定 Redis 時由所有副本共用
+    #[serde(default)]
+    #[validate(nested)]
+    pub user_cache: UserCacheConfig,
+
+    // 用例管線的逾時與重試
+    #[serde(default)]
+    #[validate(nested)]
+    pub use_cases: UseCasesConfig,
 
     pub http_headers: Option<Vec<HttpHeader>>,
 
+    // 瀏覽器跨來源呼叫
+    #[serde(default)]
+    #[validate(nested)]
+    pub cors: CorsConfig,
+
+    // 回應壓縮
+    #[serde(default)]
+    #[validate(nested)]
+    pub compression: CompressionConfig,
+
+    // API 版本的棄用設定，以版本名稱（例如 `v1`）為鍵
+    #[serde(default)]
+    pub api_versions: HashMap<String, ApiVersionConfig>,
+
+    // gRPC 服務，與 HTTP API 共用同一個連接埠
+    #[serde(default)]
+    pub grpc: GrpcConfig,
+
+    // GraphQL 端點 /graphql
+    #[serde(default)]
+    #[validate(nested)]
+    pub graphql: GraphqlConfig,
+
+    // 多租戶：決定請求所屬租戶的來源
+    #[serde(default)]
+    #[validate(nested)]
+    pub tenancy: TenancyConfig,
+
+    // 請求逾時、主體大小上限與負載卸除
+    #[serde(default)]
+    #[validate(nested)]
+    pub request_limits: RequestLimitsConfig,
+
     #[validate(length(min = 1))]
     pub database_url: String,
 
     #[validate(range(min = 1))]
     pub db_max_conn: u32,
+
+    // 發件箱轉發器的輪詢間隔（毫秒）
+    #[serde(default = "default_outbox_relay_interval_ms")]
+    #[validate(range(min = 10))]
+    pub outbox_relay_interval_ms: u64,
+
+    // 發件箱轉發器每批處理的訊息數量
+    #[serde(default = "default_outbox_batch_size")]
+    #[validate(range(min = 1, max = 1000))]
+    pub outbox_batch_size: u32,
+
+    // 每則發件箱訊息最多嘗試發佈的次數，之後列為死信
+    #[serde(default = "default_outbox_max_attempts")]
+    #[validate(range(min = 1, max = 100))]
+    pub outbox_max_attempts: u32,
+
+    // 軟刪除用戶的保留天數，法遵要求至少 30 天
+    #[serde(default = "default_deleted_user_retention_days")]
+    #[validate(range(min = 30))]
+    pub deleted_user_retention_days: u32,
+
+    // 清除過期軟刪除用戶的執行間隔（秒）
+    #[serde(default = "default_user_purge_interval_secs")]
+    #[validate(range(min = 1))]
+    pub user_purge_interval_secs: u64,
+
+    // 新實體 ID 的生成策略
+    #[serde(default)]
+    pub id_strategy: IdStrategy,
+
+    // Snowflake 策略的工作節點編號，多實例部署時每個實例必須不同
+    #[serde(default)]
+    #[validate(range(max = 1023))]
+    pub snowflake_worker_id: u16,
+
+    // JWT Bearer 驗證；未設定 JWKS 來源時停用
+    #[serde(default)]
+    #[validate(nested)]
+    pub auth: AuthConfig,
+
+    // 角色與屬性型授權策略；停用時不限制任何操作
+    #[serde(default)]
+    #[validate(nested)]
+    pub authorization: AuthorizationConfig,
+}
+
+/// Redis 設定
+#[derive(Deserialize, Validate, Debug, Clone)]
+pub struct RedisConfig {
+    // 例如 `redis://localhost:6379/0`；留空表示不使用 Redis
+    #[validate(url)]
+    pub url: Option<String>,
+
+    // 所有鍵的前綴，讓多個服務可共用同一個 Redis
+    #[serde(default = "default_redis_namespace")]
+    #[validate(length(min = 1))]
+    pub namespace: String,
+}
+
+impl Default for RedisConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            namespace: default_redis_namespace(),
+        }
+    }
+}
+
+fn default_redis_namespace() -> String {
+    "app".to_string()
+}
+
+/// gRPC 設定
+#[derive(Deserialize, Debug, Clone)]
+pub struct GrpcConfig {
+    #[serde(default = "default_grpc_enabled")]
+    pub enabled: bool,
+
+    // 提供 gRPC reflection，讓 grpcurl 等工具不需 .proto 檔即可呼叫
+    #[serde(default = "default_grpc_reflection")]
+    pub reflection: bool,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            reflection: true,
+        }
+    }
+}
+
+fn default_grpc_enabled() -> bool {
+    true
+}
+
+fn default_grpc_reflection() -> bool {
+    true
+}
+
+/// GraphQL 設定
+#[derive(Deserialize, Validate, Debug, Clone)]
+pub struct GraphqlConfig {
+    #[serde(default = "default_graphql_enabled")]
+    pub enabled: bool,
+
+    // 查詢巢狀深度上限
+    #[serde(default = "default_graphql_max_depth")]
+    #[validate(range(min = 2, max = 64))]
+    pub max_depth: usize,
+
+    // 查詢複雜度上限；列表欄位依 `limit` 倍數計算
+    #[serde(default = "default_graphql_max_complexity")]
+    #[validate(range(min = 10, max = 100000))]
+    pub max_complexity: usize,
+
+    // 以 GET /graphql 提供 GraphiQL 介面
+    #[serde(default)]
+    pub graphiql: bool,
+}
+
+impl Default for GraphqlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_graphql_enabled(),
+            max_depth: default_graphql_max_depth(),
+            max_complexity: default_graphql_max_complexity(),
+            graphiql: false,
+        }
+    }
+}
+
+/// 多租戶設定
+///
+/// 停用時所有請求屬於預設租戶。`sources` 依序列出租戶來源，多個來源都有值時必須一致。
+#[derive(Deserialize, Validate, Debug, Clone)]
+#[validate(schema(function = "validate_tenancy"))]
+pub struct TenancyConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default = "default_tenant_sources")]
+    pub sources: Vec<TenantSourceKind>,
+
+    // `header` 來源使用的標頭
+    #[serde(default = "default_tenant_header")]
+    pub header: String,
+
+    // `subdomain` 來源的基底網域，例如 `example.com`
+    pub base_domain: Option<String>,
+
+    // `jwt_claim` 來源使用的 claim
+    #[serde(default = "default_tenant_jwt_claim")]
+    #[validate(length(min = 1))]
+    pub jwt_claim: String,
+
+    // 沒有任何來源指定租戶時使用的租戶；未設定時回傳 400
+    pub default_tenant: Option<String>,
+}
+
+/// 租戶識別碼的來源
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TenantSourceKind {
+    Header,
+    Subdomain,
+    JwtClaim,
+}
+
+impl Default for TenancyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sources: default_tenant_sources(),
+            header: default_tenant_header(),
+            base_domain: None,
+            jwt_claim: default_tenant_jwt_claim(),
+            default_tenant: None,
+        }
+    }
+}
+
+fn validate_tenancy(tenancy: &TenancyConfig) -> Result<(), validator::ValidationError> {
+    if tenancy.enabled && tenancy.sources.is_empty() && tenancy.default_tenant.is_none() {
+        return Err(validator::ValidationError::new("sources")
+            .with_message("tenancy.sources must not be empty without a default_tenant".into()));
+    }
+    if tenancy.sources.contains(&TenantSourceKind::Subdomain) && tenancy.base_domain.is_none() {
+        return Err(validator::ValidationError::new("base_domain")
+            .with_message("The subdomain tenant source requires tenancy.base_domain".into()));
+    }
+    if axum::http::HeaderName::from_bytes(tenancy.header.as_bytes()).is_err() {
+        return Err(validator::ValidationError::new("header")
+            .with_message(format!("Invalid tenant header {:?}", tenancy.header).into()));
+    }
+    if let Some(tenant) = &tenancy.default_tenant {
+        if let Err(e) = contracts::TenantId::parse(tenant.as_str()) {
+            return Err(validator::ValidationError::new("default_tenant")
+                .with_message(e.to_string().into()));
+        }
+    }
+    Ok(())
+}
+
+fn default_tenant_sources() -> Vec<TenantSourceKind> {
+    vec![TenantSourceKind::JwtClaim, TenantSourceKind::Header]
+}
+
+fn default_tenant_header() -> String {
+    "x-tenant-id".to_string()
+}
+
+fn default_tenant_jwt_claim() -> String {
+    "tenant_id".to_string()
+}
+
+fn default_graphql_enabled() -> bool {
+    true
+}
+
+fn default_graphql_max_depth() -> usize {
+    10
+}
+
+fn default_graphql_max_complexity() -> usize {
+    1000
+}
+
+/// 單一 API 版本的棄用設定
+///
+/// 設定 `deprecated_at` 後，該版本的回應帶 `Deprecation`，另可加上 `Sunset` 與遷移說明的 `Link`。
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct ApiVersionConfig {
+    // RFC 3339 時間，例如 `2026-01-01T00:00:00Z`
+    pub deprecated_at: Option<String>,
+
+    // 停止服務的時間（RFC 3339），不得早於 deprecated_at
+    pub sunset_at: Option<String>,
+
+    // 遷移說明的網址
+    pub link: Option<String>,
+}
+
+impl ApiVersionConfig {
+    /// 轉為回應標頭；未設定 `deprecated_at` 時回傳 `None`
+    pub fn deprecation(&self) -> Result<Option<Deprecation>, String> {
+        match &self.deprecated_at {
+            Some(deprecated_at) => Deprecation::parse(
+                deprecated_at,
+                self.sunset_at.as_deref(),
+                self.link.as_deref(),
+            )
+            .map(Some),
+            None if self.sunset_at.is_some() || self.link.is_some() => {
+                Err("sunset_at and link require deprecated_at".to_string())
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+fn validate_api_versions(config: &Config) -> Result<(), validator::ValidationError> {
+    for (name, version) in &config.api_versions {
+        if ApiVersion::parse(name).is_none() {
+            return Err(validator::ValidationError::new("api_versions")
+                .with_message(format!("Unknown API version {name:?}").into()));
+        }
+        if let Err(e) = version.deprecation() {
+            return Err(validator::ValidationError::new("api_versions")
+                .with_message(format!("api_versions.{name}: {e}").into()));
+        }
+    }
+    Ok(())
+}
+
+// 標頭與子網域由呼叫者自行決定，啟用 JWT 驗證時必須以 token 的 claim 綁定租戶
+fn validate_tenant_binding(config: &Config) -> Result<(), validator::ValidationError> {
+    let tenancy = &config.tenancy;
+    if tenancy.enabled
+        && config.auth.is_enabled()
+        && !tenancy.sources.contains(&TenantSourceKind::JwtClaim)
+    {
+        return Err(validator::ValidationError::new("tenancy").with_message(
+            "tenancy.sources must include jwt_claim when JWT authentication is enabled".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// 冪等鍵設定
+#[derive(Deserialize, Validate, Debug, Clone)]
+#[validate(schema(function = "validate_idempotency"))]
+pub struct IdempotencyConfig {
+    #[serde(default = "default_idempotency_enabled")]
+    pub enabled: bool,
+
+    // 記錄保留秒數，過期後同一個鍵視為新請求
+    #[serde(default = "default_idempotency_ttl_secs")]
+    #[validate(range(min = 60, max = 604800))]
+    pub ttl_secs: u64,
+
+    // 處理中的請求鎖定鍵的秒數，逾時後視為放棄
+    #[serde(default = "default_idempotency_lock_timeout_secs")]
+    #[validate(range(min = 1, max = 3600))]
+    pub lock_timeout_secs: u64,
+
+    // 計算指紋時緩衝的請求主體上限
+    #[serde(default = "default_idempotency_max_bytes")]
+    #[validate(range(min = 1024))]
+    pub max_request_bytes: usize,
+
+    // 可記錄的回應主體上限，超過時不記錄
+    #[serde(default = "default_idempotency_max_bytes")]
+    #[validate(range(min = 1024))]
+    pub max_response_bytes: usize,
+
+    // 清除過期記錄的執行間隔（秒）
+    #[serde(default = "default_idempotency_purge_interval_secs")]
+    #[validate(range(min = 1))]
+    pub purge_interval_secs: u64,
+
+    // 不套用冪等鍵的路由（不含版本前綴），串流上傳無法緩衝請求主體
+    #[serde(default = "default_idempotency_exempt_routes")]
+    pub exempt_routes: Vec<String>,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_idempotency_enabled(),
+            ttl_secs: default_idempotency_ttl_secs(),
+            lock_timeout_secs: default_idempotency_lock_timeout_secs(),
+            max_request_bytes: default_idempotency_max_bytes(),
+            max_response_bytes: default_idempotency_max_bytes(),
+            purge_interval_secs: default_idempotency_purge_interval_secs(),
+            exempt_routes: default_idempotency_exempt_routes(),
+        }
+    }
+}
+
+fn validate_idempotency(idempotency: &IdempotencyConfig) -> Result<(), validator::ValidationError> {
+    if idempotency.lock_timeout_secs >= idempotency.ttl_secs {
+        return Err(validator::ValidationError::new("lock_timeout_secs")
+            .with_message("idempotency.lock_timeout_secs must be shorter than ttl_secs".into()));
+    }
+    Ok(())
+}
+
+fn default_idempotency_enabled() -> bool {
+    true
+}
+
+fn default_idempotency_ttl_secs() -> u64 {
+    86400
+}
+
+fn default_idempotency_lock_timeout_secs() -> u64 {
+    60
+}
+
+fn default_idempotency_max_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_idempotency_purge_interval_secs() -> u64 {
+    3600
+}
+
+fn default_idempotency_exempt_routes() -> Vec<String> {
+    vec!["/users/import".to_string()]
+}
+
+/// 用戶事件串流設定
+#[derive(Deserialize, Validate, Debug, Clone)]
+pub struct UserEventsConfig {
+    // 保留供 `Last-Event-ID` 續傳的最近事件數量
+    #[serde(default = "default_user_events_replay_capacity")]
+    #[validate(range(min = 1, max = 100000))]
+    pub replay_capacity: usize,
+}
+
+impl Default for UserEventsConfig {
+    fn default() -> Self {
+        Self {
+            replay_capacity: default_user_events_replay_capacity(),
+        }
+    }
+}
+
+fn default_user_events_replay_capacity() -> usize {
+    DEFAULT_REPLAY_CAPACITY
+}
+
+/// 用戶讀取快取設定
+#[derive(Deserialize, Validate, Debug, Clone)]
+pub struct UserCacheConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    // 存在的用戶保留秒數
+    #[serde(default = "default_user_cache_ttl_secs")]
+    #[validate(range(min = 1, max = 86400))]
+    pub ttl_secs: u64,
+
+    // 「用戶不存在」保留秒數；0 表示不做負向快取
+    #[serde(default = "default_user_cache_negative_ttl_secs")]
+    #[validate(range(max = 3600))]
+    pub negative_ttl_secs: u64,
+}
+
+impl Default for UserCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_secs: default_user_cache_ttl_secs(),
+            negative_ttl_secs: default_user_cache_negative_ttl_secs(),
+        }
+    }
+}
+
+fn default_user_cache_ttl_secs() -> u64 {
+    60
+}
+
+fn default_user_cache_negative_ttl_secs() -> u64 {
+    5
+}
+
+/// 用例管線設定
+///
+/// `overrides` 以用例名稱（例如 `get_user`）為鍵，未填的欄位沿用上層的預設值。
+#[derive(Deserialize, Validate, Debug, Clone)]
+#[validate(schema(function = "validate_use_cases"))]
+pub struct UseCasesConfig {
+    // 整次執行（含重試）的時間上限（毫秒）；未設定表示不限制
+    #[validate(range(min = 1))]
+    pub timeout_ms: Option<u64>,
+
+    // 最多執行次數，1 表示不重試；只有暫時性錯誤會重試
+    #[serde(default = "default_use_case_max_attempts")]
+    #[validate(range(min = 1, max = 10))]
+    pub max_attempts: u32,
+
+    // 第一次重試前的等待時間（毫秒），之後每次加倍
+    #[serde(default = "default_use_case_retry_backoff_ms")]
+    #[validate(range(max = 10000))]
+    pub retry_backoff_ms: u64,
+
+    #[serde(default)]
+    pub overrides: HashMap<String, UseCaseOverrideConfig>,
+}
+
+impl Default for UseCasesConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: None,
+            max_attempts: default_use_case_max_attempts(),
+            retry_backoff_ms: default_use_case_retry_backoff_ms(),
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct UseCaseOverrideConfig {
+    pub timeout_ms: Option<u64>,
+    pub max_attempts: Option<u32>,
+    pub retry_backoff_ms: Option<u64>,
+}
+
+fn validate_use_cases(use_cases: &UseCasesConfig) -> Result<(), validator::ValidationError> {
+    for (name, case) in &use_cases.overrides {
+        if case.timeout_ms == Some(0) {
+            return Err(validator::ValidationError::new("timeout_ms").with_message(
+                format!("use_cases.overrides.{name}.timeout_ms must be >= 1").into(),
+            ));
+        }
+        if case.max_attempts.is_some_and(|n| !(1..=10).contains(&n)) {
+            return Err(
+                validator::ValidationError::new("max_attempts").with_message(
+                    format!("use_cases.overrides.{name}.max_attempts must be between 1 and 10")
+                        .into(),
+                ),
+            );
+        }
+        if case.retry_backoff_ms.is_some_and(|ms| ms > 10000) {
+            return Err(
+                validator::ValidationError::new("retry_backoff_ms").with_message(
+                    format!("use_cases.overrides.{name}.retry_backoff_ms must be <= 10000").into(),
+                ),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn default_use_case_max_attempts() -> u32 {
+    1
+}
+
+fn default_use_case_retry_backoff_ms() -> u64 {
+    50
+}
+
+/// CORS 設定
+///
+/// 來源可為完整來源、`https://*.example.com` 形式的萬用子網域或 `*`；
+/// 方法與請求標頭填 `*` 時允許預檢請求所要求的任何值，但不可與 `allow_credentials` 併用。
+#[derive(Deserialize, Validate, Debug, Clone)]
+#[validate(schema(function = "validate_cors"))]
+pub struct CorsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+
+    #[serde(default = "default_cors_allowed_methods")]
+    pub allowed_methods: Vec<String>,
+
+    #[serde(default = "default_cors_allowed_headers")]
+    pub allowed_headers: Vec<String>,
+
+    // 瀏覽器可讀取的回應標頭
+    #[serde(default = "default_cors_expose_headers")]
+    pub expose_headers: Vec<String>,
+
+    #[serde(default)]
+    pub allow_credentials: bool,
+
+    // 預檢結果的快取秒數；0 表示不送出 `Access-Control-Max-Age`
+    #[serde(default = "default_cors_max_age_secs")]
+    pub max_age_secs: u64,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed_origins: Vec::new(),
+            allowed_methods: default_cors_allowed_methods(),
+            allowed_headers: default_cors_allowed_headers(),
+            expose_headers: default_cors_expose_headers(),
+            allow_credentials: false,
+            max_age_secs: default_cors_max_age_secs(),
+        }
+    }
+}
+
+fn validate_cors(cors: &CorsConfig) -> Result<(), validator::ValidationError> {
+    if !cors.enabled {
+        return Ok(());
+    }
+    if cors.allowed_origins.is_empty() {
+        return Err(validator::ValidationError::new("allowed_origins")
+            .with_message("CORS requires at least one allowed origin".into()));
+    }
+    for origin in &cors.allowed_origins {
+        OriginPattern::parse(origin).map_err(|message| {
+            validator::ValidationError::new("allowed_origins").with_message(message.into())
+        })?;
+    }
+    if let Some(method) = cors
+        .allowed_methods
+        .iter()
+        .find(|method| *method != "*" && method.parse::<axum::http::Method>().is_err())
+    {
+        return Err(validator::ValidationError::new("allowed_methods")
+            .with_message(format!("Invalid HTTP method {method:?}").into()));
+    }
+    let invalid_header = |header: &&String| header.parse::<axum::http::HeaderName>().is_err();
+    if let Some(header) = cors
+        .allowed_headers
+        .iter()
+        .filter(|header| *header != "*")
+        .find(invalid_header)
+        .or_else(|| cors.expose_headers.iter().find(invalid_header))
+    {
+        return Err(validator::ValidationError::new("headers")
+            .with_message(format!("Invalid HTTP header name {header:?}").into()));
+    }
+    let has_wildcard = |values: &[String]| values.iter().any(|value| value.trim() == "*");
+    if cors.allow_credentials
+        && (has_wildcard(&cors.allowed_origins)
+            || has_wildcard(&cors.allowed_methods)
+            || has_wildcard(&cors.allowed_headers))
+    {
+        return Err(
+            validator::ValidationError::new("allow_credentials").with_message(
+                "cors.allow_credentials cannot be combined with \"*\" origins, methods or headers"
+                    .into(),
+            ),
+        );
+    }
+    Ok(())
+}
+
+fn default_cors_allowed_methods() -> Vec<String> {
+    ["GET", "POST", "PUT", "DELETE"]
+        .map(str::to_string)
+        .to_vec()
+}
+
+fn default_cors_allowed_headers() -> Vec<String> {
+    [
+        "authorization",
+        "content-type",
+        "if-match",
+        "if-none-match",
+        "idempotency-key",
+        "last-event-id",
+        "x-request-id",
+        "x-tenant-id",
+    ]
+    .map(str::to_string)
+    .to_vec()
+}
+
+fn default_cors_expose_headers() -> Vec<String> {
+    [
+        "etag",
+        "location",
+        "retry-after",
+        "x-request-id",
+        "idempotent-replayed",
+        "ratelimit-limit",
+        "ratelimit-remaining",
+        "ratelimit-reset",
+        "deprecation",
+        "sunset",
+    ]
+    .map(str::to_string)
+    .to_vec()
+}
+
+fn default_cors_max_age_secs() -> u64 {
+    600
+}
+
+/// 回應壓縮設定
+///
+/// 依 `Accept-Encoding` 選擇演算法；小於 `min_size_bytes` 的回應、圖片、gRPC 與 SSE 不壓縮。
+#[derive(Deserialize, Validate, Debug, Clone)]
+#[validate(schema(function = "validate_compression"))]
+pub struct CompressionConfig {
+    #[serde(default = "default_compression_enabled")]
+    pub enabled: bool,
+
+    #[serde(default = "default_compression_algorithms")]
+    pub algorithms: Vec<CompressionAlgorithm>,
+
+    #[serde(default = "default_compression_min_size_bytes")]
+    pub min_size_bytes: u16,
+}
+
+/// 回應壓縮演算法
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionAlgorithm {
+    Gzip,
+    Br,
+    Zstd,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_compression_enabled(),
+            algorithms: default_compression_algorithms(),
+            min_size_bytes: default_compression_min_size_bytes(),
+        }
+    }
+}
+
+fn validate_compression(compression: &CompressionConfig) -> Result<(), validator::ValidationError> {
+    if compression.enabled && compression.algorithms.is_empty() {
+        return Err(validator::ValidationError::new("algorithms")
+            .with_message("Compression requires at least one algorithm".into()));
+    }
+    Ok(())
+}
+
+fn default_compression_enabled() -> bool {
+    true
+}
+
+fn default_compression_algorithms() -> Vec<CompressionAlgorithm> {
+    vec![
+        CompressionAlgorithm::Zstd,
+        CompressionAlgorithm::Br,
+        CompressionAlgorithm::Gzip,
+    ]
+}
+
+fn default_compression_min_size_bytes() -> u16 {
+    1024
+}
+
+/// 請求保護設定
+///
+/// 數值為 0 代表不限制。`routes` 依序比對，第一條相符的覆寫全域的逾時與主體上限。
+#[derive(Deserialize, Validate, Debug, Clone)]
+#[validate(schema(function = "validate_request_limits"))]
+pub struct RequestLimitsConfig {
+    // 處理器產生回應的期限（毫秒）
+    #[serde(default = "default_request_timeout_ms")]
+    pub timeout_ms: u64,
+
+    // 請求主體上限（位元組）
+    #[serde(default = "default_request_max_body_bytes")]
+    pub max_body_bytes: usize,
+
+    // 同時處理的請求上限，超過時回傳 503
+    #[serde(default = "default_request_max_concurrency")]
+    pub max_concurrency: usize,
+
+    #[serde(default = "default_request_limit_routes")]
+    #[validate(nested)]
+    pub routes: Vec<RequestLimitRouteConfig>,
+}
+
+#[derive(Deserialize, Validate, Debug, Clone)]
+pub struct RequestLimitRouteConfig {
+    // 路由樣板，例如 `/users/{id}`；結尾的 `*` 代表前綴比對
+    #[validate(length(min = 1))]
+    pub route: String,
+
+    // 留空表示所有方法
+    #[serde(default)]
+    pub methods: Vec<String>,
+
+    // 未設定時沿用全域值
+    pub timeout_ms: Option<u64>,
+
+    pub max_body_bytes: Option<usize>,
+}
+
+impl Default for RequestLimitsConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: default_request_timeout_ms(),
+            max_body_bytes: default_request_max_body_bytes(),
+            max_concurrency: default_request_max_concurrency(),
+            routes: default_request_limit_routes(),
+        }
+    }
+}
+
+fn validate_request_limits(
+    request_limits: &RequestLimitsConfig,
+) -> Result<(), validator::ValidationError> {
+    for route in &request_limits.routes {
+        if route.route != "*" && !route.route.starts_with('/') {
+            return Err(validator::ValidationError::new("route").with_message(
+                format!("Request limit route {:?} must start with '/'", route.route).into(),
+            ));
+        }
+        if let Some(method) = route
+            .methods
+            .iter()
+            .find(|method| method.parse::<axum::http::Method>().is_err())
+        {
+            return Err(validator::ValidationError::new("methods")
+                .with_message(format!("Invalid HTTP method {method:?}").into()));
+        }
+    }
+    Ok(())
+}
+
+fn default_request_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_request_max_body_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_request_max_concurrency() -> usize {
+    1024
+}
+
+// 事件串流與批次匯入本來就是長時間的請求，匯入另有逐列的大小限制；
+// 自訂 routes 時需保留匯入的覆寫，否則長時間的匯入會在全域逾時後被中斷
+fn default_request_limit_routes() -> Vec<RequestLimitRouteConfig> {
+    vec![
+        RequestLimitRouteConfig {
+            route: "/events/users".to_string(),
+            methods: Vec::new(),
+            timeout_ms: Some(0),
+            max_body_bytes: None,
+        },
+        RequestLimitRouteConfig {
+            route: "/users/import".to_string(),
+            methods: Vec::new(),
+            timeout_ms: Some(0),
+            max_body_bytes: Some(0),
+        },
+    ]
+}
+
+/// 限流設定
+///
+/// 沒有任何 `policies` 相符的路由套用預設配額（`requests` / `window_secs` / `key`）。
+#[derive(Deserialize, Validate, Debug, Clone)]
+#[validate(schema(function = "validate_rate_limit"))]
+pub struct RateLimitConfig {
+    #[serde(default = "default_rate_limit_enabled")]
+    pub enabled: bool,
+
+    // 預設配額：每個時間窗允許的請求數
+    #[serde(default = "default_rate_limit_requests")]
+    #[validate(range(min = 1))]
+    pub requests: u32,
+
+    // 預設時間窗長度（秒）
+    #[serde(default = "default_rate_limit_window_secs")]
+    #[validate(range(min = 1))]
+    pub window_secs: u64,
+
+    // 預設的配額單位
+    #[serde(default)]
+    pub key: RateLimitKey,
+
+    // 服務前方受信任的代理層數；使用 forwarded_ip 時至少為 1
+    #[serde(default)]
+    #[validate(range(max = 10))]
+    pub trusted_proxy_hops: u8,
+
+    // 依序比對，第一條相符的策略生效
+    #[serde(default)]
+    #[validate(nested)]
+    pub policies: Vec<RateLimitPolicyConfig>,
+}
+
+#[derive(Deserialize, Validate, Debug, Clone)]
+pub struct RateLimitPolicyConfig {
+    // 路由樣板，例如 `/users/{id}`；結尾的 `*` 代表前綴比對
+    #[validate(length(min = 1))]
+    pub route: String,
+
+    // 留空表示所有方法
+    #[serde(default)]
+    pub methods: Vec<String>,
+
+    #[serde(default)]
+    pub key: RateLimitKey,
+
+    #[validate(range(min = 1))]
+    pub requests: u32,
+
+    #[validate(range(min = 1))]
+    pub window_secs: u64,
+}
+
+/// 限流配額的計算單位
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKey {
+    #[default]
+    Ip,
+    ForwardedIp,
+    ApiKey,
+    User,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_rate_limit_enabled(),
+            requests: default_rate_limit_requests(),
+            window_secs: default_rate_limit_window_secs(),
+            key: RateLimitKey::default(),
+            trusted_proxy_hops: 0,
+            policies: Vec::new(),
+        }
+    }
+}
+
+fn validate_rate_limit(rate_limit: &RateLimitConfig) -> Result<(), validator::ValidationError> {
+    let uses_forwarded_ip = rate_limit.key == RateLimitKey::ForwardedIp
+        || rate_limit
+            .policies
+            .iter()
+            .any(|policy| policy.key == RateLimitKey::ForwardedIp);
+    if uses_forwarded_ip && rate_limit.trusted_proxy_hops == 0 {
+        return Err(validator::ValidationError::new("trusted_proxy_hops")
+            .with_message("forwarded_ip keys require rate_limit.trusted_proxy_hops >= 1".into()));
+    }
+    for policy in &rate_limit.policies {
+        if policy.route != "*" && !policy.route.starts_with('/') {
+            return Err(validator::ValidationError::new("route").with_message(
+                format!("Rate limit route {:?} must start with '/'", policy.route).into(),
+            ));
+        }
+        if let Some(method) = policy
+            .methods
+            .iter()
+            .find(|method| method.parse::<axum::http::Method>().is_err())
+        {
+            return Err(validator::ValidationError::new("methods")
+                .with_message(format!("Invalid HTTP method {method:?}").into()));
+        }
+    }
+    Ok(())
+}
+
+fn default_rate_limit_enabled() -> bool {
+    true
+}
+
+fn default_rate_limit_requests() -> u32 {
+    100
+}
+
+fn default_rate_limit_window_secs() -> u64 {
+    60
+}
+
+/// 授權策略設定
+#[derive(Deserialize, Validate, Debug, Clone, Default)]
+pub struct AuthorizationConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    // 未帶憑證的呼叫者擁有的權限
+    #[serde(default)]
+    pub anonymous_permissions: Vec<String>,
+
+    // 角色名稱 -> 權限清單，權限支援 `users:*` 與 `*` 萬用字元
+    #[serde(default)]
+    pub roles: HashMap<String, Vec<String>>,
+
+    // 屬性型規則：條件全部成立時授予權限
+    #[serde(default)]
+    #[validate(nested)]
+    pub rules: Vec<AuthorizationRuleConfig>,
+}
+
+#[derive(Deserialize, Validate, Debug, Clone)]
+pub struct AuthorizationRuleConfig {
+    #[validate(length(min = 1))]
+    pub permissions: Vec<String>,
+
+    // 只允許呼叫者對自己（用戶 ID 等於 token subject）執行
+    #[serde(default)]
+    pub owner: bool,
+
+    // 呼叫者必須帶有此 scope
+    pub scope: Option<String>,
+}
+
+/// JWT 驗證設定
+#[derive(Deserialize, Validate, Debug, Clone)]
+#[validate(schema(function = "validate_auth"))]
+pub struct AuthConfig {
+    // 本機 JWKS 檔案路徑
+    pub jwks_path: Option<String>,
+
+    // 遠端 JWKS 端點
+    #[validate(url)]
+    pub jwks_url: Option<String>,
+
+    // 期望的簽發者（iss）
+    pub issuer: Option<String>,
+
+    // 接受的受眾（aud）；留空表示不檢查
+    #[serde(default)]
+    pub audiences: Vec<String>,
+
+    // 檢查過期時間時容許的時鐘誤差（秒）
+    #[serde(default = "default_jwt_leeway_secs")]
+    #[validate(range(max = 300))]
+    pub leeway_secs: u64,
+
+    // JWKS 快取的重新載入間隔（秒）
+    #[serde(default = "default_jwks_refresh_secs")]
+    #[validate(range(min = 10))]
+    pub jwks_refresh_secs: u64,
+
+    // 兩次重新載入 JWKS 之間的最小間隔（秒），未知的 kid 或載入失敗都不會更頻繁地請求端點
+    #[serde(default = "default_jwks_min_refresh_secs")]
+    #[validate(range(min = 1))]
+    pub jwks_min_refresh_secs: u64,
+}
+
+impl AuthConfig {
+    /// 是否已設定 JWKS 來源
+    pub fn is_enabled(&self) -> bool {
+        self.jwks_path.is_some() || self.jwks_url.is_some()
+    }
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            jwks_path: None,
+            jwks_url: None,
+            issuer: None,
+            audiences: Vec::new(),
+            leeway_secs: default_jwt_leeway_secs(),
+            jwks_refresh_secs: default_jwks_refresh_secs(),
+            jwks_min_refresh_secs: default_jwks_min_refresh_secs(),
+        }
+    }
+}
+
+fn validate_auth(auth: &AuthConfig) -> Result<(), validator::ValidationError> {
+    if auth.jwks_path.is_some() && auth.jwks_url.is_some() {
+        return Err(validator::ValidationError::new("jwks_source")
+            .with_message("Set either auth.jwks_path or auth.jwks_url, not both".into()));
+    }
+    if auth.jwks_min_refresh_secs > auth.jwks_refresh_secs {
+        return Err(validator::ValidationError::new("jwks_min_refresh_secs")
+            .with_message("auth.jwks_min_refresh_secs must not exceed jwks_refresh_secs".into()));
+    }
+    Ok(())
+}
+
+fn default_jwt_leeway_secs() -> u64 {
+    60
+}
+
+fn default_jwks_refresh_secs() -> u64 {
+    300
+}
+
+fn default_jwks_min_refresh_secs() -> u64 {
+    10
+}
+
+/// ID 生成策略
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IdStrategy {
+    #[default]
+    UuidV7,
+    Ulid,
+    Snowflake,
+}
+
+fn default_outbox_relay_interval_ms() -> u64 {
+    1_000
+}
+
+fn default_deleted_user_retention_days() -> u32 {
+    30
+}
+
+fn default_user_purge_interval_secs() -> u64 {
+    3_600
+}
+
+fn default_outbox_batch_size() -> u32 {
+    100
+}
+
+fn default_outbox_max_attempts() -> u32 {
+    DEFAULT_MAX_ATTEMPTS
 }
 
 impl Config {
@@ -1,33 +1,497 @@
 use std::sync::Arc;
+use std::time::Duration;
 
-use application::Container;
-use contracts::ports::{DynObservability, DynUserRepo};
-use infra_db_postgres::user_repo::PostgresUserRepository;
-use infra_telemetry::{config::TelemetryConfig, metrics::Metrics};
+use application::{
+    authorization::{AllowAllPolicy, AttributeRule, RolePolicy, RuleCondition},
+    id_generation::{SnowflakeGenerator, UlidGenerator, UuidV7Generator},
+    outbox_relay::OutboxRelay,
+    pipeline::{UseCaseOptions, UseCasePipeline},
+    user_cache::{CachingUnitOfWorkFactory, CachingUserRepository, UserCacheSettings},
+    user_events::UserEventStream,
+    user_purge::DeletedUserPurger,
+    Container, ContainerBuilder,
+};
+use contracts::{
+    ports::{DynApiKeyRepo, DynIdGenerator, DynObservability, DynUnitOfWorkFactory, DynUserRepo},
+    DynAuthorizationPolicy, DynCache, DynIdempotencyStore, DynRateLimitStore, RateLimitQuota,
+    TenantId,
+};
+use infra_cache_redis::{RedisCache, RedisRateLimitStore};
+use infra_db_postgres::{
+    api_key_repo::PostgresApiKeyRepository, idempotency::PostgresIdempotencyStore,
+    outbox::PostgresOutbox, unit_of_work::PostgresUnitOfWorkFactory,
+    user_repo::PostgresUserRepository,
+};
+use infra_memory::{InMemoryCache, InMemoryRateLimitStore};
+use infra_telemetry::{
+    config::TelemetryConfig, event_publisher::LogEventPublisher, metrics::Metrics,
+};
+use pres_web_axum::{
+    middleware::{
+        auth_middleware::{
+            FileJwksSource, HttpJwksSource, JwksSource, JwtAuthenticator, JwtSettings,
+        },
+        cors::{CorsPolicy, OriginPattern},
+        idempotency::IdempotencyGuard,
+        rate_limit::{RateLimitKeyKind, RateLimitPolicy, RateLimiter},
+        request_limits::{RequestLimits, RouteLimits},
+        tenant::{TenantResolver, TenantSource},
+    },
+    versioning::{ApiVersion, VersionPolicies},
+};
+use redis::aio::ConnectionManager;
+use tower_http::{
+    compression::{
+        predicate::{NotForContentType, SizeAbove},
+        CompressionLayer, Predicate,
+    },
+    cors::CorsLayer,
+};
 
-use crate::config::Config;
+use crate::config::{CompressionAlgorithm, Config, IdStrategy, RateLimitKey, TenantSourceKind};
 
 /// 依賴工廠 - 負責組裝所有依賴
 pub struct DependencyFactory;
 
 impl DependencyFactory {
-    /// 創建完整的依賴容器
+    /// 創建完整的依賴容器；缺少元件時回傳列出所有缺少項目的錯誤
     pub async fn create_container(
         config: &Config,
     ) -> Result<Container, Box<dyn std::error::Error>> {
+        Ok(Self::container_builder(config).await?.build()?)
+    }
+
+    /// 依設定完成正式環境的組裝，但尚未建立容器
+    ///
+    /// 測試可在 `build` 前以 `with_*` 覆寫個別元件，其餘沿用正式組裝。
+    pub async fn container_builder(
+        config: &Config,
+    ) -> Result<ContainerBuilder, Box<dyn std::error::Error>> {
         // 創建基礎設施適配器
-        let user_repo = Self::create_user_repository(config).await?;
+        let repo = PostgresUserRepository::new(&config.database_url, config.db_max_conn).await?;
+        let (user_repo, unit_of_work) = Self::create_persistence(&repo);
         let observability = Self::create_observability(config);
+        let (user_repo, unit_of_work) =
+            Self::create_user_cache(config, user_repo, unit_of_work, observability.clone()).await?;
+        let api_key_repo: DynApiKeyRepo =
+            Arc::new(PostgresApiKeyRepository::new(repo.pool().clone()));
+        let outbox_relay = Self::create_outbox_relay(config, &repo);
+        let user_purger = Self::create_user_purger(config, user_repo.clone());
+        let id_generator = Self::create_id_generator(config)?;
+        let policy = Self::create_authorization_policy(config);
+        let pipeline = Self::create_use_case_pipeline(config, observability.clone());
 
-        // 組裝容器
-        Ok(Container::new(user_repo, observability))
+        let mut builder = Container::builder()
+            .with_user_repo(user_repo)
+            .with_unit_of_work(unit_of_work)
+            .with_api_key_repo(api_key_repo)
+            .with_observability(observability)
+            .with_id_generator(id_generator)
+            .with_authorization_policy(policy)
+            .with_pipeline(pipeline)
+            .with_outbox_relay(outbox_relay)
+            .with_user_events(Arc::new(UserEventStream::new(
+                config.user_events.replay_capacity,
+            )))
+            .with_user_purger(user_purger);
+        if config.idempotency.enabled {
+            builder = builder.with_idempotency_store(Arc::new(PostgresIdempotencyStore::new(
+                repo.pool().clone(),
+            )));
+        }
+        Ok(builder)
     }
 
-    async fn create_user_repository(
+    /// 建立儲存庫與工作單元工廠，兩者共用同一個連線池
+    fn create_persistence(repo: &PostgresUserRepository) -> (DynUserRepo, DynUnitOfWorkFactory) {
+        let unit_of_work = PostgresUnitOfWorkFactory::new(repo.pool().clone());
+        (Arc::new(repo.clone()), Arc::new(unit_of_work))
+    }
+
+    /// 依設定以讀取穿透快取包裝儲存庫與工作單元工廠；停用時原樣回傳
+    ///
+    /// 設定 Redis 時快取由所有副本共用，否則只在本實例內有效。
+    async fn create_user_cache(
         config: &Config,
-    ) -> Result<DynUserRepo, Box<dyn std::error::Error>> {
-        let repo = PostgresUserRepository::new(&config.database_url, config.db_max_conn).await?;
-        Ok(Arc::new(repo))
+        user_repo: DynUserRepo,
+        unit_of_work: DynUnitOfWorkFactory,
+        observability: DynObservability,
+    ) -> Result<(DynUserRepo, DynUnitOfWorkFactory), Box<dyn std::error::Error>> {
+        let user_cache = &config.user_cache;
+        if !user_cache.enabled {
+            return Ok((user_repo, unit_of_work));
+        }
+
+        let cache: DynCache = match Self::connect_redis(config).await? {
+            Some(conn) => Arc::new(RedisCache::new(
+                conn,
+                format!("{}:cache", config.redis.namespace),
+            )),
+            None => Arc::new(InMemoryCache::new()),
+        };
+        let settings = UserCacheSettings {
+            ttl: Duration::from_secs(user_cache.ttl_secs),
+            negative_ttl: Duration::from_secs(user_cache.negative_ttl_secs),
+        };
+        Ok((
+            Arc::new(CachingUserRepository::new(
+                user_repo,
+                cache.clone(),
+                observability,
+                settings,
+            )),
+            Arc::new(CachingUnitOfWorkFactory::new(unit_of_work, cache)),
+        ))
+    }
+
+    /// 建立各 API 版本的棄用設定
+    pub fn create_version_policies(
+        config: &Config,
+    ) -> Result<VersionPolicies, Box<dyn std::error::Error>> {
+        let mut policies = VersionPolicies::new();
+        for (name, version_config) in &config.api_versions {
+            let version =
+                ApiVersion::parse(name).ok_or_else(|| format!("Unknown API version {name:?}"))?;
+            if let Some(deprecation) = version_config
+                .deprecation()
+                .map_err(|e| format!("api_versions.{name}: {e}"))?
+            {
+                policies = policies.with_deprecation(version, deprecation);
+            }
+        }
+        Ok(policies)
+    }
+
+    /// 建立 JWT 驗證器；未設定 JWKS 來源時回傳 `None`
+    pub fn create_authenticator(config: &Config) -> Option<Arc<JwtAuthenticator>> {
+        let auth = &config.auth;
+        let source: Arc<dyn JwksSource> = match (&auth.jwks_path, &auth.jwks_url) {
+            (Some(path), _) => Arc::new(FileJwksSource::new(path)),
+            (None, Some(url)) => Arc::new(HttpJwksSource::new(url)),
+            (None, None) => return None,
+        };
+        let settings = JwtSettings {
+            issuer: auth.issuer.clone(),
+            audiences: auth.audiences.clone(),
+            leeway: Duration::from_secs(auth.leeway_secs),
+            jwks_refresh_interval: Duration::from_secs(auth.jwks_refresh_secs),
+            jwks_min_refresh_interval: Duration::from_secs(auth.jwks_min_refresh_secs),
+        };
+        Some(Arc::new(JwtAuthenticator::new(source, settings)))
+    }
+
+    /// 建立限流器；停用時回傳 `None`
+    ///
+    /// 設定中的策略依序比對，最後附上套用到所有路由的預設策略。
+    /// 設定 Redis 時計數在所有副本間共用，否則只在本實例內有效。
+    pub async fn create_rate_limiter(
+        config: &Config,
+        observability: DynObservability,
+    ) -> Result<Option<Arc<RateLimiter>>, Box<dyn std::error::Error>> {
+        let rate_limit = &config.rate_limit;
+        if !rate_limit.enabled {
+            return Ok(None);
+        }
+
+        let store: DynRateLimitStore = match Self::connect_redis(config).await? {
+            Some(conn) => Arc::new(RedisRateLimitStore::new(
+                conn,
+                format!("{}:ratelimit", config.redis.namespace),
+            )),
+            None => Arc::new(InMemoryRateLimitStore::new()),
+        };
+
+        let policies = rate_limit
+            .policies
+            .iter()
+            .map(|policy| {
+                RateLimitPolicy::new(
+                    policy.route.clone(),
+                    rate_limit_key_kind(policy.key),
+                    RateLimitQuota::new(policy.requests, Duration::from_secs(policy.window_secs)),
+                )
+                .with_methods(
+                    policy
+                        .methods
+                        .iter()
+                        .filter_map(|method| method.parse().ok())
+                        .collect(),
+                )
+            })
+            .chain(std::iter::once(RateLimitPolicy::new(
+                "*",
+                rate_limit_key_kind(rate_limit.key),
+                RateLimitQuota::new(
+                    rate_limit.requests,
+                    Duration::from_secs(rate_limit.window_secs),
+                ),
+            )))
+            .collect();
+
+        Ok(Some(Arc::new(
+            RateLimiter::new(store, observability)
+                .with_policies(policies)
+                .with_trusted_proxy_hops(usize::from(rate_limit.trusted_proxy_hops)),
+        )))
+    }
+
+    /// 建立冪等鍵中介層的狀態；停用或容器沒有記錄儲存時回傳 `None`
+    pub fn create_idempotency_guard(
+        config: &Config,
+        store: Option<DynIdempotencyStore>,
+    ) -> Option<Arc<IdempotencyGuard>> {
+        let idempotency = &config.idempotency;
+        if !idempotency.enabled {
+            return None;
+        }
+        Some(Arc::new(
+            IdempotencyGuard::new(store?)
+                .with_ttl(Duration::from_secs(idempotency.ttl_secs))
+                .with_lock_timeout(Duration::from_secs(idempotency.lock_timeout_secs))
+                .with_max_request_bytes(idempotency.max_request_bytes)
+                .with_max_response_bytes(idempotency.max_response_bytes)
+                .with_exempt_routes(idempotency.exempt_routes.clone())
+                // 未啟用 JWT 驗證時多數呼叫者是匿名的，改以租戶區隔冪等鍵
+                .with_anonymous_callers(!config.auth.is_enabled()),
+        ))
+    }
+
+    /// 建立請求保護中介層的狀態；所有限制皆為 0 且沒有路由覆寫時回傳 `None`
+    pub fn create_request_limits(
+        config: &Config,
+        observability: DynObservability,
+    ) -> Option<Arc<RequestLimits>> {
+        let limits = &config.request_limits;
+        if limits.timeout_ms == 0
+            && limits.max_body_bytes == 0
+            && limits.max_concurrency == 0
+            && limits.routes.is_empty()
+        {
+            return None;
+        }
+        let routes = limits
+            .routes
+            .iter()
+            .map(|route| {
+                let mut limits = RouteLimits::new(route.route.clone()).with_methods(
+                    route
+                        .methods
+                        .iter()
+                        .filter_map(|method| method.parse().ok())
+                        .collect(),
+                );
+                if let Some(timeout_ms) = route.timeout_ms {
+                    limits = limits.with_timeout(timeout_from_ms(timeout_ms));
+                }
+                if let Some(bytes) = route.max_body_bytes {
+                    limits = limits.with_max_body_bytes(non_zero(bytes));
+                }
+                limits
+            })
+            .collect();
+        Some(Arc::new(
+            RequestLimits::new(observability)
+                .with_timeout(timeout_from_ms(limits.timeout_ms))
+                .with_max_body_bytes(non_zero(limits.max_body_bytes))
+                .with_max_concurrency(non_zero(limits.max_concurrency))
+                .with_routes(routes),
+        ))
+    }
+
+    /// 建立 CORS 層；未啟用時回傳 `None`
+    pub fn create_cors_layer(
+        config: &Config,
+    ) -> Result<Option<CorsLayer>, Box<dyn std::error::Error>> {
+        let cors = &config.cors;
+        if !cors.enabled {
+            return Ok(None);
+        }
+        let origins = cors
+            .allowed_origins
+            .iter()
+            .map(|origin| OriginPattern::parse(origin))
+            .collect::<Result<Vec<_>, _>>()?;
+        let methods = if cors.allowed_methods.iter().any(|method| method == "*") {
+            None
+        } else {
+            Some(
+                cors.allowed_methods
+                    .iter()
+                    .map(|method| method.parse())
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+        };
+        let headers = if cors.allowed_headers.iter().any(|header| header == "*") {
+            None
+        } else {
+            Some(
+                cors.allowed_headers
+                    .iter()
+                    .map(|header| header.parse())
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+        };
+        let expose_headers = cors
+            .expose_headers
+            .iter()
+            .map(|header| header.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(
+            CorsPolicy::new(origins)
+                .with_methods(methods)
+                .with_headers(headers)
+                .with_expose_headers(expose_headers)
+                .with_credentials(cors.allow_credentials)
+                .with_max_age(
+                    (cors.max_age_secs > 0).then(|| Duration::from_secs(cors.max_age_secs)),
+                )
+                .layer(),
+        ))
+    }
+
+    /// 建立回應壓縮層；未啟用時回傳 `None`
+    pub fn create_compression_layer(config: &Config) -> Option<CompressionLayer<impl Predicate>> {
+        let compression = &config.compression;
+        if !compression.enabled {
+            return None;
+        }
+        let enabled = |algorithm| compression.algorithms.contains(&algorithm);
+        Some(
+            CompressionLayer::new()
+                .gzip(enabled(CompressionAlgorithm::Gzip))
+                .br(enabled(CompressionAlgorithm::Br))
+                .zstd(enabled(CompressionAlgorithm::Zstd))
+                .compress_when(
+                    SizeAbove::new(compression.min_size_bytes)
+                        .and(NotForContentType::GRPC)
+                        .and(NotForContentType::IMAGES)
+                        .and(NotForContentType::SSE),
+                ),
+        )
+    }
+
+    /// 建立租戶解析器；未啟用多租戶時回傳 `None`（所有請求屬於預設租戶）
+    pub fn create_tenant_resolver(
+        config: &Config,
+    ) -> Result<Option<Arc<TenantResolver>>, Box<dyn std::error::Error>> {
+        let tenancy = &config.tenancy;
+        if !tenancy.enabled {
+            return Ok(None);
+        }
+        let mut sources = Vec::with_capacity(tenancy.sources.len());
+        for kind in &tenancy.sources {
+            sources.push(match kind {
+                TenantSourceKind::Header => TenantSource::Header(tenancy.header.parse()?),
+                TenantSourceKind::Subdomain => TenantSource::Subdomain {
+                    base_domain: tenancy
+                        .base_domain
+                        .clone()
+                        .ok_or("The subdomain tenant source requires tenancy.base_domain")?,
+                },
+                TenantSourceKind::JwtClaim => TenantSource::JwtClaim(tenancy.jwt_claim.clone()),
+            });
+        }
+        let default_tenant = tenancy
+            .default_tenant
+            .as_deref()
+            .map(TenantId::parse)
+            .transpose()?;
+        Ok(Some(Arc::new(
+            TenantResolver::new(sources).with_default_tenant(default_tenant),
+        )))
+    }
+
+    /// 連線到 Redis；未設定時回傳 `None`
+    async fn connect_redis(
+        config: &Config,
+    ) -> Result<Option<ConnectionManager>, Box<dyn std::error::Error>> {
+        match &config.redis.url {
+            Some(url) => Ok(Some(infra_cache_redis::connect(url).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 建立用例管線；個別設定未填的欄位沿用預設值
+    fn create_use_case_pipeline(
+        config: &Config,
+        observability: DynObservability,
+    ) -> UseCasePipeline {
+        let use_cases = &config.use_cases;
+        let defaults = UseCaseOptions {
+            timeout: use_cases.timeout_ms.map(Duration::from_millis),
+            max_attempts: use_cases.max_attempts,
+            retry_backoff: Duration::from_millis(use_cases.retry_backoff_ms),
+        };
+        use_cases.overrides.iter().fold(
+            UseCasePipeline::new(observability).with_defaults(defaults),
+            |pipeline, (name, case)| {
+                pipeline.with_options(
+                    name.clone(),
+                    UseCaseOptions {
+                        timeout: case
+                            .timeout_ms
+                            .map(Duration::from_millis)
+                            .or(defaults.timeout),
+                        max_attempts: case.max_attempts.unwrap_or(defaults.max_attempts),
+                        retry_backoff: case
+                            .retry_backoff_ms
+                            .map_or(defaults.retry_backoff, Duration::from_millis),
+                    },
+                )
+            },
+        )
+    }
+
+    fn create_outbox_relay(config: &Config, repo: &PostgresUserRepository) -> Arc<OutboxRelay> {
+        let store = Arc::new(PostgresOutbox::new(repo.pool().clone()));
+        let publisher = Arc::new(LogEventPublisher::new());
+        Arc::new(
+            OutboxRelay::new(store, publisher, config.outbox_batch_size)
+                .with_max_attempts(config.outbox_max_attempts),
+        )
+    }
+
+    fn create_user_purger(config: &Config, user_repo: DynUserRepo) -> Arc<DeletedUserPurger> {
+        let retention =
+            Duration::from_secs(u64::from(config.deleted_user_retention_days) * 24 * 60 * 60);
+        Arc::new(DeletedUserPurger::new(user_repo, retention))
+    }
+
+    fn create_id_generator(config: &Config) -> Result<DynIdGenerator, Box<dyn std::error::Error>> {
+        Ok(match config.id_strategy {
+            IdStrategy::UuidV7 => Arc::new(UuidV7Generator),
+            IdStrategy::Ulid => Arc::new(UlidGenerator::default()),
+            IdStrategy::Snowflake => Arc::new(
+                SnowflakeGenerator::new(config.snowflake_worker_id)
+                    .ok_or("snowflake_worker_id must be between 0 and 1023")?,
+            ),
+        })
+    }
+
+    fn create_authorization_policy(config: &Config) -> DynAuthorizationPolicy {
+        let authorization = &config.authorization;
+        if !authorization.enabled {
+            return Arc::new(AllowAllPolicy);
+        }
+
+        let rules = authorization
+            .rules
+            .iter()
+            .map(|rule| AttributeRule {
+                permissions: rule.permissions.clone(),
+                conditions: rule
+                    .owner
+                    .then_some(RuleCondition::Owner)
+                    .into_iter()
+                    .chain(rule.scope.clone().map(RuleCondition::Scope))
+                    .collect(),
+            })
+            .collect();
+        Arc::new(
+            RolePolicy::new(authorization.roles.clone())
+                .with_anonymous_permissions(authorization.anonymous_permissions.clone())
+                .with_rules(rules),
+        )
     }
 
     fn create_observability(_config: &Config) -> DynObservability {
@@ -40,3 +504,21 @@ impl DependencyFactory {
         Arc::new(Metrics::new(&telemetry_config))
     }
 }
+
+fn rate_limit_key_kind(key: RateLimitKey) -> RateLimitKeyKind {
+    match key {
+        RateLimitKey::Ip => RateLimitKeyKind::Ip,
+        RateLimitKey::ForwardedIp => RateLimitKeyKind::ForwardedIp,
+        RateLimitKey::ApiKey => RateLimitKeyKind::ApiKey,
+        RateLimitKey::User => RateLimitKeyKind::User,
+    }
+}
+
+/// 設定中以 0 代表不限制
+fn non_zero(value: usize) -> Option<usize> {
+    (value > 0).then_some(value)
+}
+
+fn timeout_from_ms(ms: u64) -> Option<Duration> {
+    (ms > 0).then(|| Duration::from_millis(ms))
+}
@@ -3,3 +3,4 @@ pub mod app;
 pub mod config;
 pub mod factory;
 pub mod state;
+pub mod workers;
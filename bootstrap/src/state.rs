@@ -1,4 +1,7 @@
-use application::{Container, HasObservability};
+use application::{
+    bus::{Bus, HasBus},
+    Container, HasObservability,
+};
 use contracts::ports::MetricsRegistry;
 use std::sync::Arc;
 
@@ -15,9 +18,9 @@ impl MetricsRegistry for AppState {
     }
 }
 
-impl application::use_cases::create_user::HasCreateUserUc for AppState {
-    fn create_user_uc(&self) -> Arc<dyn application::use_cases::create_user::CreateUserUseCase> {
-        self.container.create_user_uc()
+impl HasBus for AppState {
+    fn bus(&self) -> &Bus {
+        self.container.bus()
     }
 }
 
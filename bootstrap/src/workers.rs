@@ -0,0 +1,63 @@
+//! 背景工作 - 與 HTTP 服務一同運行的週期性任務
+
+use application::{outbox_relay::OutboxRelay, user_purge::DeletedUserPurger};
+use contracts::DynIdempotencyStore;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// 定期執行發件箱轉發；若一批已滿則立即處理下一批
+pub fn spawn_outbox_relay(relay: Arc<OutboxRelay>, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            loop {
+                match relay.relay_batch().await {
+                    Ok(published) if published as u32 >= relay.batch_size() => continue,
+                    Ok(_) => break,
+                    Err(e) => {
+                        tracing::error!(error = %e, "Outbox relay failed");
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// 定期永久清除超過保留期限的軟刪除用戶
+pub fn spawn_user_purger(purger: Arc<DeletedUserPurger>, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            match purger.purge_expired().await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!(purged, "Purged expired soft-deleted users"),
+                Err(e) => tracing::error!(error = %e, "Deleted user purge failed"),
+            }
+        }
+    })
+}
+
+/// 定期刪除過期的冪等鍵記錄
+pub fn spawn_idempotency_purger(store: DynIdempotencyStore, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            match store.purge_expired().await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!(purged, "Purged expired idempotency keys"),
+                Err(e) => tracing::error!(error = %e, "Idempotency key purge failed"),
+            }
+        }
+    })
+}
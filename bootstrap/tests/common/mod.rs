@@ -1,29 +1,71 @@
 use async_trait::async_trait;
-use contracts::{DomainError, ObservabilityPort, User, UserId};
+use contracts::{
+    AuditTimestamps, DomainError, ObservabilityPort, TenantId, User, UserId, UserListQuery,
+    UserName, UserScanQuery,
+};
 use domain::UserRepository;
 use std::sync::{
     atomic::{AtomicUsize, Ordering},
     Arc, Mutex,
 };
-use std::{future::Future, pin::Pin};
+use std::{future::Future, pin::Pin, time::SystemTime};
 
 #[derive(Default)]
 pub struct FakeUserRepository;
 
 impl UserRepository for FakeUserRepository {
+    fn for_tenant(&self, _tenant: &TenantId) -> Arc<dyn UserRepository> {
+        Arc::new(FakeUserRepository)
+    }
+
     fn find(
         &self,
         id: &UserId,
     ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
         let id = id.clone();
-        Box::pin(async move { User::new(id, "Test User".to_string()) })
+        let name = UserName::parse("Test User").expect("valid test name");
+        Box::pin(async move { Ok(User::reconstitute(id, name, 1)) })
+    }
+
+    fn find_including_deleted(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+        self.find(id)
+    }
+
+    fn list(
+        &self,
+        _query: UserListQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    fn scan(
+        &self,
+        _query: UserScanQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        Box::pin(async { Ok(Vec::new()) })
     }
 
     fn save(
         &self,
         _user: &User,
-    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
-        Box::pin(async { Ok(()) })
+    ) -> Pin<Box<dyn Future<Output = Result<AuditTimestamps, DomainError>> + Send + '_>> {
+        let now = SystemTime::now();
+        Box::pin(async move {
+            Ok(AuditTimestamps {
+                created_at: now,
+                updated_at: now,
+            })
+        })
+    }
+
+    fn purge_deleted_before(
+        &self,
+        _cutoff: SystemTime,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, DomainError>> + Send + '_>> {
+        Box::pin(async { Ok(0) })
     }
 
     fn shutdown(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
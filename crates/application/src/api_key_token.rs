@@ -0,0 +1,131 @@
+//=== API Key Tokens ===//
+
+use std::fmt;
+
+use contracts::ApiKeyId;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// 秘密部分的隨機位元組數
+const SECRET_BYTES: usize = 32;
+
+/// 交給呼叫端的 API 金鑰明文：`<金鑰 ID>_<秘密>`
+///
+/// 金鑰 ID 讓驗證時能直接以主鍵查詢，再以常數時間比對秘密的雜湊。
+pub struct ApiKeyToken {
+    pub id: ApiKeyId,
+    secret: String,
+}
+
+// 秘密不可出現在日誌或 panic 訊息中
+impl fmt::Debug for ApiKeyToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKeyToken")
+            .field("id", &self.id)
+            .field("secret", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl ApiKeyToken {
+    /// 為指定金鑰產生新的隨機秘密
+    pub fn generate(id: ApiKeyId) -> Self {
+        let mut bytes = [0u8; SECRET_BYTES];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Self {
+            id,
+            secret: hex::encode(bytes),
+        }
+    }
+
+    /// 解析呼叫端提供的明文；格式不符時回傳 `None`
+    pub fn parse(token: &str) -> Option<Self> {
+        let (id, secret) = token.rsplit_once('_')?;
+        let well_formed = secret.len() == SECRET_BYTES * 2
+            && secret
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+        if !well_formed {
+            return None;
+        }
+        Some(Self {
+            id: ApiKeyId::parse(id).ok()?,
+            secret: secret.to_string(),
+        })
+    }
+
+    /// 儲存用的秘密雜湊
+    pub fn secret_hash(&self) -> String {
+        hex::encode(Sha256::digest(self.secret.as_bytes()))
+    }
+
+    /// 以常數時間比對秘密與儲存的雜湊
+    pub fn matches(&self, secret_hash: &str) -> bool {
+        let expected = self.secret_hash();
+        expected.len() == secret_hash.len()
+            && expected
+                .bytes()
+                .zip(secret_hash.bytes())
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+
+    /// 明文（只在建立或輪替時回傳一次）
+    pub fn expose(&self) -> String {
+        format!("{}_{}", self.id, self.secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_hash_match() {
+        let token = ApiKeyToken::generate(ApiKeyId::from_u128(42));
+        let hash = token.secret_hash();
+        let plaintext = token.expose();
+
+        let parsed = ApiKeyToken::parse(&plaintext).unwrap();
+
+        assert!(plaintext.starts_with("key_"));
+        assert_eq!(parsed.id, ApiKeyId::from_u128(42));
+        assert!(parsed.matches(&hash));
+        assert!(!plaintext.contains(&hash));
+    }
+
+    #[test]
+    fn test_different_secrets_do_not_match() {
+        let id = ApiKeyId::from_u128(1);
+        let first = ApiKeyToken::generate(id.clone());
+        let second = ApiKeyToken::generate(id);
+
+        assert!(!second.matches(&first.secret_hash()));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_tokens() {
+        let valid = ApiKeyToken::generate(ApiKeyId::from_u128(1)).expose();
+        let (id, _) = valid.rsplit_once('_').unwrap();
+
+        for token in [
+            "",
+            "no-separator",
+            &format!("{id}_short"),
+            &format!("{id}_{}", "Z".repeat(64)),
+            &format!("usr_00000000000000000000000001_{}", "a".repeat(64)),
+        ] {
+            assert!(ApiKeyToken::parse(token).is_none(), "{token:?}");
+        }
+    }
+
+    #[test]
+    fn test_debug_redacts_secret() {
+        let token = ApiKeyToken::generate(ApiKeyId::from_u128(42));
+
+        let debug = format!("{token:?}");
+
+        assert!(!debug.contains(&token.secret));
+        assert!(debug.contains("[REDACTED]"));
+    }
+}
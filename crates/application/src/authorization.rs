@@ -0,0 +1,196 @@
+//=== Authorization Policies ===//
+
+use std::collections::HashMap;
+
+use contracts::{Action, AuthorizationPolicy, Caller, Principal, Resource};
+
+/// 不做任何限制的策略（未啟用授權時使用）
+#[derive(Debug, Default)]
+pub struct AllowAllPolicy;
+
+impl AuthorizationPolicy for AllowAllPolicy {
+    fn is_allowed(&self, _caller: &Caller, _action: Action, _resource: &Resource<'_>) -> bool {
+        true
+    }
+}
+
+/// 屬性型規則的條件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleCondition {
+    /// 呼叫者就是目標資源本身
+    Owner,
+    /// 呼叫者的 token 帶有指定 scope
+    Scope(String),
+}
+
+/// 屬性型規則 - 條件全部成立時，任何已驗證的呼叫者都擁有這些權限
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeRule {
+    pub permissions: Vec<String>,
+    pub conditions: Vec<RuleCondition>,
+}
+
+impl AttributeRule {
+    fn grants(&self, principal: &Principal, permission: &str, resource: &Resource<'_>) -> bool {
+        self.permissions
+            .iter()
+            .any(|pattern| permission_matches(pattern, permission))
+            && self.conditions.iter().all(|condition| match condition {
+                RuleCondition::Owner => resource.is_owned_by(principal),
+                RuleCondition::Scope(scope) => principal.has_scope(scope),
+            })
+    }
+}
+
+/// 角色型策略（RBAC），可附加屬性型規則（ABAC）
+///
+/// 權限名稱支援萬用字元：`*` 代表全部，`users:*` 代表 `users:` 開頭的所有權限。
+#[derive(Debug, Clone, Default)]
+pub struct RolePolicy {
+    roles: HashMap<String, Vec<String>>,
+    anonymous: Vec<String>,
+    rules: Vec<AttributeRule>,
+}
+
+impl RolePolicy {
+    pub fn new(roles: HashMap<String, Vec<String>>) -> Self {
+        Self {
+            roles,
+            ..Self::default()
+        }
+    }
+
+    /// 未帶憑證的呼叫者擁有的權限
+    pub fn with_anonymous_permissions(mut self, permissions: Vec<String>) -> Self {
+        self.anonymous = permissions;
+        self
+    }
+
+    pub fn with_rules(mut self, rules: Vec<AttributeRule>) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    fn role_grants(&self, principal: &Principal, permission: &str) -> bool {
+        principal
+            .roles
+            .iter()
+            .filter_map(|role| self.roles.get(role))
+            .flatten()
+            .any(|pattern| permission_matches(pattern, permission))
+    }
+}
+
+impl AuthorizationPolicy for RolePolicy {
+    fn is_allowed(&self, caller: &Caller, action: Action, resource: &Resource<'_>) -> bool {
+        let permission = action.permission();
+        match caller {
+            Caller::System => true,
+            Caller::Anonymous => self
+                .anonymous
+                .iter()
+                .any(|pattern| permission_matches(pattern, permission)),
+            Caller::Authenticated(principal) => {
+                self.role_grants(principal, permission)
+                    || self
+                        .rules
+                        .iter()
+                        .any(|rule| rule.grants(principal, permission, resource))
+            }
+        }
+    }
+}
+
+fn permission_matches(pattern: &str, permission: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => permission.starts_with(prefix),
+        None => pattern == permission,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::{CallerContext, DomainError, DynAuthorizationPolicy, UserId};
+    use std::sync::Arc;
+
+    fn policy() -> RolePolicy {
+        RolePolicy::new(HashMap::from([
+            ("admin".to_string(), vec!["users:*".to_string()]),
+            (
+                "viewer".to_string(),
+                vec!["users:read".to_string(), "users:list".to_string()],
+            ),
+        ]))
+        .with_rules(vec![AttributeRule {
+            permissions: vec!["users:read".to_string(), "users:update".to_string()],
+            conditions: vec![RuleCondition::Owner],
+        }])
+    }
+
+    fn caller(subject: &str, roles: &[&str]) -> Caller {
+        Caller::Authenticated(Principal::new(subject).with_roles(roles.iter().copied()))
+    }
+
+    #[test]
+    fn test_roles_grant_permissions_with_wildcards() {
+        let policy = policy();
+        let admin = caller("alice", &["admin"]);
+        let viewer = caller("bob", &["viewer"]);
+
+        assert!(policy.is_allowed(&admin, Action::DeleteUser, &Resource::Users));
+        assert!(policy.is_allowed(&viewer, Action::ListUsers, &Resource::Users));
+        assert!(!policy.is_allowed(&viewer, Action::CreateUser, &Resource::Users));
+        assert!(!policy.is_allowed(
+            &caller("eve", &["unknown"]),
+            Action::ListUsers,
+            &Resource::Users
+        ));
+    }
+
+    #[test]
+    fn test_owner_rule_only_applies_to_own_resource() {
+        let policy = policy();
+        let own_id = UserId::from_u128(7);
+        let other_id = UserId::from_u128(8);
+        let owner = caller(&own_id.to_string(), &[]);
+
+        assert!(policy.is_allowed(&owner, Action::UpdateUser, &Resource::User(&own_id)));
+        assert!(!policy.is_allowed(&owner, Action::UpdateUser, &Resource::User(&other_id)));
+        assert!(!policy.is_allowed(&owner, Action::DeleteUser, &Resource::User(&own_id)));
+    }
+
+    #[test]
+    fn test_scope_condition() {
+        let policy = RolePolicy::default().with_rules(vec![AttributeRule {
+            permissions: vec!["users:list".to_string()],
+            conditions: vec![RuleCondition::Scope("users:read".to_string())],
+        }]);
+        let with_scope = Caller::Authenticated(Principal::new("svc").with_scopes(["users:read"]));
+
+        assert!(policy.is_allowed(&with_scope, Action::ListUsers, &Resource::Users));
+        assert!(!policy.is_allowed(&caller("svc", &[]), Action::ListUsers, &Resource::Users));
+    }
+
+    #[test]
+    fn test_anonymous_and_system_callers() {
+        let policy: DynAuthorizationPolicy =
+            Arc::new(policy().with_anonymous_permissions(vec!["users:list".to_string()]));
+        let anonymous = CallerContext::anonymous();
+
+        assert!(policy
+            .authorize(&anonymous, Action::ListUsers, &Resource::Users)
+            .is_ok());
+        assert!(matches!(
+            policy.authorize(&anonymous, Action::CreateUser, &Resource::Users),
+            Err(DomainError::Forbidden { .. })
+        ));
+        assert!(policy
+            .authorize(
+                &CallerContext::system(),
+                Action::DeleteUser,
+                &Resource::Users
+            )
+            .is_ok());
+    }
+}
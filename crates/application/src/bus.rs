@@ -0,0 +1,200 @@
+//=== Command / Query Bus ===//
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::Debug,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use contracts::{CallerContext, DomainError};
+use thiserror::Error;
+
+use crate::{error::AppError, pipeline::UseCasePipeline};
+
+/// 可經由匯流排派送的命令或查詢
+///
+/// 管線重試時會重新派送同一則訊息，因此訊息必須可複製。
+pub trait Message: Debug + Clone + Send + Sync + 'static {
+    type Output: Send + 'static;
+
+    /// 用於 span、指標標籤與設定檔的用例名稱
+    const NAME: &'static str;
+
+    /// 重新執行不會產生額外副作用；只有冪等的訊息會被管線重試
+    const IDEMPOTENT: bool = false;
+
+    /// 記錄在用例 span 上的欄位，預設不記錄任何訊息內容
+    ///
+    /// 只能列出不含個人資料的欄位（ID、旗標、分頁參數等），名稱、電子郵件與憑證不得出現在這裡。
+    fn span_fields(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+}
+
+/// 訊息處理器 - 每種訊息只有一個處理器
+#[async_trait]
+pub trait Handler<M: Message>: Send + Sync {
+    async fn handle(&self, ctx: &CallerContext, msg: M) -> Result<M::Output, DomainError>;
+}
+
+/// 訊息種類，用來在啟動時檢查處理器是否都已註冊
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MessageKind {
+    type_id: TypeId,
+    name: &'static str,
+}
+
+impl MessageKind {
+    pub fn of<M: Message>() -> Self {
+        Self {
+            type_id: TypeId::of::<M>(),
+            name: M::NAME,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BusError {
+    #[error("No handler registered for: {}", .0.join(", "))]
+    MissingHandlers(Vec<&'static str>),
+}
+
+/// 命令與查詢匯流排 - 依訊息型別派送到註冊的處理器
+///
+/// 設定管線時，每次派送都經過管線（追蹤、指標、逾時與重試）。
+#[derive(Default)]
+pub struct Bus {
+    handlers: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    pipeline: Option<Arc<UseCasePipeline>>,
+}
+
+impl Bus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_pipeline(mut self, pipeline: Arc<UseCasePipeline>) -> Self {
+        self.pipeline = Some(pipeline);
+        self
+    }
+
+    /// 註冊處理器；同一種訊息重複註冊時以後者為準
+    pub fn register<M: Message>(&mut self, handler: Arc<dyn Handler<M>>) {
+        self.handlers.insert(TypeId::of::<M>(), Box::new(handler));
+    }
+
+    /// 以 builder 形式註冊處理器
+    pub fn with_handler<M: Message>(mut self, handler: Arc<dyn Handler<M>>) -> Self {
+        self.register(handler);
+        self
+    }
+
+    pub fn is_registered(&self, kind: &MessageKind) -> bool {
+        self.handlers.contains_key(&kind.type_id)
+    }
+
+    /// 確認所有訊息都有處理器，回報缺少的訊息名稱
+    pub fn ensure_registered(&self, kinds: &[MessageKind]) -> Result<(), BusError> {
+        let missing: Vec<_> = kinds
+            .iter()
+            .filter(|kind| !self.is_registered(kind))
+            .map(MessageKind::name)
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(BusError::MissingHandlers(missing))
+        }
+    }
+
+    /// 派送訊息並等待處理結果
+    pub async fn send<M: Message>(
+        &self,
+        ctx: &CallerContext,
+        msg: M,
+    ) -> Result<M::Output, AppError> {
+        let handler = self
+            .handlers
+            .get(&TypeId::of::<M>())
+            .and_then(|handler| handler.downcast_ref::<Arc<dyn Handler<M>>>())
+            .ok_or_else(|| {
+                AppError::Application(format!("No handler registered for {}", M::NAME))
+            })?;
+
+        let result = match &self.pipeline {
+            Some(pipeline) => {
+                pipeline
+                    .run(M::NAME, M::IDEMPOTENT, &msg.span_fields(), || {
+                        handler.handle(ctx, msg.clone())
+                    })
+                    .await
+            }
+            None => handler.handle(ctx, msg).await,
+        };
+        result.map_err(AppError::Domain)
+    }
+}
+
+/// 提供匯流排的 trait - 表現層只依賴這一個能力
+pub trait HasBus: Send + Sync {
+    fn bus(&self) -> &Bus;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct Ping(u32);
+
+    impl Message for Ping {
+        type Output = u32;
+        const NAME: &'static str = "ping";
+    }
+
+    #[derive(Debug, Clone)]
+    struct Unhandled;
+
+    impl Message for Unhandled {
+        type Output = ();
+        const NAME: &'static str = "unhandled";
+    }
+
+    struct Echo;
+
+    #[async_trait]
+    impl Handler<Ping> for Echo {
+        async fn handle(&self, _ctx: &CallerContext, msg: Ping) -> Result<u32, DomainError> {
+            Ok(msg.0 + 1)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_dispatches_by_message_type() {
+        let bus = Bus::new().with_handler::<Ping>(Arc::new(Echo));
+        let ctx = CallerContext::system();
+
+        assert_eq!(bus.send(&ctx, Ping(1)).await.unwrap(), 2);
+        assert!(matches!(
+            bus.send(&ctx, Unhandled).await,
+            Err(AppError::Application(_))
+        ));
+    }
+
+    #[test]
+    fn test_ensure_registered_lists_missing_handlers() {
+        let bus = Bus::new().with_handler::<Ping>(Arc::new(Echo));
+
+        assert!(bus.ensure_registered(&[MessageKind::of::<Ping>()]).is_ok());
+        assert_eq!(
+            bus.ensure_registered(&[MessageKind::of::<Ping>(), MessageKind::of::<Unhandled>()]),
+            Err(BusError::MissingHandlers(vec!["unhandled"]))
+        );
+    }
+}
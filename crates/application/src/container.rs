@@ -1,59 +1,284 @@
-use std::any::{Any, TypeId};
-use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::use_cases::create_user::{CreateUserUseCase, HasCreateUserUc, UserSvc};
-use contracts::ports::{DynObservability, DynUserRepo};
+use crate::bus::{Bus, BusError, Handler, HasBus, Message};
+use crate::outbox_relay::OutboxRelay;
+use crate::pipeline::UseCasePipeline;
+use crate::use_cases::{
+    self, authenticate_api_key::AuthenticateApiKeySvc, create_api_key::CreateApiKeySvc,
+    create_user::UserSvc, delete_user::DeleteUserSvc, export_users::ExportUsersSvc,
+    get_user::GetUserSvc, get_users::GetUsersSvc, import_users::ImportUsersSvc,
+    list_users::ListUsersSvc, restore_user::RestoreUserSvc, revoke_api_key::RevokeApiKeySvc,
+    rotate_api_key::RotateApiKeySvc, subscribe_user_events::SubscribeUserEventsSvc,
+    update_user::UpdateUserSvc,
+};
+use crate::user_events::{PublishingUnitOfWorkFactory, UserEventStream};
+use crate::user_purge::DeletedUserPurger;
+use contracts::{
+    ports::{DynApiKeyRepo, DynIdGenerator, DynObservability, DynUnitOfWorkFactory, DynUserRepo},
+    DynAuthorizationPolicy, DynIdempotencyStore,
+};
+use thiserror::Error;
 
 /// 改進的依賴注入容器
 pub struct Container {
     // 基礎設施依賴
     user_repo: DynUserRepo,
+    unit_of_work: DynUnitOfWorkFactory,
+    api_key_repo: DynApiKeyRepo,
     observability: DynObservability,
+    id_generator: DynIdGenerator,
+    policy: DynAuthorizationPolicy,
+    outbox_relay: Option<Arc<OutboxRelay>>,
+    user_purger: Option<Arc<DeletedUserPurger>>,
+    idempotency_store: Option<DynIdempotencyStore>,
 
-    // 用例註冊表
-    use_cases: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    // 命令與查詢匯流排
+    bus: Bus,
 }
 
-impl Container {
-    pub fn new(user_repo: DynUserRepo, observability: DynObservability) -> Self {
-        let mut container = Self {
-            user_repo: user_repo.clone(),
-            observability,
-            use_cases: HashMap::new(),
+/// 容器組裝錯誤
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ContainerError {
+    #[error("Missing required components: {}", .0.join(", "))]
+    MissingComponents(Vec<&'static str>),
+    #[error(transparent)]
+    Bus(#[from] BusError),
+}
+
+/// 延後到 `build` 時才註冊的處理器覆寫
+type HandlerOverride = Box<dyn FnOnce(&mut Bus) + Send>;
+
+/// 容器建構器 - 宣告必要元件，`build` 時一次回報所有缺少的元件
+///
+/// 測試可以沿用正式環境的組裝，只以 `with_*` 覆寫個別元件或用例處理器。
+#[derive(Default)]
+pub struct ContainerBuilder {
+    user_repo: Option<DynUserRepo>,
+    unit_of_work: Option<DynUnitOfWorkFactory>,
+    api_key_repo: Option<DynApiKeyRepo>,
+    observability: Option<DynObservability>,
+    id_generator: Option<DynIdGenerator>,
+    policy: Option<DynAuthorizationPolicy>,
+    pipeline: Option<UseCasePipeline>,
+    outbox_relay: Option<Arc<OutboxRelay>>,
+    user_purger: Option<Arc<DeletedUserPurger>>,
+    user_events: Option<Arc<UserEventStream>>,
+    idempotency_store: Option<DynIdempotencyStore>,
+    handler_overrides: Vec<HandlerOverride>,
+}
+
+impl ContainerBuilder {
+    pub fn with_user_repo(mut self, user_repo: DynUserRepo) -> Self {
+        self.user_repo = Some(user_repo);
+        self
+    }
+
+    pub fn with_unit_of_work(mut self, unit_of_work: DynUnitOfWorkFactory) -> Self {
+        self.unit_of_work = Some(unit_of_work);
+        self
+    }
+
+    pub fn with_api_key_repo(mut self, api_key_repo: DynApiKeyRepo) -> Self {
+        self.api_key_repo = Some(api_key_repo);
+        self
+    }
+
+    pub fn with_observability(mut self, observability: DynObservability) -> Self {
+        self.observability = Some(observability);
+        self
+    }
+
+    pub fn with_id_generator(mut self, id_generator: DynIdGenerator) -> Self {
+        self.id_generator = Some(id_generator);
+        self
+    }
+
+    pub fn with_authorization_policy(mut self, policy: DynAuthorizationPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// 設定用例管線；未設定時使用不含逾時與重試的預設管線
+    pub fn with_pipeline(mut self, pipeline: UseCasePipeline) -> Self {
+        self.pipeline = Some(pipeline);
+        self
+    }
+
+    /// 設定發件箱轉發器（由背景工作定期執行）
+    pub fn with_outbox_relay(mut self, relay: Arc<OutboxRelay>) -> Self {
+        self.outbox_relay = Some(relay);
+        self
+    }
+
+    /// 設定軟刪除用戶的清除工作（由背景工作定期執行）
+    pub fn with_user_purger(mut self, purger: Arc<DeletedUserPurger>) -> Self {
+        self.user_purger = Some(purger);
+        self
+    }
+
+    /// 設定用戶事件廣播；未設定時使用預設重播容量
+    pub fn with_user_events(mut self, events: Arc<UserEventStream>) -> Self {
+        self.user_events = Some(events);
+        self
+    }
+
+    /// 設定冪等鍵記錄（供 HTTP 層處理 `Idempotency-Key`）
+    pub fn with_idempotency_store(mut self, store: DynIdempotencyStore) -> Self {
+        self.idempotency_store = Some(store);
+        self
+    }
+
+    /// 以指定處理器取代預設的用例實作
+    pub fn with_handler<M: Message>(mut self, handler: Arc<dyn Handler<M>>) -> Self {
+        self.handler_overrides
+            .push(Box::new(move |bus: &mut Bus| bus.register(handler)));
+        self
+    }
+
+    /// 組裝容器；缺少必要元件或有命令、查詢沒有處理器時回傳錯誤
+    pub fn build(self) -> Result<Container, ContainerError> {
+        let missing: Vec<_> = [
+            ("user_repo", self.user_repo.is_none()),
+            ("unit_of_work", self.unit_of_work.is_none()),
+            ("api_key_repo", self.api_key_repo.is_none()),
+            ("observability", self.observability.is_none()),
+            ("id_generator", self.id_generator.is_none()),
+            ("authorization_policy", self.policy.is_none()),
+        ]
+        .into_iter()
+        .filter_map(|(name, is_missing)| is_missing.then_some(name))
+        .collect();
+        let (
+            Some(user_repo),
+            Some(unit_of_work),
+            Some(api_key_repo),
+            Some(observability),
+            Some(id_generator),
+            Some(policy),
+        ) = (
+            self.user_repo,
+            self.unit_of_work,
+            self.api_key_repo,
+            self.observability,
+            self.id_generator,
+            self.policy,
+        )
+        else {
+            return Err(ContainerError::MissingComponents(missing));
         };
 
-        // 註冊預設用例
-        let create_user_uc: Arc<dyn CreateUserUseCase> = Arc::new(UserSvc::new(user_repo));
-        container.register_use_case(create_user_uc);
+        // 所有寫入都經由工作單元，提交成功後把事件發佈給訂閱者
+        let user_events = self.user_events.unwrap_or_default();
+        let unit_of_work: DynUnitOfWorkFactory = Arc::new(PublishingUnitOfWorkFactory::new(
+            unit_of_work,
+            user_events.clone(),
+        ));
 
-        container
+        let pipeline = self
+            .pipeline
+            .unwrap_or_else(|| UseCasePipeline::new(observability.clone()));
+        let mut bus = Bus::new()
+            .with_pipeline(Arc::new(pipeline))
+            // 用戶管理
+            .with_handler(Arc::new(UserSvc::new(
+                unit_of_work.clone(),
+                id_generator.clone(),
+                policy.clone(),
+            )))
+            .with_handler(Arc::new(GetUserSvc::new(user_repo.clone(), policy.clone())))
+            .with_handler(Arc::new(GetUsersSvc::new(
+                user_repo.clone(),
+                policy.clone(),
+            )))
+            .with_handler(Arc::new(ListUsersSvc::new(
+                user_repo.clone(),
+                policy.clone(),
+            )))
+            .with_handler(Arc::new(UpdateUserSvc::new(
+                unit_of_work.clone(),
+                policy.clone(),
+            )))
+            .with_handler(Arc::new(DeleteUserSvc::new(
+                unit_of_work.clone(),
+                policy.clone(),
+            )))
+            .with_handler(Arc::new(RestoreUserSvc::new(
+                user_repo.clone(),
+                unit_of_work.clone(),
+                policy.clone(),
+            )))
+            .with_handler(Arc::new(SubscribeUserEventsSvc::new(
+                user_events,
+                policy.clone(),
+            )))
+            .with_handler(Arc::new(ImportUsersSvc::new(
+                unit_of_work.clone(),
+                id_generator.clone(),
+                policy.clone(),
+            )))
+            .with_handler(Arc::new(ExportUsersSvc::new(
+                user_repo.clone(),
+                policy.clone(),
+            )))
+            // API 金鑰管理
+            .with_handler(Arc::new(CreateApiKeySvc::new(
+                api_key_repo.clone(),
+                id_generator.clone(),
+                policy.clone(),
+            )))
+            .with_handler(Arc::new(RotateApiKeySvc::new(
+                api_key_repo.clone(),
+                policy.clone(),
+            )))
+            .with_handler(Arc::new(RevokeApiKeySvc::new(
+                api_key_repo.clone(),
+                policy.clone(),
+            )))
+            .with_handler(Arc::new(AuthenticateApiKeySvc::new(api_key_repo.clone())));
+        for register in self.handler_overrides {
+            register(&mut bus);
+        }
+        bus.ensure_registered(&use_cases::messages())?;
+
+        Ok(Container {
+            user_repo,
+            unit_of_work,
+            api_key_repo,
+            observability,
+            id_generator,
+            policy,
+            outbox_relay: self.outbox_relay,
+            user_purger: self.user_purger,
+            idempotency_store: self.idempotency_store,
+            bus,
+        })
+    }
+}
+
+impl Container {
+    pub fn builder() -> ContainerBuilder {
+        ContainerBuilder::default()
     }
 
-    /// 註冊用例
-    pub fn register_use_case<T>(&mut self, use_case: Arc<T>)
-    where
-        T: Send + Sync + 'static + ?Sized,
-    {
-        self.use_cases.insert(TypeId::of::<T>(), Box::new(use_case));
+    /// 獲取發件箱轉發器
+    pub fn outbox_relay(&self) -> Option<Arc<OutboxRelay>> {
+        self.outbox_relay.clone()
     }
 
-    /// 獲取用例
-    pub fn get_use_case<T>(&self) -> Option<Arc<T>>
-    where
-        T: Send + Sync + 'static + ?Sized,
-    {
-        self.use_cases
-            .get(&TypeId::of::<T>())?
-            .downcast_ref::<Arc<T>>()
-            .cloned()
+    /// 獲取軟刪除用戶的清除工作
+    pub fn user_purger(&self) -> Option<Arc<DeletedUserPurger>> {
+        self.user_purger.clone()
+    }
+
+    /// 獲取冪等鍵記錄；未設定時不處理 `Idempotency-Key`
+    pub fn idempotency_store(&self) -> Option<DynIdempotencyStore> {
+        self.idempotency_store.clone()
     }
 }
 
-impl HasCreateUserUc for Container {
-    fn create_user_uc(&self) -> Arc<dyn CreateUserUseCase> {
-        self.get_use_case::<dyn CreateUserUseCase>()
-            .expect("CreateUserUseCase not registered")
+impl HasBus for Container {
+    fn bus(&self) -> &Bus {
+        &self.bus
     }
 }
 
@@ -68,6 +293,28 @@ impl HasObservability for Container {
     }
 }
 
+/// 提供 ID 生成器的 trait
+pub trait HasIdGenerator {
+    fn id_generator(&self) -> DynIdGenerator;
+}
+
+impl HasIdGenerator for Container {
+    fn id_generator(&self) -> DynIdGenerator {
+        self.id_generator.clone()
+    }
+}
+
+/// 提供授權策略的 trait
+pub trait HasAuthorizationPolicy {
+    fn authorization_policy(&self) -> DynAuthorizationPolicy;
+}
+
+impl HasAuthorizationPolicy for Container {
+    fn authorization_policy(&self) -> DynAuthorizationPolicy {
+        self.policy.clone()
+    }
+}
+
 /// 提供儲存庫的 trait (內部使用)
 pub trait HasUserRepo {
     fn user_repo(&self) -> contracts::ports::DynUserRepo;
@@ -78,3 +325,117 @@ impl HasUserRepo for Container {
         self.user_repo.clone()
     }
 }
+
+/// 提供 API 金鑰儲存庫的 trait (內部使用)
+pub trait HasApiKeyRepo {
+    fn api_key_repo(&self) -> DynApiKeyRepo;
+}
+
+impl HasApiKeyRepo for Container {
+    fn api_key_repo(&self) -> DynApiKeyRepo {
+        self.api_key_repo.clone()
+    }
+}
+
+/// 提供工作單元工廠的 trait (內部使用)
+pub trait HasUnitOfWork {
+    fn unit_of_work(&self) -> contracts::ports::DynUnitOfWorkFactory;
+}
+
+impl HasUnitOfWork for Container {
+    fn unit_of_work(&self) -> contracts::ports::DynUnitOfWorkFactory {
+        self.unit_of_work.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        authorization::AllowAllPolicy, id_generation::SequenceIdGenerator,
+        use_cases::get_user::GetUserQuery,
+    };
+    use async_trait::async_trait;
+    use contracts::{CallerContext, DomainError, MockObservabilityPort, User, UserId, UserName};
+    use infra_memory::{
+        InMemoryApiKeyRepository, InMemoryUnitOfWorkFactory, InMemoryUserRepository,
+    };
+
+    fn builder() -> ContainerBuilder {
+        Container::builder()
+            .with_user_repo(Arc::new(InMemoryUserRepository::new()))
+            .with_unit_of_work(Arc::new(InMemoryUnitOfWorkFactory::default()))
+            .with_api_key_repo(Arc::new(InMemoryApiKeyRepository::new()))
+            .with_observability(Arc::new(MockObservabilityPort::new()))
+            .with_id_generator(Arc::new(SequenceIdGenerator::default()))
+            .with_authorization_policy(Arc::new(AllowAllPolicy))
+    }
+
+    struct FixedUser;
+
+    #[async_trait]
+    impl Handler<GetUserQuery> for FixedUser {
+        async fn handle(
+            &self,
+            _ctx: &CallerContext,
+            query: GetUserQuery,
+        ) -> Result<User, DomainError> {
+            Ok(User::reconstitute(
+                query.id,
+                UserName::parse("Fixed").unwrap(),
+                1,
+            ))
+        }
+    }
+
+    #[test]
+    fn test_build_lists_every_missing_component() {
+        let result = Container::builder()
+            .with_user_repo(Arc::new(InMemoryUserRepository::new()))
+            .with_authorization_policy(Arc::new(AllowAllPolicy))
+            .build();
+
+        let Err(error) = result else {
+            panic!("container built without required components");
+        };
+        assert_eq!(
+            error,
+            ContainerError::MissingComponents(vec![
+                "unit_of_work",
+                "api_key_repo",
+                "observability",
+                "id_generator",
+            ])
+        );
+        assert_eq!(
+            error.to_string(),
+            "Missing required components: unit_of_work, api_key_repo, observability, id_generator"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handler_override_replaces_default_use_case() {
+        let container = builder()
+            .with_pipeline(UseCasePipeline::new(Arc::new({
+                let mut observability = MockObservabilityPort::new();
+                observability.expect_on_use_case_end().return_const(());
+                observability
+            })))
+            .with_handler::<GetUserQuery>(Arc::new(FixedUser))
+            .build()
+            .unwrap();
+
+        // 預設的 GetUserSvc 會因儲存庫為空回傳 NotFound
+        let user = container
+            .bus()
+            .send(
+                &CallerContext::system(),
+                GetUserQuery {
+                    id: UserId::from_u128(1),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(user.name.as_str(), "Fixed");
+    }
+}
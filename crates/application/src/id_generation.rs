@@ -0,0 +1,200 @@
+//=== ID Generation Strategies ===//
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use contracts::ports::IdGenerator;
+use ulid::Ulid;
+use uuid::Uuid;
+
+/// UUIDv7 生成器 - 時間排序，預設策略
+#[derive(Debug, Default)]
+pub struct UuidV7Generator;
+
+impl IdGenerator for UuidV7Generator {
+    fn next_raw(&self) -> u128 {
+        Uuid::now_v7().as_u128()
+    }
+}
+
+/// ULID 生成器 - 同一毫秒內單調遞增
+#[derive(Default)]
+pub struct UlidGenerator {
+    inner: Mutex<ulid::Generator>,
+}
+
+impl IdGenerator for UlidGenerator {
+    fn next_raw(&self) -> u128 {
+        let mut generator = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        // 同一毫秒內的隨機部分耗盡時退回一般 ULID，放棄單調性而非失敗
+        generator.generate().unwrap_or_else(|_| Ulid::new()).0
+    }
+}
+
+/// Snowflake 生成器 - 64 位元：41 位元毫秒時間戳、10 位元工作節點、12 位元序號
+///
+/// 多個實例同時產生 ID 時，必須為每個實例設定不同的 `worker_id`。
+#[derive(Debug)]
+pub struct SnowflakeGenerator {
+    worker_id: u16,
+    state: Mutex<SnowflakeState>,
+}
+
+#[derive(Debug, Default)]
+struct SnowflakeState {
+    last_millis: u64,
+    sequence: u16,
+}
+
+impl SnowflakeGenerator {
+    /// 工作節點編號上限（不含）
+    pub const MAX_WORKERS: u16 = 1 << 10;
+    const MAX_SEQUENCE: u16 = (1 << 12) - 1;
+    /// 自訂紀元：2024-01-01T00:00:00Z
+    const EPOCH: Duration = Duration::from_millis(1_704_067_200_000);
+
+    /// 建立生成器；`worker_id` 超出範圍時回傳 `None`
+    pub fn new(worker_id: u16) -> Option<Self> {
+        (worker_id < Self::MAX_WORKERS).then(|| Self {
+            worker_id,
+            state: Mutex::new(SnowflakeState::default()),
+        })
+    }
+
+    fn current_millis() -> u64 {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH + Self::EPOCH)
+            .unwrap_or_default();
+        since_epoch.as_millis() as u64
+    }
+}
+
+impl IdGenerator for SnowflakeGenerator {
+    fn next_raw(&self) -> u128 {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        // 時鐘回撥時沿用上次的時間戳，以維持遞增
+        let mut millis = Self::current_millis().max(state.last_millis);
+        if millis == state.last_millis {
+            state.sequence = (state.sequence + 1) & Self::MAX_SEQUENCE;
+            if state.sequence == 0 {
+                // 本毫秒序號已用盡，等待下一毫秒
+                while millis <= state.last_millis {
+                    std::hint::spin_loop();
+                    millis = Self::current_millis();
+                }
+            }
+        } else {
+            state.sequence = 0;
+        }
+        state.last_millis = millis;
+
+        let id = (millis << 22) | (u64::from(self.worker_id) << 12) | u64::from(state.sequence);
+        u128::from(id)
+    }
+}
+
+/// 確定性序號生成器 - 依序產生 ID，供測試使用
+#[derive(Debug)]
+pub struct SequenceIdGenerator {
+    next: AtomicU64,
+}
+
+impl SequenceIdGenerator {
+    pub fn new(start: u64) -> Self {
+        Self {
+            next: AtomicU64::new(start),
+        }
+    }
+}
+
+impl Default for SequenceIdGenerator {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl IdGenerator for SequenceIdGenerator {
+    fn next_raw(&self) -> u128 {
+        u128::from(self.next.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::ports::{DynIdGenerator, UserId};
+    use std::{collections::HashSet, sync::Arc};
+
+    fn generate(generator: &dyn IdGenerator, count: usize) -> Vec<u128> {
+        (0..count).map(|_| generator.next_raw()).collect()
+    }
+
+    #[test]
+    fn test_uuid_v7_generator_produces_version_7() {
+        let raw = UuidV7Generator.next_raw();
+        assert_eq!(Uuid::from_u128(raw).get_version_num(), 7);
+    }
+
+    #[test]
+    fn test_ulid_generator_is_monotonic() {
+        let ids = generate(&UlidGenerator::default(), 1_000);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_snowflake_generator_is_unique_and_ordered() {
+        let generator = SnowflakeGenerator::new(7).unwrap();
+        let ids = generate(&generator, 10_000);
+
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids.iter().all(|id| (id >> 12) & 0x3ff == 7));
+        assert!(ids.iter().all(|&id| id <= u128::from(u64::MAX)));
+    }
+
+    #[test]
+    fn test_snowflake_generator_rejects_out_of_range_worker() {
+        assert!(SnowflakeGenerator::new(SnowflakeGenerator::MAX_WORKERS).is_none());
+    }
+
+    #[test]
+    fn test_sequence_generator_is_deterministic() {
+        let generator: DynIdGenerator = Arc::new(SequenceIdGenerator::new(10));
+
+        let first: UserId = generator.next_id();
+        let second: UserId = generator.next_id();
+
+        assert_eq!(first.as_u128(), 10);
+        assert_eq!(second.as_u128(), 11);
+        assert!(first.to_string().starts_with("usr_"));
+    }
+
+    #[test]
+    fn test_generators_are_unique_across_threads() {
+        let generators: Vec<DynIdGenerator> = vec![
+            Arc::new(UuidV7Generator),
+            Arc::new(UlidGenerator::default()),
+            Arc::new(SnowflakeGenerator::new(1).unwrap()),
+            Arc::new(SequenceIdGenerator::default()),
+        ];
+
+        for generator in generators {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    let generator = generator.clone();
+                    std::thread::spawn(move || generate(generator.as_ref(), 500))
+                })
+                .collect();
+            let ids: HashSet<u128> = handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect();
+            assert_eq!(ids.len(), 2_000);
+        }
+    }
+}
@@ -7,10 +7,19 @@
     unused
 )]
 
+pub mod api_key_token;
+pub mod authorization;
+pub mod bus;
 pub mod container;
 pub mod error;
-pub mod id_service;
+pub mod id_generation;
+pub mod outbox_relay;
+pub mod pipeline;
+pub(crate) mod unit_of_work;
 pub mod use_cases;
+pub mod user_cache;
+pub mod user_events;
+pub mod user_purge;
 
 // Re-export contracts for convenience
 pub use container::*;
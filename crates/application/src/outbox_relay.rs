@@ -0,0 +1,257 @@
+//=== Outbox Relay ===//
+
+use std::{
+    collections::HashSet,
+    time::{Duration, SystemTime},
+};
+
+use contracts::{AppError, DynEventPublisher, DynOutboxStore};
+
+/// 預設的最大發佈嘗試次數，超過後訊息改列死信
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 10;
+
+/// 第一次重試前的等待時間，之後每次加倍
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// 重試等待時間的上限
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(300);
+
+/// 發件箱轉發器 - 將已提交的領域事件送往 `EventPublisher`
+pub struct OutboxRelay {
+    store: DynOutboxStore,
+    publisher: DynEventPublisher,
+    batch_size: u32,
+    max_attempts: u32,
+    retry_backoff: Duration,
+}
+
+impl OutboxRelay {
+    pub fn new(store: DynOutboxStore, publisher: DynEventPublisher, batch_size: u32) -> Self {
+        Self {
+            store,
+            publisher,
+            batch_size,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+        }
+    }
+
+    /// 每則訊息最多嘗試發佈的次數（含第一次），之後改列死信
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// 第一次重試前的等待時間；之後每次加倍，最多等待五分鐘
+    pub fn with_retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// 每批最多轉發的訊息數量
+    pub fn batch_size(&self) -> u32 {
+        self.batch_size
+    }
+
+    /// 第 `attempts` 次失敗後的等待時間
+    fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.retry_backoff
+            .saturating_mul(factor)
+            .min(MAX_RETRY_BACKOFF)
+    }
+
+    /// 轉發一批訊息，回傳成功發佈的數量
+    ///
+    /// 發佈失敗的訊息依失敗次數延後重試，同批中同一聚合較晚的訊息跳過，避免亂序送達；
+    /// 其他聚合的訊息照常轉發。失敗達到上限的訊息改列死信，不再阻擋後續訊息。
+    pub async fn relay_batch(&self) -> Result<usize, AppError> {
+        let messages = self.store.claim_pending(self.batch_size).await?;
+        let mut published = 0;
+        let mut blocked = HashSet::new();
+
+        for message in messages {
+            let aggregate = (message.aggregate_type.clone(), message.aggregate_id.clone());
+            if blocked.contains(&aggregate) {
+                continue;
+            }
+            match self.publisher.publish(&message).await {
+                Ok(()) => {
+                    self.store.mark_published(message.id).await?;
+                    published += 1;
+                }
+                Err(e) => {
+                    let attempts = message.attempts + 1;
+                    if attempts >= self.max_attempts {
+                        tracing::error!(
+                            message_id = %message.id,
+                            event_type = %message.event_type,
+                            attempts,
+                            error = %e,
+                            "Outbox message dead-lettered"
+                        );
+                        self.store
+                            .mark_dead_lettered(message.id, &e.to_string())
+                            .await?;
+                    } else {
+                        let retry_at = SystemTime::now() + self.backoff(attempts);
+                        self.store
+                            .mark_failed(message.id, &e.to_string(), retry_at)
+                            .await?;
+                        blocked.insert(aggregate);
+                    }
+                }
+            }
+        }
+
+        Ok(published)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use contracts::{
+        DomainEvent, EventPublisher, InfraError, OutboxMessage, OutboxStore, UserId, UserName,
+    };
+    use domain::OutboxRepository;
+    use infra_memory::InMemoryOutbox;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct RecordingPublisher {
+        published: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for RecordingPublisher {
+        async fn publish(&self, message: &OutboxMessage) -> Result<(), InfraError> {
+            self.published
+                .lock()
+                .unwrap()
+                .push(message.aggregate_id.clone());
+            Ok(())
+        }
+    }
+
+    struct FailingPublisher;
+
+    #[async_trait]
+    impl EventPublisher for FailingPublisher {
+        async fn publish(&self, _message: &OutboxMessage) -> Result<(), InfraError> {
+            Err(InfraError::Network("broker unavailable".to_string()))
+        }
+    }
+
+    fn user_id(n: u8) -> UserId {
+        UserId::from_bytes([n; 16])
+    }
+
+    async fn outbox_with(ids: &[u8]) -> InMemoryOutbox {
+        let outbox = InMemoryOutbox::new();
+        let events: Vec<DomainEvent> = ids
+            .iter()
+            .map(|&id| DomainEvent::UserCreated {
+                user_id: user_id(id),
+                name: UserName::parse("Alice").unwrap(),
+                email: None,
+            })
+            .collect();
+        outbox.append(&events).await.unwrap();
+        outbox
+    }
+
+    #[tokio::test]
+    async fn test_relay_publishes_in_order() {
+        let outbox = outbox_with(&[1, 2, 3]).await;
+        let publisher = Arc::new(RecordingPublisher::default());
+        let relay = OutboxRelay::new(Arc::new(outbox.clone()), publisher.clone(), 2);
+
+        assert_eq!(relay.relay_batch().await.unwrap(), 2);
+        assert_eq!(relay.relay_batch().await.unwrap(), 1);
+        assert_eq!(relay.relay_batch().await.unwrap(), 0);
+
+        assert_eq!(
+            *publisher.published.lock().unwrap(),
+            [1, 2, 3].map(|n| user_id(n).to_string())
+        );
+        assert_eq!(outbox.pending_count(), 0);
+    }
+
+    /// 只有指定聚合的訊息發佈失敗
+    struct PoisonPublisher {
+        poison: String,
+        published: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for PoisonPublisher {
+        async fn publish(&self, message: &OutboxMessage) -> Result<(), InfraError> {
+            if message.aggregate_id == self.poison {
+                return Err(InfraError::Network("payload rejected".to_string()));
+            }
+            self.published
+                .lock()
+                .unwrap()
+                .push(message.aggregate_id.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_relay_backs_off_failed_message() {
+        let outbox = outbox_with(&[1, 2]).await;
+        let relay = OutboxRelay::new(Arc::new(outbox.clone()), Arc::new(FailingPublisher), 10)
+            .with_retry_backoff(Duration::from_secs(60));
+
+        assert_eq!(relay.relay_batch().await.unwrap(), 0);
+
+        let messages = outbox.messages();
+        assert_eq!(outbox.pending_count(), 2);
+        assert_eq!(
+            outbox.last_error(messages[0].id).as_deref(),
+            Some("Network error: broker unavailable")
+        );
+        // 等待期間不會重新認領
+        assert!(outbox.claim_pending(10).await.unwrap().is_empty());
+        assert_eq!(relay.backoff(1), Duration::from_secs(60));
+        assert_eq!(relay.backoff(2), Duration::from_secs(120));
+        assert_eq!(relay.backoff(30), MAX_RETRY_BACKOFF);
+    }
+
+    #[tokio::test]
+    async fn test_poison_message_is_dead_lettered_without_blocking_others() {
+        let outbox = InMemoryOutbox::new();
+        let events: Vec<DomainEvent> = [1, 2, 3, 1]
+            .into_iter()
+            .map(|id| DomainEvent::UserCreated {
+                user_id: user_id(id),
+                name: UserName::parse("Alice").unwrap(),
+                email: None,
+            })
+            .collect();
+        outbox.append(&events).await.unwrap();
+        let publisher = Arc::new(PoisonPublisher {
+            poison: user_id(2).to_string(),
+            published: Mutex::new(Vec::new()),
+        });
+        let relay = OutboxRelay::new(Arc::new(outbox.clone()), publisher.clone(), 10)
+            .with_max_attempts(3)
+            .with_retry_backoff(Duration::ZERO);
+
+        // 毒訊息失敗時，其他聚合（包含較晚的訊息）照常轉發
+        assert_eq!(relay.relay_batch().await.unwrap(), 3);
+        assert_eq!(relay.relay_batch().await.unwrap(), 0);
+        assert_eq!(relay.relay_batch().await.unwrap(), 0);
+
+        let poison = outbox.messages()[1].id;
+        assert!(outbox.is_dead_lettered(poison));
+        assert_eq!(outbox.pending_count(), 0);
+        assert!(outbox.claim_pending(10).await.unwrap().is_empty());
+        assert_eq!(
+            *publisher.published.lock().unwrap(),
+            [1, 3, 1].map(|n| user_id(n).to_string())
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//=== Use Case Pipeline ===//
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use contracts::{ports::DynObservability, DomainError};
+use tracing::{field::Empty, Instrument};
+
+/// 單一用例的執行選項
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UseCaseOptions {
+    /// 整次執行（含重試）的時間上限；`None` 表示不限制
+    pub timeout: Option<Duration>,
+    /// 最多執行次數，1 表示不重試
+    pub max_attempts: u32,
+    /// 第一次重試前的等待時間，之後每次加倍
+    pub retry_backoff: Duration,
+}
+
+impl Default for UseCaseOptions {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            max_attempts: 1,
+            retry_backoff: Duration::from_millis(50),
+        }
+    }
+}
+
+/// 用例管線 - 為每次用例呼叫加上 tracing span、耗時指標、逾時與重試
+///
+/// 由 `Bus` 在派送每則訊息時套用，個別用例不需要重複這些橫切邏輯。
+/// 只有宣告為冪等（`Message::IDEMPOTENT`）的訊息在 `DomainError::is_retryable` 的錯誤時重試，
+/// 且整個用例從頭執行。命令失敗時可能已經提交（例如提交後連線中斷），重試會重複寫入，因此一律不重試。
+/// span 只記錄用例名稱與訊息以 `Message::span_fields` 明確列出的欄位，避免個人資料寫入追蹤系統。
+pub struct UseCasePipeline {
+    observability: DynObservability,
+    defaults: UseCaseOptions,
+    overrides: HashMap<String, UseCaseOptions>,
+}
+
+impl UseCasePipeline {
+    pub fn new(observability: DynObservability) -> Self {
+        Self {
+            observability,
+            defaults: UseCaseOptions::default(),
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// 設定沒有個別設定的用例所使用的選項
+    pub fn with_defaults(mut self, defaults: UseCaseOptions) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// 設定指定用例的選項，名稱見 `Message::NAME`
+    pub fn with_options(mut self, use_case: impl Into<String>, options: UseCaseOptions) -> Self {
+        self.overrides.insert(use_case.into(), options);
+        self
+    }
+
+    pub fn options_for(&self, use_case: &str) -> UseCaseOptions {
+        self.overrides
+            .get(use_case)
+            .copied()
+            .unwrap_or(self.defaults)
+    }
+
+    /// 執行一次用例呼叫；`call` 在每次嘗試時重新建立用例的 future，
+    /// `idempotent` 為 `false` 時不論設定都只執行一次，`fields` 記錄在 span 的 `fields` 欄位
+    pub async fn run<T, F, Fut>(
+        &self,
+        use_case: &'static str,
+        idempotent: bool,
+        fields: &[(&'static str, String)],
+        mut call: F,
+    ) -> Result<T, DomainError>
+    where
+        F: FnMut() -> Fut + Send,
+        Fut: Future<Output = Result<T, DomainError>> + Send,
+        T: Send,
+    {
+        let options = self.options_for(use_case);
+        let max_attempts = if idempotent { options.max_attempts } else { 1 };
+        let span = tracing::info_span!(
+            "use_case",
+            use_case,
+            fields = Empty,
+            attempts = Empty,
+            outcome = Empty
+        );
+        if !fields.is_empty() {
+            span.record("fields", format_fields(fields));
+        }
+        let started = Instant::now();
+
+        let attempts = async {
+            let mut attempt = 1;
+            let mut backoff = options.retry_backoff;
+            loop {
+                match call().await {
+                    Err(e) if e.is_retryable() && attempt < max_attempts => {
+                        tracing::warn!(attempt, error = %e, "Retrying use case");
+                        tokio::time::sleep(backoff).await;
+                        backoff = backoff.saturating_mul(2);
+                        attempt += 1;
+                    }
+                    result => {
+                        tracing::Span::current().record("attempts", attempt);
+                        return result;
+                    }
+                }
+            }
+        };
+
+        let (result, outcome) = async {
+            match options.timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, attempts).await {
+                    Ok(result) => outcome(result),
+                    Err(_) => (
+                        Err(DomainError::Unavailable {
+                            message: format!(
+                                "{use_case} timed out after {}ms",
+                                timeout.as_millis()
+                            ),
+                        }),
+                        "timeout",
+                    ),
+                },
+                None => outcome(attempts.await),
+            }
+        }
+        .instrument(span.clone())
+        .await;
+
+        span.record("outcome", outcome);
+        self.observability
+            .on_use_case_end(use_case, outcome, started.elapsed().as_secs_f64())
+            .await;
+        result
+    }
+}
+
+/// 以 `名稱=值` 空白分隔的形式呈現 span 欄位
+fn format_fields(fields: &[(&'static str, String)]) -> String {
+    fields
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn outcome<T>(result: Result<T, DomainError>) -> (Result<T, DomainError>, &'static str) {
+    let outcome = if result.is_ok() { "ok" } else { "error" };
+    (result, outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::MockObservabilityPort;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    fn pipeline(outcome: &'static str, options: UseCaseOptions) -> UseCasePipeline {
+        let mut observability = MockObservabilityPort::new();
+        observability
+            .expect_on_use_case_end()
+            .withf(move |use_case, actual, _| use_case == "test" && actual == outcome)
+            .times(1)
+            .return_const(());
+        UseCasePipeline::new(Arc::new(observability)).with_options("test", options)
+    }
+
+    fn unavailable() -> DomainError {
+        DomainError::Unavailable {
+            message: "connection reset".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_retryable_errors_until_success() {
+        let pipeline = pipeline(
+            "ok",
+            UseCaseOptions {
+                max_attempts: 3,
+                retry_backoff: Duration::from_millis(1),
+                ..UseCaseOptions::default()
+            },
+        );
+        let calls = AtomicU32::new(0);
+
+        let result = pipeline
+            .run("test", true, &[], || async {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 | 1 => Err(unavailable()),
+                    _ => Ok(42),
+                }
+            })
+            .await;
+
+        assert_eq!(result, Ok(42));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_non_idempotent_messages() {
+        let pipeline = pipeline(
+            "error",
+            UseCaseOptions {
+                max_attempts: 3,
+                retry_backoff: Duration::from_millis(1),
+                ..UseCaseOptions::default()
+            },
+        );
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), _> = pipeline
+            .run("test", false, &[], || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(unavailable())
+            })
+            .await;
+
+        assert!(matches!(result, Err(DomainError::Unavailable { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_domain_errors() {
+        let pipeline = pipeline(
+            "error",
+            UseCaseOptions {
+                max_attempts: 3,
+                ..UseCaseOptions::default()
+            },
+        );
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), _> = pipeline
+            .run("test", true, &[], || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(DomainError::Conflict {
+                    message: "version".to_string(),
+                })
+            })
+            .await;
+
+        assert!(matches!(result, Err(DomainError::Conflict { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_returns_unavailable() {
+        let pipeline = pipeline(
+            "timeout",
+            UseCaseOptions {
+                timeout: Some(Duration::from_millis(10)),
+                ..UseCaseOptions::default()
+            },
+        );
+
+        let result = pipeline
+            .run("test", true, &[], || async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(())
+            })
+            .await;
+
+        assert!(matches!(result, Err(DomainError::Unavailable { .. })));
+    }
+
+    #[test]
+    fn test_span_fields_are_formatted_in_order() {
+        let fields = [("id", "usr_1".to_string()), ("dry_run", "true".to_string())];
+
+        assert_eq!(format_fields(&fields), "id=usr_1 dry_run=true");
+        assert_eq!(format_fields(&[]), "");
+    }
+}
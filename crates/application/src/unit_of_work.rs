@@ -0,0 +1,72 @@
+//=== Unit of Work Helpers ===//
+
+use contracts::{DomainError, UnitOfWorkFactory, User, UserId};
+
+/// 在同一個交易中儲存用戶並將其領域事件寫入發件箱
+///
+/// 任何一步失敗都會回滾，並回傳原始錯誤；成功提交後推進用戶的版本號
+/// 並帶入儲存庫寫入的稽核時間戳記。
+pub(crate) async fn save_user_with_events(
+    factory: &dyn UnitOfWorkFactory,
+    user: &mut User,
+) -> Result<(), DomainError> {
+    let events = user.take_events();
+    let uow = factory.begin().await?;
+
+    let result = async {
+        let audit = uow.users().save(user).await?;
+        uow.outbox().append(&events).await?;
+        Ok(audit)
+    }
+    .await;
+
+    match result {
+        Ok(audit) => {
+            uow.commit().await?;
+            user.mark_persisted(audit);
+            Ok(())
+        }
+        Err(e) => {
+            // 回滾失敗時交易也會隨連線釋放而中止，保留原始錯誤較有意義
+            let _ = uow.rollback().await;
+            Err(e)
+        }
+    }
+}
+
+/// 在同一個交易中載入用戶、套用變更並儲存其領域事件
+///
+/// 載入經由工作單元而非讀取快取，前置條件檢查一律比對儲存庫中的最新版本，
+/// 不會因快取中的舊資料誤判衝突。
+pub(crate) async fn modify_user_with_events<F>(
+    factory: &dyn UnitOfWorkFactory,
+    id: &UserId,
+    change: F,
+) -> Result<User, DomainError>
+where
+    F: FnOnce(&mut User) -> Result<(), DomainError> + Send,
+{
+    let uow = factory.begin().await?;
+
+    let result = async {
+        let mut user = uow.users().find(id).await?;
+        change(&mut user)?;
+        let events = user.take_events();
+        let audit = uow.users().save(&user).await?;
+        uow.outbox().append(&events).await?;
+        Ok((user, audit))
+    }
+    .await;
+
+    match result {
+        Ok((mut user, audit)) => {
+            uow.commit().await?;
+            user.mark_persisted(audit);
+            Ok(user)
+        }
+        Err(e) => {
+            let _ = uow.rollback().await;
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use std::{
+    fmt,
+    time::{Duration, SystemTime},
+};
+
+use crate::api_key_token::ApiKeyToken;
+use crate::bus::{Handler, Message};
+use async_trait::async_trait;
+use contracts::{
+    ports::{ApiKey, DynApiKeyRepo},
+    CallerContext, DomainError,
+};
+
+/// 最後使用時間的更新粒度，避免每個請求都寫入資料庫
+pub const LAST_USED_RESOLUTION: Duration = Duration::from_secs(60);
+
+/// 驗證呼叫端提供的金鑰明文，成功時回傳對應的金鑰
+#[derive(Clone)]
+pub struct AuthenticateApiKeyQuery {
+    pub token: String,
+}
+
+// 金鑰明文不得出現在 span 與日誌中
+impl fmt::Debug for AuthenticateApiKeyQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthenticateApiKeyQuery")
+            .field("token", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl Message for AuthenticateApiKeyQuery {
+    type Output = ApiKey;
+    const NAME: &'static str = "authenticate_api_key";
+    const IDEMPOTENT: bool = true;
+}
+
+// 具體實作
+
+pub struct AuthenticateApiKeySvc {
+    repo: DynApiKeyRepo,
+}
+
+impl AuthenticateApiKeySvc {
+    pub fn new(repo: DynApiKeyRepo) -> Self {
+        Self { repo }
+    }
+}
+
+/// 所有驗證失敗都回傳相同錯誤，不透露金鑰是否存在
+fn invalid_key() -> DomainError {
+    DomainError::NotFound {
+        message: "Invalid API key".to_string(),
+    }
+}
+
+#[async_trait]
+impl Handler<AuthenticateApiKeyQuery> for AuthenticateApiKeySvc {
+    async fn handle(
+        &self,
+        _ctx: &CallerContext,
+        query: AuthenticateApiKeyQuery,
+    ) -> Result<ApiKey, DomainError> {
+        let token = ApiKeyToken::parse(&query.token).ok_or_else(invalid_key)?;
+        // 只有「不存在」併入驗證失敗；儲存庫故障照實回報，避免把停機誤報為無效金鑰
+        let mut key = self.repo.find(&token.id).await.map_err(|e| match e {
+            DomainError::NotFound { .. } => invalid_key(),
+            other => other,
+        })?;
+
+        let now = SystemTime::now();
+        if !token.matches(&key.secret_hash) || key.ensure_usable(now).is_err() {
+            return Err(invalid_key());
+        }
+
+        let stale = key
+            .last_used_at
+            .is_none_or(|at| now.duration_since(at).unwrap_or_default() >= LAST_USED_RESOLUTION);
+        if stale {
+            // 最後使用時間僅供稽核參考，寫入失敗不影響這次驗證
+            if self.repo.record_usage(&key.id, now).await.is_ok() {
+                key.last_used_at = Some(now);
+            }
+        }
+
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::{ApiKeyId, ApiKeyRepository, TenantId};
+    use infra_memory::InMemoryApiKeyRepository;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Arc;
+
+    /// 每次查詢都回報儲存庫無法使用
+    struct UnavailableRepo;
+
+    impl ApiKeyRepository for UnavailableRepo {
+        fn find(
+            &self,
+            _id: &ApiKeyId,
+        ) -> Pin<Box<dyn Future<Output = Result<ApiKey, DomainError>> + Send + '_>> {
+            Box::pin(async {
+                Err(DomainError::Unavailable {
+                    message: "database is down".to_string(),
+                })
+            })
+        }
+
+        fn save(
+            &self,
+            _key: &ApiKey,
+        ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn record_usage(
+            &self,
+            _id: &ApiKeyId,
+            _at: SystemTime,
+        ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    async fn authenticate(svc: &AuthenticateApiKeySvc, token: &str) -> Result<ApiKey, DomainError> {
+        let query = AuthenticateApiKeyQuery {
+            token: token.to_string(),
+        };
+        svc.handle(&CallerContext::anonymous(), query).await
+    }
+
+    async fn setup(
+        expires_at: Option<SystemTime>,
+    ) -> (AuthenticateApiKeySvc, InMemoryApiKeyRepository, String) {
+        let repo = InMemoryApiKeyRepository::new();
+        let token = ApiKeyToken::generate(ApiKeyId::from_u128(1));
+        let key = ApiKey::issue(
+            token.id.clone(),
+            TenantId::default(),
+            "ci",
+            token.secret_hash(),
+            vec!["users:read".to_string()],
+            expires_at,
+            SystemTime::now() - Duration::from_secs(3_600),
+        )
+        .unwrap();
+        repo.save(&key).await.unwrap();
+        (
+            AuthenticateApiKeySvc::new(Arc::new(repo.clone())),
+            repo,
+            token.expose(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_valid_key_records_usage() {
+        let (svc, repo, token) = setup(None).await;
+
+        let key = authenticate(&svc, &token).await.unwrap();
+
+        assert_eq!(key.scopes, vec!["users:read".to_string()]);
+        assert!(repo.get(&key.id).unwrap().last_used_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_usage_is_recorded_at_most_once_per_resolution() {
+        let (svc, repo, token) = setup(None).await;
+        let first = authenticate(&svc, &token).await.unwrap().last_used_at;
+
+        authenticate(&svc, &token).await.unwrap();
+
+        assert_eq!(
+            repo.get(&ApiKeyId::from_u128(1)).unwrap().last_used_at,
+            first
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wrong_secret_revoked_and_expired_keys_are_rejected() {
+        let (svc, repo, token) = setup(Some(SystemTime::now() + Duration::from_secs(60))).await;
+        let forged = ApiKeyToken::generate(ApiKeyId::from_u128(1)).expose();
+        assert!(authenticate(&svc, &forged).await.is_err());
+        assert!(authenticate(&svc, "garbage").await.is_err());
+
+        let mut key = repo.get(&ApiKeyId::from_u128(1)).unwrap();
+        key.revoke(SystemTime::now()).unwrap();
+        repo.save(&key).await.unwrap();
+        assert!(matches!(
+            authenticate(&svc, &token).await,
+            Err(DomainError::NotFound { .. })
+        ));
+
+        let (svc, repo, token) = setup(None).await;
+        let mut key = repo.get(&ApiKeyId::from_u128(1)).unwrap();
+        key.expires_at = Some(SystemTime::now() - Duration::from_secs(1));
+        repo.save(&key).await.unwrap();
+        assert!(authenticate(&svc, &token).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_repository_outage_is_not_reported_as_invalid_key() {
+        let svc = AuthenticateApiKeySvc::new(Arc::new(UnavailableRepo));
+        let token = ApiKeyToken::generate(ApiKeyId::from_u128(1)).expose();
+
+        assert!(matches!(
+            authenticate(&svc, &token).await,
+            Err(DomainError::Unavailable { .. })
+        ));
+    }
+}
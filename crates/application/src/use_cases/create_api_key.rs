@@ -0,0 +1,128 @@
+use std::{fmt, time::SystemTime};
+
+use crate::api_key_token::ApiKeyToken;
+use crate::bus::{Handler, Message};
+use async_trait::async_trait;
+use contracts::{
+    ports::{ApiKey, ApiKeyId, DynApiKeyRepo, DynIdGenerator},
+    Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource,
+};
+
+#[derive(Debug, Clone)]
+pub struct CreateApiKeyCmd {
+    pub name: String,
+    pub scopes: Vec<String>,
+    /// `None` 表示永不過期
+    pub expires_at: Option<SystemTime>,
+}
+
+/// 剛發行（或輪替）的金鑰與只會出現這一次的明文
+pub struct IssuedApiKey {
+    pub key: ApiKey,
+    pub token: String,
+}
+
+impl fmt::Debug for IssuedApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IssuedApiKey")
+            .field("key", &self.key)
+            .field("token", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl Message for CreateApiKeyCmd {
+    type Output = IssuedApiKey;
+    const NAME: &'static str = "create_api_key";
+}
+
+// 具體實作
+
+pub struct CreateApiKeySvc {
+    repo: DynApiKeyRepo,
+    id_generator: DynIdGenerator,
+    policy: DynAuthorizationPolicy,
+}
+
+impl CreateApiKeySvc {
+    pub fn new(
+        repo: DynApiKeyRepo,
+        id_generator: DynIdGenerator,
+        policy: DynAuthorizationPolicy,
+    ) -> Self {
+        Self {
+            repo,
+            id_generator,
+            policy,
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<CreateApiKeyCmd> for CreateApiKeySvc {
+    async fn handle(
+        &self,
+        ctx: &CallerContext,
+        cmd: CreateApiKeyCmd,
+    ) -> Result<IssuedApiKey, DomainError> {
+        self.policy
+            .authorize(ctx, Action::CreateApiKey, &Resource::ApiKeys)?;
+
+        let id: ApiKeyId = self.id_generator.next_id();
+        let token = ApiKeyToken::generate(id.clone());
+        let key = ApiKey::issue(
+            id,
+            ctx.tenant().clone(),
+            cmd.name,
+            token.secret_hash(),
+            cmd.scopes,
+            cmd.expires_at,
+            SystemTime::now(),
+        )?;
+        self.repo.save(&key).await?;
+
+        Ok(IssuedApiKey {
+            key,
+            token: token.expose(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{authorization::AllowAllPolicy, id_generation::SequenceIdGenerator};
+    use contracts::ApiKeyRepository;
+    use infra_memory::InMemoryApiKeyRepository;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_create_api_key_stores_only_hash() {
+        let repo = InMemoryApiKeyRepository::new();
+        let svc = CreateApiKeySvc::new(
+            Arc::new(repo.clone()),
+            Arc::new(SequenceIdGenerator::default()),
+            Arc::new(AllowAllPolicy),
+        );
+
+        let issued = svc
+            .handle(
+                &CallerContext::system(),
+                CreateApiKeyCmd {
+                    name: "ci".to_string(),
+                    scopes: vec!["users:read".to_string()],
+                    expires_at: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let stored = repo.find(&issued.key.id).await.unwrap();
+        assert_eq!(stored.scopes, vec!["users:read".to_string()]);
+        assert!(issued.token.starts_with(&stored.id.to_string()));
+        assert!(!issued.token.contains(&stored.secret_hash));
+        assert!(ApiKeyToken::parse(&issued.token)
+            .unwrap()
+            .matches(&stored.secret_hash));
+    }
+}
@@ -1,48 +1,57 @@
-use std::sync::Arc;
-
-use crate::id_service::IdService;
+use crate::bus::{Handler, Message};
+use crate::unit_of_work::save_user_with_events;
 use async_trait::async_trait;
 use contracts::{
-    ports::{User, UserRepository},
-    DomainError,
+    ports::{DynIdGenerator, DynUnitOfWorkFactory, Email, User, UserId, UserName},
+    Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CreateUserCmd {
-    pub name: String,
-}
-pub trait HasCreateUserUc: Send + Sync {
-    fn create_user_uc(&self) -> Arc<dyn CreateUserUseCase>;
+    pub name: UserName,
+    pub email: Option<Email>,
 }
-
-#[async_trait]
-pub trait CreateUserUseCase: Send + Sync {
-    async fn exec(&self, cmd: CreateUserCmd) -> Result<User, DomainError>;
+impl Message for CreateUserCmd {
+    type Output = User;
+    const NAME: &'static str = "create_user";
 }
 
 // 具體實作
 
 pub struct UserSvc {
-    repo: Arc<dyn UserRepository>,
+    uow: DynUnitOfWorkFactory,
+    id_generator: DynIdGenerator,
+    policy: DynAuthorizationPolicy,
 }
 
 impl UserSvc {
-    pub fn new(repo: Arc<dyn UserRepository>) -> Self {
-        Self { repo }
+    pub fn new(
+        uow: DynUnitOfWorkFactory,
+        id_generator: DynIdGenerator,
+        policy: DynAuthorizationPolicy,
+    ) -> Self {
+        Self {
+            uow,
+            id_generator,
+            policy,
+        }
     }
 }
 
 #[async_trait]
-impl CreateUserUseCase for UserSvc {
-    async fn exec(&self, cmd: CreateUserCmd) -> Result<User, DomainError> {
+impl Handler<CreateUserCmd> for UserSvc {
+    async fn handle(&self, ctx: &CallerContext, cmd: CreateUserCmd) -> Result<User, DomainError> {
+        self.policy
+            .authorize(ctx, Action::CreateUser, &Resource::Users)?;
+
         // 1) 生成 ID
-        let user_id = IdService::generate_user_id();
+        let user_id: UserId = self.id_generator.next_id();
 
-        // 2) 建立 Domain 物件（使用 Domain 層的業務驗證）
-        let user = User::new(user_id, cmd.name)?;
+        // 2) 建立 Domain 物件（值物件已在建構時完成驗證）
+        let mut user = User::new(user_id, cmd.name, cmd.email);
 
-        // 3) 儲存
-        self.repo.save(&user).await?;
+        // 3) 在同一交易中儲存用戶與領域事件
+        save_user_with_events(self.uow.for_tenant(ctx.tenant()).as_ref(), &mut user).await?;
 
         Ok(user)
     }
@@ -51,13 +60,39 @@ impl CreateUserUseCase for UserSvc {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use contracts::{DomainError, User, UserId};
-    use domain::UserRepository;
-    use std::{future::Future, pin::Pin, sync::Arc};
+    use crate::authorization::AllowAllPolicy;
+    use crate::id_generation::SequenceIdGenerator;
+    use contracts::{
+        AuditTimestamps, DomainError, DomainEvent, OutboxRepository, TenantId, UnitOfWork,
+        UnitOfWorkFactory, UnitOfWorkFuture, UserListQuery, UserRepository, UserScanQuery,
+    };
+    use infra_memory::InMemoryUnitOfWorkFactory;
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+    };
+
+    /// 儲存失敗並記錄是否回滾的工作單元
+    #[derive(Clone, Default)]
+    struct FailingUnitOfWorkFactory {
+        rolled_back: Arc<AtomicBool>,
+    }
 
-    struct MockUserRepository;
+    struct FailingUnitOfWork {
+        rolled_back: Arc<AtomicBool>,
+    }
+
+    struct FailingUserRepository;
+
+    impl UserRepository for FailingUserRepository {
+        fn for_tenant(&self, _tenant: &TenantId) -> Arc<dyn UserRepository> {
+            Arc::new(FailingUserRepository)
+        }
 
-    impl UserRepository for MockUserRepository {
         fn find(
             &self,
             _id: &UserId,
@@ -69,11 +104,44 @@ mod tests {
             })
         }
 
+        fn find_including_deleted(
+            &self,
+            id: &UserId,
+        ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+            self.find(id)
+        }
+
+        fn list(
+            &self,
+            _query: UserListQuery,
+        ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+            Box::pin(async { Ok(Vec::new()) })
+        }
+
+        fn scan(
+            &self,
+            _query: UserScanQuery,
+        ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+            Box::pin(async { Ok(Vec::new()) })
+        }
+
         fn save(
             &self,
             _user: &User,
-        ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
-            Box::pin(async { Ok(()) })
+        ) -> Pin<Box<dyn Future<Output = Result<AuditTimestamps, DomainError>> + Send + '_>>
+        {
+            Box::pin(async {
+                Err(DomainError::InvalidOperation {
+                    message: "Database error".to_string(),
+                })
+            })
+        }
+
+        fn purge_deleted_before(
+            &self,
+            _cutoff: std::time::SystemTime,
+        ) -> Pin<Box<dyn Future<Output = Result<u64, DomainError>> + Send + '_>> {
+            Box::pin(async { Ok(0) })
         }
 
         fn shutdown(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
@@ -81,41 +149,130 @@ mod tests {
         }
     }
 
+    impl OutboxRepository for FailingUserRepository {
+        fn append(
+            &self,
+            _events: &[DomainEvent],
+        ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
+            Box::pin(async { panic!("events should not be appended after a failed save") })
+        }
+    }
+
+    impl UnitOfWork for FailingUnitOfWork {
+        fn users(&self) -> &dyn UserRepository {
+            &FailingUserRepository
+        }
+
+        fn outbox(&self) -> &dyn OutboxRepository {
+            &FailingUserRepository
+        }
+
+        fn commit(
+            self: Box<Self>,
+        ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>> {
+            Box::pin(async { panic!("commit should not be called") })
+        }
+
+        fn rollback(
+            self: Box<Self>,
+        ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>> {
+            self.rolled_back.store(true, Ordering::SeqCst);
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    impl UnitOfWorkFactory for FailingUnitOfWorkFactory {
+        fn begin(&self) -> UnitOfWorkFuture<'_> {
+            let uow = FailingUnitOfWork {
+                rolled_back: self.rolled_back.clone(),
+            };
+            Box::pin(async move { Ok(Box::new(uow) as Box<dyn UnitOfWork>) })
+        }
+
+        fn for_tenant(&self, _tenant: &TenantId) -> Arc<dyn UnitOfWorkFactory> {
+            Arc::new(self.clone())
+        }
+    }
+
     #[tokio::test]
     async fn test_create_user_success() {
         // Arrange
-        let mock_repo = MockUserRepository;
-        let use_case = UserSvc::new(Arc::new(mock_repo));
+        let uow = InMemoryUnitOfWorkFactory::default();
+        let use_case = UserSvc::new(
+            Arc::new(uow.clone()),
+            Arc::new(SequenceIdGenerator::default()),
+            Arc::new(AllowAllPolicy),
+        );
         let cmd = CreateUserCmd {
-            name: "Test User".to_string(),
+            name: UserName::parse("Test User").unwrap(),
+            email: None,
         };
 
         // Act
-        let result = use_case.exec(cmd).await;
+        let result = use_case.handle(&CallerContext::system(), cmd).await;
 
         // Assert
         assert!(result.is_ok());
         let user = result.unwrap();
-        assert_eq!(user.name, "Test User");
+        assert_eq!(user.name.as_str(), "Test User");
+        assert!(uow.users().find(&user.id).await.is_ok());
+        assert!(user.pending_events().is_empty());
+
+        let messages = uow.outbox().messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].event_type, "user.created");
+        assert_eq!(user.id.as_u128(), 1);
+        assert_eq!(messages[0].aggregate_id, user.id.to_string());
     }
 
     #[tokio::test]
-    async fn test_create_user_empty_name() {
+    async fn test_create_user_with_email() {
         // Arrange
-        let mock_repo = MockUserRepository;
-        let use_case = UserSvc::new(Arc::new(mock_repo));
+        let uow = InMemoryUnitOfWorkFactory::default();
+        let use_case = UserSvc::new(
+            Arc::new(uow.clone()),
+            Arc::new(SequenceIdGenerator::default()),
+            Arc::new(AllowAllPolicy),
+        );
         let cmd = CreateUserCmd {
-            name: "".to_string(),
+            name: UserName::parse("Test User").unwrap(),
+            email: Some(Email::parse("test@example.com").unwrap()),
         };
 
         // Act
-        let result = use_case.exec(cmd).await;
+        let user = use_case
+            .handle(&CallerContext::system(), cmd)
+            .await
+            .unwrap();
 
         // Assert
-        assert!(result.is_err());
-        match result.unwrap_err() {
-            DomainError::ValidationError { message } => assert!(message.contains("empty")),
-            _ => panic!("Expected validation error"),
-        }
+        let stored = uow.users().find(&user.id).await.unwrap();
+        assert_eq!(stored.email, user.email);
+        assert_eq!(
+            uow.outbox().messages()[0].payload["email"],
+            "test@example.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_user_rolls_back_on_save_failure() {
+        // Arrange
+        let uow = FailingUnitOfWorkFactory::default();
+        let use_case = UserSvc::new(
+            Arc::new(uow.clone()),
+            Arc::new(SequenceIdGenerator::default()),
+            Arc::new(AllowAllPolicy),
+        );
+        let cmd = CreateUserCmd {
+            name: UserName::parse("Test User").unwrap(),
+            email: None,
+        };
+
+        // Act
+        let result = use_case.handle(&CallerContext::system(), cmd).await;
+
+        // Assert
+        assert!(matches!(result, Err(DomainError::InvalidOperation { .. })));
+        assert!(uow.rolled_back.load(Ordering::SeqCst));
     }
 }
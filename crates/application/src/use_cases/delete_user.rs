@@ -0,0 +1,154 @@
+use std::time::SystemTime;
+
+use crate::bus::{Handler, Message};
+use crate::unit_of_work::modify_user_with_events;
+use async_trait::async_trait;
+use contracts::{
+    ports::{DynUnitOfWorkFactory, UserId},
+    Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource,
+};
+
+#[derive(Debug, Clone)]
+pub struct DeleteUserCmd {
+    pub id: UserId,
+    /// 呼叫端預期的版本；`None` 表示不做前置條件檢查
+    pub expected_version: Option<u64>,
+}
+
+impl Message for DeleteUserCmd {
+    type Output = ();
+    const NAME: &'static str = "delete_user";
+
+    fn span_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = vec![("id", self.id.to_string())];
+        if let Some(expected) = self.expected_version {
+            fields.push(("expected_version", expected.to_string()));
+        }
+        fields
+    }
+}
+
+// 具體實作
+
+/// 軟刪除用戶 - 資料保留到清除工作依保留期限移除為止
+pub struct DeleteUserSvc {
+    uow: DynUnitOfWorkFactory,
+    policy: DynAuthorizationPolicy,
+}
+
+impl DeleteUserSvc {
+    pub fn new(uow: DynUnitOfWorkFactory, policy: DynAuthorizationPolicy) -> Self {
+        Self { uow, policy }
+    }
+}
+
+#[async_trait]
+impl Handler<DeleteUserCmd> for DeleteUserSvc {
+    async fn handle(&self, ctx: &CallerContext, cmd: DeleteUserCmd) -> Result<(), DomainError> {
+        self.policy
+            .authorize(ctx, Action::DeleteUser, &Resource::User(&cmd.id))?;
+
+        modify_user_with_events(
+            self.uow.for_tenant(ctx.tenant()).as_ref(),
+            &cmd.id,
+            |user| {
+                if let Some(expected) = cmd.expected_version {
+                    user.ensure_version(expected)?;
+                }
+                user.delete(SystemTime::now())
+            },
+        )
+        .await
+        .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorization::{AllowAllPolicy, RolePolicy};
+    use contracts::{Principal, User, UserName, UserRepository};
+    use infra_memory::{InMemoryOutbox, InMemoryUnitOfWorkFactory, InMemoryUserRepository};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    async fn setup() -> (DeleteUserSvc, InMemoryUnitOfWorkFactory, UserId) {
+        let repo = InMemoryUserRepository::new();
+        let user = User::new(
+            UserId::from_bytes([1; 16]),
+            UserName::parse("Alice").unwrap(),
+            None,
+        );
+        repo.save(&user).await.unwrap();
+
+        let uow = InMemoryUnitOfWorkFactory::new(repo.clone(), InMemoryOutbox::new());
+        let svc = DeleteUserSvc::new(Arc::new(uow.clone()), Arc::new(AllowAllPolicy));
+        (svc, uow, user.id)
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_hides_it() {
+        let (svc, uow, id) = setup().await;
+
+        svc.handle(
+            &CallerContext::system(),
+            DeleteUserCmd {
+                id: id.clone(),
+                expected_version: Some(1),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            uow.users().find(&id).await,
+            Err(DomainError::NotFound { .. })
+        ));
+        let stored = uow.users().find_including_deleted(&id).await.unwrap();
+        assert!(stored.is_deleted());
+        assert_eq!(stored.version, 2);
+        assert_eq!(uow.outbox().messages()[0].event_type, "user.deleted");
+    }
+
+    #[tokio::test]
+    async fn test_delete_missing_user() {
+        let (svc, _uow, _id) = setup().await;
+
+        let result = svc
+            .handle(
+                &CallerContext::system(),
+                DeleteUserCmd {
+                    id: UserId::from_bytes([0; 16]),
+                    expected_version: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(DomainError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_delete_requires_permission() {
+        let (_svc, uow, id) = setup().await;
+        let policy = RolePolicy::new(HashMap::from([(
+            "viewer".to_string(),
+            vec!["users:read".to_string()],
+        )]));
+        let svc = DeleteUserSvc::new(Arc::new(uow.clone()), Arc::new(policy));
+        let viewer = CallerContext::authenticated(Principal::new("bob").with_roles(["viewer"]));
+
+        let result = svc
+            .handle(
+                &viewer,
+                DeleteUserCmd {
+                    id: id.clone(),
+                    expected_version: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(DomainError::Forbidden { .. })));
+        assert!(uow.users().find(&id).await.is_ok());
+        assert!(uow.outbox().messages().is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+use crate::bus::{Handler, Message};
+use async_trait::async_trait;
+use contracts::{
+    ports::{DynUserRepo, User, UserScanQuery},
+    Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource,
+};
+
+/// 匯出時每批讀取的用戶數量上限
+pub const MAX_EXPORT_BATCH_SIZE: u32 = 1000;
+
+/// 依 ID 順序匯出所有用戶；回傳的游標逐批讀取，不會一次載入整個資料表
+#[derive(Debug, Clone)]
+pub struct ExportUsersQuery {
+    pub include_deleted: bool,
+    pub batch_size: u32,
+}
+
+impl Message for ExportUsersQuery {
+    type Output = UserExport;
+    const NAME: &'static str = "export_users";
+    const IDEMPOTENT: bool = true;
+
+    fn span_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("include_deleted", self.include_deleted.to_string()),
+            ("batch_size", self.batch_size.to_string()),
+        ]
+    }
+}
+
+/// 匯出游標；每次 `next_batch` 以上一批最後的 ID 繼續讀取
+pub struct UserExport {
+    repo: DynUserRepo,
+    query: UserScanQuery,
+    done: bool,
+}
+
+impl UserExport {
+    /// 下一批用戶；讀完時回傳 `None`
+    pub async fn next_batch(&mut self) -> Result<Option<Vec<User>>, DomainError> {
+        if self.done {
+            return Ok(None);
+        }
+        let users = self.repo.scan(self.query.clone()).await?;
+        if users.len() < self.query.limit as usize {
+            self.done = true;
+        }
+        match users.last() {
+            Some(last) => {
+                self.query.after = Some(last.id.clone());
+                Ok(Some(users))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+// 具體實作
+
+pub struct ExportUsersSvc {
+    repo: DynUserRepo,
+    policy: DynAuthorizationPolicy,
+}
+
+impl ExportUsersSvc {
+    pub fn new(repo: DynUserRepo, policy: DynAuthorizationPolicy) -> Self {
+        Self { repo, policy }
+    }
+}
+
+#[async_trait]
+impl Handler<ExportUsersQuery> for ExportUsersSvc {
+    async fn handle(
+        &self,
+        ctx: &CallerContext,
+        query: ExportUsersQuery,
+    ) -> Result<UserExport, DomainError> {
+        self.policy
+            .authorize(ctx, Action::ListUsers, &Resource::Users)?;
+        if query.batch_size == 0 || query.batch_size > MAX_EXPORT_BATCH_SIZE {
+            return Err(DomainError::ValidationError {
+                message: format!("Batch size must be between 1 and {MAX_EXPORT_BATCH_SIZE}"),
+            });
+        }
+
+        Ok(UserExport {
+            repo: self.repo.for_tenant(ctx.tenant()),
+            query: UserScanQuery {
+                after: None,
+                limit: query.batch_size,
+                include_deleted: query.include_deleted,
+            },
+            done: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorization::AllowAllPolicy;
+    use contracts::{UserId, UserName, UserRepository};
+    use infra_memory::InMemoryUserRepository;
+    use std::{sync::Arc, time::SystemTime};
+
+    #[tokio::test]
+    async fn test_export_reads_in_id_order_batches() {
+        let repo = InMemoryUserRepository::new();
+        for id in [3, 1, 2] {
+            repo.save(&User::new(
+                UserId::from_u128(id),
+                UserName::parse("User").unwrap(),
+                None,
+            ))
+            .await
+            .unwrap();
+        }
+        let mut deleted = repo.find(&UserId::from_u128(2)).await.unwrap();
+        deleted.delete(SystemTime::now()).unwrap();
+        repo.save(&deleted).await.unwrap();
+
+        let use_case = ExportUsersSvc::new(Arc::new(repo), Arc::new(AllowAllPolicy));
+        let mut export = use_case
+            .handle(
+                &CallerContext::system(),
+                ExportUsersQuery {
+                    include_deleted: false,
+                    batch_size: 1,
+                },
+            )
+            .await
+            .unwrap();
+
+        let mut ids = Vec::new();
+        while let Some(batch) = export.next_batch().await.unwrap() {
+            assert!(batch.len() <= 1);
+            ids.extend(batch.into_iter().map(|u| u.id.as_u128()));
+        }
+        assert_eq!(ids, vec![1, 3]);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::bus::{Handler, Message};
+use async_trait::async_trait;
+use contracts::{
+    ports::{DynUserRepo, User, UserId},
+    Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource,
+};
+
+#[derive(Debug, Clone)]
+pub struct GetUserQuery {
+    pub id: UserId,
+}
+
+impl Message for GetUserQuery {
+    type Output = User;
+    const NAME: &'static str = "get_user";
+    const IDEMPOTENT: bool = true;
+
+    fn span_fields(&self) -> Vec<(&'static str, String)> {
+        vec![("id", self.id.to_string())]
+    }
+}
+
+// 具體實作
+
+pub struct GetUserSvc {
+    repo: DynUserRepo,
+    policy: DynAuthorizationPolicy,
+}
+
+impl GetUserSvc {
+    pub fn new(repo: DynUserRepo, policy: DynAuthorizationPolicy) -> Self {
+        Self { repo, policy }
+    }
+}
+
+#[async_trait]
+impl Handler<GetUserQuery> for GetUserSvc {
+    async fn handle(&self, ctx: &CallerContext, query: GetUserQuery) -> Result<User, DomainError> {
+        self.policy
+            .authorize(ctx, Action::ReadUser, &Resource::User(&query.id))?;
+
+        self.repo.for_tenant(ctx.tenant()).find(&query.id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorization::AllowAllPolicy;
+    use contracts::{UserName, UserRepository};
+    use infra_memory::InMemoryUserRepository;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_get_existing_user() {
+        let repo = InMemoryUserRepository::new();
+        let user = User::new(
+            UserId::from_bytes([1; 16]),
+            UserName::parse("Alice").unwrap(),
+            None,
+        );
+        repo.save(&user).await.unwrap();
+
+        let use_case = GetUserSvc::new(Arc::new(repo), Arc::new(AllowAllPolicy));
+        let found = use_case
+            .handle(
+                &CallerContext::system(),
+                GetUserQuery {
+                    id: user.id.clone(),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(found.name.as_str(), "Alice");
+        assert_eq!(found.version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_missing_user() {
+        let use_case = GetUserSvc::new(
+            Arc::new(InMemoryUserRepository::new()),
+            Arc::new(AllowAllPolicy),
+        );
+
+        let result = use_case
+            .handle(
+                &CallerContext::system(),
+                GetUserQuery {
+                    id: UserId::from_bytes([0; 16]),
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(DomainError::NotFound { .. })));
+    }
+}
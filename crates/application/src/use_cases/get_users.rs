@@ -0,0 +1,115 @@
+use crate::bus::{Handler, Message};
+use async_trait::async_trait;
+use contracts::{
+    ports::{DynUserRepo, User, UserId},
+    Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource,
+};
+
+/// 單次批次查詢最多的 ID 數量
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// 一次取得多個用戶；不存在或已刪除的用戶不會出現在結果中，順序不保證
+#[derive(Debug, Clone)]
+pub struct GetUsersQuery {
+    pub ids: Vec<UserId>,
+}
+
+impl Message for GetUsersQuery {
+    type Output = Vec<User>;
+    const NAME: &'static str = "get_users";
+    const IDEMPOTENT: bool = true;
+
+    fn span_fields(&self) -> Vec<(&'static str, String)> {
+        vec![("count", self.ids.len().to_string())]
+    }
+}
+
+// 具體實作
+
+pub struct GetUsersSvc {
+    repo: DynUserRepo,
+    policy: DynAuthorizationPolicy,
+}
+
+impl GetUsersSvc {
+    pub fn new(repo: DynUserRepo, policy: DynAuthorizationPolicy) -> Self {
+        Self { repo, policy }
+    }
+}
+
+#[async_trait]
+impl Handler<GetUsersQuery> for GetUsersSvc {
+    async fn handle(
+        &self,
+        ctx: &CallerContext,
+        query: GetUsersQuery,
+    ) -> Result<Vec<User>, DomainError> {
+        if query.ids.len() > MAX_BATCH_SIZE {
+            return Err(DomainError::ValidationError {
+                message: format!("At most {MAX_BATCH_SIZE} users can be fetched at once"),
+            });
+        }
+        for id in &query.ids {
+            self.policy
+                .authorize(ctx, Action::ReadUser, &Resource::User(id))?;
+        }
+
+        self.repo
+            .for_tenant(ctx.tenant())
+            .find_many(&query.ids)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorization::AllowAllPolicy;
+    use contracts::{UserName, UserRepository};
+    use infra_memory::InMemoryUserRepository;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_get_users_skips_missing() {
+        let repo = InMemoryUserRepository::new();
+        let alice = User::new(
+            UserId::from_bytes([1; 16]),
+            UserName::parse("Alice").unwrap(),
+            None,
+        );
+        repo.save(&alice).await.unwrap();
+
+        let use_case = GetUsersSvc::new(Arc::new(repo), Arc::new(AllowAllPolicy));
+        let found = use_case
+            .handle(
+                &CallerContext::system(),
+                GetUsersQuery {
+                    ids: vec![alice.id.clone(), UserId::from_bytes([2; 16])],
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, alice.id);
+    }
+
+    #[tokio::test]
+    async fn test_get_users_rejects_oversized_batch() {
+        let use_case = GetUsersSvc::new(
+            Arc::new(InMemoryUserRepository::new()),
+            Arc::new(AllowAllPolicy),
+        );
+
+        let result = use_case
+            .handle(
+                &CallerContext::system(),
+                GetUsersQuery {
+                    ids: vec![UserId::from_bytes([1; 16]); MAX_BATCH_SIZE + 1],
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(DomainError::ValidationError { .. })));
+    }
+}
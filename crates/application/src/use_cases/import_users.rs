@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+
+use crate::bus::{Handler, Message};
+use async_trait::async_trait;
+use contracts::{
+    ports::{DynIdGenerator, DynUnitOfWorkFactory, Email, User, UserId, UserName},
+    Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource,
+};
+
+/// 單次匯入命令最多的資料列數量；更大的檔案由呼叫端分批送出
+pub const MAX_IMPORT_BATCH: usize = 1000;
+
+/// 待匯入的一列原始資料，驗證規則與建立用戶相同
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportUserRow {
+    /// 來源檔案中的行號，用於回報結果
+    pub line: u64,
+    /// 沿用來源系統的 ID；未提供時產生新的 ID
+    pub id: Option<String>,
+    pub name: String,
+    pub email: Option<String>,
+}
+
+/// 匯入一批用戶；`dry_run` 時只驗證不寫入
+///
+/// 同一批中驗證通過的資料列在同一個交易中寫入，不合法的資料列只回報不影響其他列。
+#[derive(Debug, Clone)]
+pub struct ImportUsersCmd {
+    pub rows: Vec<ImportUserRow>,
+    pub dry_run: bool,
+}
+
+/// 單列的匯入結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportOutcome {
+    /// 已寫入
+    Imported(UserId),
+    /// 驗證通過，dry run 未寫入
+    Valid(UserId),
+    /// ID 已存在（包含已軟刪除的用戶），未寫入
+    AlreadyExists(UserId),
+    /// 驗證失敗
+    Invalid(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRowResult {
+    pub line: u64,
+    pub outcome: ImportOutcome,
+}
+
+impl Message for ImportUsersCmd {
+    type Output = Vec<ImportRowResult>;
+    const NAME: &'static str = "import_users";
+
+    fn span_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("rows", self.rows.len().to_string()),
+            ("dry_run", self.dry_run.to_string()),
+        ]
+    }
+}
+
+// 具體實作
+
+pub struct ImportUsersSvc {
+    uow: DynUnitOfWorkFactory,
+    id_generator: DynIdGenerator,
+    policy: DynAuthorizationPolicy,
+}
+
+impl ImportUsersSvc {
+    pub fn new(
+        uow: DynUnitOfWorkFactory,
+        id_generator: DynIdGenerator,
+        policy: DynAuthorizationPolicy,
+    ) -> Self {
+        Self {
+            uow,
+            id_generator,
+            policy,
+        }
+    }
+
+    /// 經由值物件與 `User::new` 建立用戶
+    fn build_user(&self, row: ImportUserRow) -> Result<User, DomainError> {
+        let id = match row.id {
+            Some(id) => UserId::parse(&id)?,
+            None => self.id_generator.next_id(),
+        };
+        let name = UserName::parse(row.name)?;
+        let email = row.email.map(Email::parse).transpose()?;
+        Ok(User::new(id, name, email))
+    }
+
+    /// 在呼叫者租戶的同一個交易中寫入用戶與其事件，回傳每位用戶是否實際寫入
+    async fn insert(
+        &self,
+        ctx: &CallerContext,
+        users: &mut [User],
+    ) -> Result<Vec<bool>, DomainError> {
+        let uow = self.uow.for_tenant(ctx.tenant()).begin().await?;
+
+        let result = async {
+            let audits = uow.users().insert_many(users).await?;
+            // 只有實際寫入的用戶才產生事件
+            let events: Vec<_> = users
+                .iter_mut()
+                .zip(&audits)
+                .filter(|(_, audit)| audit.is_some())
+                .flat_map(|(user, _)| user.take_events())
+                .collect();
+            uow.outbox().append(&events).await?;
+            Ok(audits)
+        }
+        .await;
+
+        match result {
+            Ok(audits) => {
+                uow.commit().await?;
+                for (user, audit) in users.iter_mut().zip(&audits) {
+                    if let Some(audit) = audit {
+                        user.mark_persisted(*audit);
+                    }
+                }
+                Ok(audits.iter().map(Option::is_some).collect())
+            }
+            Err(e) => {
+                let _ = uow.rollback().await;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<ImportUsersCmd> for ImportUsersSvc {
+    async fn handle(
+        &self,
+        ctx: &CallerContext,
+        cmd: ImportUsersCmd,
+    ) -> Result<Vec<ImportRowResult>, DomainError> {
+        self.policy
+            .authorize(ctx, Action::CreateUser, &Resource::Users)?;
+        if cmd.rows.len() > MAX_IMPORT_BATCH {
+            return Err(DomainError::ValidationError {
+                message: format!("At most {MAX_IMPORT_BATCH} rows can be imported at once"),
+            });
+        }
+
+        let mut results = Vec::with_capacity(cmd.rows.len());
+        let mut valid = Vec::new();
+        let mut seen = HashSet::new();
+        for row in cmd.rows {
+            let line = row.line;
+            let outcome = match self.build_user(row) {
+                Ok(user) if !seen.insert(user.id.clone()) => {
+                    ImportOutcome::Invalid(format!("Duplicate id {} in import", user.id))
+                }
+                Ok(user) => {
+                    let outcome = ImportOutcome::Valid(user.id.clone());
+                    valid.push((results.len(), user));
+                    outcome
+                }
+                Err(e) => ImportOutcome::Invalid(e.to_string()),
+            };
+            results.push(ImportRowResult { line, outcome });
+        }
+
+        if cmd.dry_run || valid.is_empty() {
+            return Ok(results);
+        }
+
+        let (indexes, mut users): (Vec<usize>, Vec<User>) = valid.into_iter().unzip();
+        let inserted = self.insert(ctx, &mut users).await?;
+        for ((index, user), inserted) in indexes.into_iter().zip(users).zip(inserted) {
+            results[index].outcome = if inserted {
+                ImportOutcome::Imported(user.id)
+            } else {
+                ImportOutcome::AlreadyExists(user.id)
+            };
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorization::AllowAllPolicy;
+    use crate::id_generation::SequenceIdGenerator;
+    use contracts::UserRepository;
+    use infra_memory::InMemoryUnitOfWorkFactory;
+    use std::sync::Arc;
+
+    fn row(line: u64, id: Option<&str>, name: &str, email: Option<&str>) -> ImportUserRow {
+        ImportUserRow {
+            line,
+            id: id.map(str::to_string),
+            name: name.to_string(),
+            email: email.map(str::to_string),
+        }
+    }
+
+    fn use_case(uow: &InMemoryUnitOfWorkFactory) -> ImportUsersSvc {
+        ImportUsersSvc::new(
+            Arc::new(uow.clone()),
+            Arc::new(SequenceIdGenerator::default()),
+            Arc::new(AllowAllPolicy),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_import_reports_each_row_and_skips_existing_ids() {
+        let uow = InMemoryUnitOfWorkFactory::default();
+        let existing = UserId::from_u128(42);
+        uow.users()
+            .save(&User::new(
+                existing.clone(),
+                UserName::parse("Existing").unwrap(),
+                None,
+            ))
+            .await
+            .unwrap();
+
+        let results = use_case(&uow)
+            .handle(
+                &CallerContext::system(),
+                ImportUsersCmd {
+                    rows: vec![
+                        row(1, None, "Ada", Some("ada@example.com")),
+                        row(2, None, "", None),
+                        row(3, None, "Bob", Some("not-an-email")),
+                        row(4, Some(&existing.to_string()), "Again", None),
+                    ],
+                    dry_run: false,
+                },
+            )
+            .await
+            .unwrap();
+
+        let ImportOutcome::Imported(ada) = &results[0].outcome else {
+            panic!("unexpected outcome {:?}", results[0]);
+        };
+        assert!(matches!(results[1].outcome, ImportOutcome::Invalid(_)));
+        assert_eq!(results[2].line, 3);
+        assert!(matches!(results[2].outcome, ImportOutcome::Invalid(_)));
+        assert_eq!(results[3].outcome, ImportOutcome::AlreadyExists(existing));
+
+        assert_eq!(uow.users().find(ada).await.unwrap().name.as_str(), "Ada");
+        assert_eq!(uow.users().len(), 2);
+        // 只有實際寫入的用戶產生事件
+        assert_eq!(uow.outbox().messages().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_validates_without_writing() {
+        let uow = InMemoryUnitOfWorkFactory::default();
+        let id = UserId::from_u128(7).to_string();
+
+        let results = use_case(&uow)
+            .handle(
+                &CallerContext::system(),
+                ImportUsersCmd {
+                    rows: vec![
+                        row(1, Some(&id), "Ada", None),
+                        row(2, Some(&id), "Ada again", None),
+                    ],
+                    dry_run: true,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            results[0].outcome,
+            ImportOutcome::Valid(UserId::from_u128(7))
+        );
+        assert!(matches!(results[1].outcome, ImportOutcome::Invalid(_)));
+        assert!(uow.users().is_empty());
+        assert!(uow.outbox().messages().is_empty());
+    }
+}
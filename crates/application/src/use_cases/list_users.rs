@@ -0,0 +1,118 @@
+use crate::bus::{Handler, Message};
+use async_trait::async_trait;
+use contracts::{
+    ports::{DynUserRepo, User, UserListQuery},
+    Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource,
+};
+
+/// 單頁最多回傳的用戶數量
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[derive(Debug, Clone)]
+pub struct ListUsersQuery {
+    pub limit: u32,
+    pub offset: u64,
+    pub include_deleted: bool,
+}
+
+impl Message for ListUsersQuery {
+    type Output = Vec<User>;
+    const NAME: &'static str = "list_users";
+    const IDEMPOTENT: bool = true;
+
+    fn span_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("limit", self.limit.to_string()),
+            ("offset", self.offset.to_string()),
+            ("include_deleted", self.include_deleted.to_string()),
+        ]
+    }
+}
+
+// 具體實作
+
+pub struct ListUsersSvc {
+    repo: DynUserRepo,
+    policy: DynAuthorizationPolicy,
+}
+
+impl ListUsersSvc {
+    pub fn new(repo: DynUserRepo, policy: DynAuthorizationPolicy) -> Self {
+        Self { repo, policy }
+    }
+}
+
+#[async_trait]
+impl Handler<ListUsersQuery> for ListUsersSvc {
+    async fn handle(
+        &self,
+        ctx: &CallerContext,
+        query: ListUsersQuery,
+    ) -> Result<Vec<User>, DomainError> {
+        self.policy
+            .authorize(ctx, Action::ListUsers, &Resource::Users)?;
+
+        self.repo
+            .for_tenant(ctx.tenant())
+            .list(UserListQuery {
+                limit: query.limit.clamp(1, MAX_PAGE_SIZE),
+                offset: query.offset,
+                include_deleted: query.include_deleted,
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorization::AllowAllPolicy;
+    use contracts::{UserId, UserName, UserRepository};
+    use infra_memory::InMemoryUserRepository;
+    use std::sync::Arc;
+    use std::time::SystemTime;
+
+    #[tokio::test]
+    async fn test_list_excludes_deleted_by_default() {
+        let repo = InMemoryUserRepository::new();
+        for id in 1..=3 {
+            let user = User::new(
+                UserId::from_bytes([id; 16]),
+                UserName::parse("A").unwrap(),
+                None,
+            );
+            repo.save(&user).await.unwrap();
+        }
+        let mut deleted = repo.find(&UserId::from_bytes([2; 16])).await.unwrap();
+        deleted.delete(SystemTime::now()).unwrap();
+        repo.save(&deleted).await.unwrap();
+
+        let svc = ListUsersSvc::new(Arc::new(repo), Arc::new(AllowAllPolicy));
+        let active = svc
+            .handle(
+                &CallerContext::system(),
+                ListUsersQuery {
+                    limit: 10,
+                    offset: 0,
+                    include_deleted: false,
+                },
+            )
+            .await
+            .unwrap();
+        let all = svc
+            .handle(
+                &CallerContext::system(),
+                ListUsersQuery {
+                    limit: 10,
+                    offset: 0,
+                    include_deleted: true,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(active.len(), 2);
+        assert!(active.iter().all(|u| !u.is_deleted()));
+        assert_eq!(all.len(), 3);
+    }
+}
@@ -1 +1,36 @@
+pub mod authenticate_api_key;
+pub mod create_api_key;
 pub mod create_user;
+pub mod delete_user;
+pub mod export_users;
+pub mod get_user;
+pub mod get_users;
+pub mod import_users;
+pub mod list_users;
+pub mod restore_user;
+pub mod revoke_api_key;
+pub mod rotate_api_key;
+pub mod subscribe_user_events;
+pub mod update_user;
+
+use crate::bus::MessageKind;
+
+/// 應用層提供的所有命令與查詢；啟動時確認每一則都有處理器
+pub fn messages() -> Vec<MessageKind> {
+    vec![
+        MessageKind::of::<create_user::CreateUserCmd>(),
+        MessageKind::of::<get_user::GetUserQuery>(),
+        MessageKind::of::<get_users::GetUsersQuery>(),
+        MessageKind::of::<list_users::ListUsersQuery>(),
+        MessageKind::of::<update_user::UpdateUserCmd>(),
+        MessageKind::of::<delete_user::DeleteUserCmd>(),
+        MessageKind::of::<restore_user::RestoreUserCmd>(),
+        MessageKind::of::<subscribe_user_events::SubscribeUserEventsQuery>(),
+        MessageKind::of::<import_users::ImportUsersCmd>(),
+        MessageKind::of::<export_users::ExportUsersQuery>(),
+        MessageKind::of::<create_api_key::CreateApiKeyCmd>(),
+        MessageKind::of::<rotate_api_key::RotateApiKeyCmd>(),
+        MessageKind::of::<revoke_api_key::RevokeApiKeyCmd>(),
+        MessageKind::of::<authenticate_api_key::AuthenticateApiKeyQuery>(),
+    ]
+}
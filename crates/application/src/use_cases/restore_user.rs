@@ -0,0 +1,137 @@
+use crate::bus::{Handler, Message};
+use crate::unit_of_work::save_user_with_events;
+use async_trait::async_trait;
+use contracts::{
+    ports::{DynUnitOfWorkFactory, DynUserRepo, User, UserId},
+    Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource,
+};
+
+#[derive(Debug, Clone)]
+pub struct RestoreUserCmd {
+    pub id: UserId,
+    /// 呼叫端預期的版本；`None` 表示不做前置條件檢查
+    pub expected_version: Option<u64>,
+}
+
+impl Message for RestoreUserCmd {
+    type Output = User;
+    const NAME: &'static str = "restore_user";
+
+    fn span_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = vec![("id", self.id.to_string())];
+        if let Some(expected) = self.expected_version {
+            fields.push(("expected_version", expected.to_string()));
+        }
+        fields
+    }
+}
+
+// 具體實作
+
+/// 還原尚未被清除的軟刪除用戶
+pub struct RestoreUserSvc {
+    repo: DynUserRepo,
+    uow: DynUnitOfWorkFactory,
+    policy: DynAuthorizationPolicy,
+}
+
+impl RestoreUserSvc {
+    pub fn new(
+        repo: DynUserRepo,
+        uow: DynUnitOfWorkFactory,
+        policy: DynAuthorizationPolicy,
+    ) -> Self {
+        Self { repo, uow, policy }
+    }
+}
+
+#[async_trait]
+impl Handler<RestoreUserCmd> for RestoreUserSvc {
+    async fn handle(&self, ctx: &CallerContext, cmd: RestoreUserCmd) -> Result<User, DomainError> {
+        self.policy
+            .authorize(ctx, Action::RestoreUser, &Resource::User(&cmd.id))?;
+
+        let mut user = self
+            .repo
+            .for_tenant(ctx.tenant())
+            .find_including_deleted(&cmd.id)
+            .await?;
+        if let Some(expected) = cmd.expected_version {
+            user.ensure_version(expected)?;
+        }
+
+        user.restore()?;
+        save_user_with_events(self.uow.for_tenant(ctx.tenant()).as_ref(), &mut user).await?;
+
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorization::AllowAllPolicy;
+    use contracts::{UserName, UserRepository};
+    use infra_memory::{InMemoryOutbox, InMemoryUnitOfWorkFactory, InMemoryUserRepository};
+    use std::sync::Arc;
+    use std::time::SystemTime;
+
+    async fn setup(deleted: bool) -> (RestoreUserSvc, InMemoryUnitOfWorkFactory, UserId) {
+        let repo = InMemoryUserRepository::new();
+        let mut user = User::new(
+            UserId::from_bytes([1; 16]),
+            UserName::parse("Alice").unwrap(),
+            None,
+        );
+        if deleted {
+            user.delete(SystemTime::now()).unwrap();
+        }
+        repo.save(&user).await.unwrap();
+
+        let uow = InMemoryUnitOfWorkFactory::new(repo.clone(), InMemoryOutbox::new());
+        let svc = RestoreUserSvc::new(
+            Arc::new(repo),
+            Arc::new(uow.clone()),
+            Arc::new(AllowAllPolicy),
+        );
+        (svc, uow, user.id)
+    }
+
+    #[tokio::test]
+    async fn test_restore_deleted_user() {
+        let (svc, uow, id) = setup(true).await;
+
+        let user = svc
+            .handle(
+                &CallerContext::system(),
+                RestoreUserCmd {
+                    id: id.clone(),
+                    expected_version: Some(1),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(!user.is_deleted());
+        assert_eq!(user.version, 2);
+        assert!(uow.users().find(&id).await.is_ok());
+        assert_eq!(uow.outbox().messages()[0].event_type, "user.restored");
+    }
+
+    #[tokio::test]
+    async fn test_restore_active_user_is_rejected() {
+        let (svc, _uow, id) = setup(false).await;
+
+        let result = svc
+            .handle(
+                &CallerContext::system(),
+                RestoreUserCmd {
+                    id,
+                    expected_version: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(DomainError::InvalidOperation { .. })));
+    }
+}
@@ -0,0 +1,49 @@
+use std::time::SystemTime;
+
+use crate::bus::{Handler, Message};
+use async_trait::async_trait;
+use contracts::{
+    ports::{ApiKeyId, DynApiKeyRepo},
+    Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource,
+};
+
+#[derive(Debug, Clone)]
+pub struct RevokeApiKeyCmd {
+    pub id: ApiKeyId,
+}
+
+impl Message for RevokeApiKeyCmd {
+    type Output = ();
+    const NAME: &'static str = "revoke_api_key";
+
+    fn span_fields(&self) -> Vec<(&'static str, String)> {
+        vec![("id", self.id.to_string())]
+    }
+}
+
+// 具體實作
+
+/// 撤銷金鑰；撤銷後的金鑰保留以供稽核，但無法再用於驗證
+pub struct RevokeApiKeySvc {
+    repo: DynApiKeyRepo,
+    policy: DynAuthorizationPolicy,
+}
+
+impl RevokeApiKeySvc {
+    pub fn new(repo: DynApiKeyRepo, policy: DynAuthorizationPolicy) -> Self {
+        Self { repo, policy }
+    }
+}
+
+#[async_trait]
+impl Handler<RevokeApiKeyCmd> for RevokeApiKeySvc {
+    async fn handle(&self, ctx: &CallerContext, cmd: RevokeApiKeyCmd) -> Result<(), DomainError> {
+        self.policy
+            .authorize(ctx, Action::RevokeApiKey, &Resource::ApiKey(&cmd.id))?;
+
+        let mut key = self.repo.find(&cmd.id).await?;
+        key.ensure_tenant(ctx.tenant())?;
+        key.revoke(SystemTime::now())?;
+        self.repo.save(&key).await
+    }
+}
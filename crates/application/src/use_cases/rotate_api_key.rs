@@ -0,0 +1,124 @@
+use crate::bus::{Handler, Message};
+use crate::{api_key_token::ApiKeyToken, use_cases::create_api_key::IssuedApiKey};
+use async_trait::async_trait;
+use contracts::{
+    ports::{ApiKeyId, DynApiKeyRepo},
+    Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource,
+};
+
+#[derive(Debug, Clone)]
+pub struct RotateApiKeyCmd {
+    pub id: ApiKeyId,
+}
+
+impl Message for RotateApiKeyCmd {
+    type Output = IssuedApiKey;
+    const NAME: &'static str = "rotate_api_key";
+
+    fn span_fields(&self) -> Vec<(&'static str, String)> {
+        vec![("id", self.id.to_string())]
+    }
+}
+
+// 具體實作
+
+/// 為既有金鑰換發新的秘密，保留 ID、名稱與權限範圍
+pub struct RotateApiKeySvc {
+    repo: DynApiKeyRepo,
+    policy: DynAuthorizationPolicy,
+}
+
+impl RotateApiKeySvc {
+    pub fn new(repo: DynApiKeyRepo, policy: DynAuthorizationPolicy) -> Self {
+        Self { repo, policy }
+    }
+}
+
+#[async_trait]
+impl Handler<RotateApiKeyCmd> for RotateApiKeySvc {
+    async fn handle(
+        &self,
+        ctx: &CallerContext,
+        cmd: RotateApiKeyCmd,
+    ) -> Result<IssuedApiKey, DomainError> {
+        self.policy
+            .authorize(ctx, Action::RotateApiKey, &Resource::ApiKey(&cmd.id))?;
+
+        let mut key = self.repo.find(&cmd.id).await?;
+        key.ensure_tenant(ctx.tenant())?;
+        let token = ApiKeyToken::generate(key.id.clone());
+        key.rotate(token.secret_hash())?;
+        self.repo.save(&key).await?;
+
+        Ok(IssuedApiKey {
+            key,
+            token: token.expose(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorization::AllowAllPolicy;
+    use contracts::{ApiKey, ApiKeyRepository, TenantId};
+    use infra_memory::InMemoryApiKeyRepository;
+    use std::sync::Arc;
+    use std::time::SystemTime;
+
+    #[tokio::test]
+    async fn test_rotate_invalidates_old_secret() {
+        let repo = InMemoryApiKeyRepository::new();
+        let id = ApiKeyId::from_u128(1);
+        let old = ApiKeyToken::generate(id.clone());
+        let key = ApiKey::issue(
+            id.clone(),
+            TenantId::default(),
+            "ci",
+            old.secret_hash(),
+            Vec::new(),
+            None,
+            SystemTime::now(),
+        )
+        .unwrap();
+        repo.save(&key).await.unwrap();
+        let svc = RotateApiKeySvc::new(Arc::new(repo.clone()), Arc::new(AllowAllPolicy));
+
+        let issued = svc
+            .handle(&CallerContext::system(), RotateApiKeyCmd { id: id.clone() })
+            .await
+            .unwrap();
+
+        let stored = repo.find(&id).await.unwrap();
+        assert!(!old.matches(&stored.secret_hash));
+        assert!(ApiKeyToken::parse(&issued.token)
+            .unwrap()
+            .matches(&stored.secret_hash));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_rejects_key_of_another_tenant() {
+        let repo = InMemoryApiKeyRepository::new();
+        let id = ApiKeyId::from_u128(1);
+        let old = ApiKeyToken::generate(id.clone());
+        let key = ApiKey::issue(
+            id.clone(),
+            TenantId::parse("acme").unwrap(),
+            "ci",
+            old.secret_hash(),
+            Vec::new(),
+            None,
+            SystemTime::now(),
+        )
+        .unwrap();
+        repo.save(&key).await.unwrap();
+        let svc = RotateApiKeySvc::new(Arc::new(repo.clone()), Arc::new(AllowAllPolicy));
+
+        let result = svc
+            .handle(&CallerContext::system(), RotateApiKeyCmd { id: id.clone() })
+            .await;
+
+        assert!(matches!(result, Err(DomainError::NotFound { .. })));
+        assert!(old.matches(&repo.find(&id).await.unwrap().secret_hash));
+    }
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use crate::bus::{Handler, Message};
+use crate::user_events::{EventId, UserEventStream, UserEventSubscription};
+use async_trait::async_trait;
+use contracts::{Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource};
+
+/// 訂閱用戶變更事件；`last_event_id` 為斷線前最後收到的事件 ID
+#[derive(Debug, Clone)]
+pub struct SubscribeUserEventsQuery {
+    pub last_event_id: Option<EventId>,
+}
+
+impl Message for SubscribeUserEventsQuery {
+    type Output = UserEventSubscription;
+    const NAME: &'static str = "subscribe_user_events";
+    const IDEMPOTENT: bool = true;
+}
+
+// 具體實作
+
+pub struct SubscribeUserEventsSvc {
+    events: Arc<UserEventStream>,
+    policy: DynAuthorizationPolicy,
+}
+
+impl SubscribeUserEventsSvc {
+    pub fn new(events: Arc<UserEventStream>, policy: DynAuthorizationPolicy) -> Self {
+        Self { events, policy }
+    }
+}
+
+#[async_trait]
+impl Handler<SubscribeUserEventsQuery> for SubscribeUserEventsSvc {
+    async fn handle(
+        &self,
+        ctx: &CallerContext,
+        query: SubscribeUserEventsQuery,
+    ) -> Result<UserEventSubscription, DomainError> {
+        // 事件內容與列表相同，沿用列表權限
+        self.policy
+            .authorize(ctx, Action::ListUsers, &Resource::Users)?;
+
+        Ok(self.events.subscribe(ctx.tenant(), query.last_event_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorization::RolePolicy;
+    use contracts::{DomainEvent, Principal, UserId};
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_subscribe_requires_list_permission() {
+        let events = Arc::new(UserEventStream::default());
+        let policy = RolePolicy::new(HashMap::from([(
+            "viewer".to_string(),
+            vec!["users:list".to_string()],
+        )]));
+        let svc = SubscribeUserEventsSvc::new(events.clone(), Arc::new(policy));
+
+        let anonymous = svc
+            .handle(
+                &CallerContext::anonymous(),
+                SubscribeUserEventsQuery {
+                    last_event_id: None,
+                },
+            )
+            .await;
+        assert!(matches!(anonymous, Err(DomainError::Forbidden { .. })));
+
+        let viewer = CallerContext::authenticated(Principal::new("bob").with_roles(["viewer"]));
+        let mut subscription = svc
+            .handle(
+                &viewer,
+                SubscribeUserEventsQuery {
+                    last_event_id: None,
+                },
+            )
+            .await
+            .unwrap();
+        events.publish(
+            viewer.tenant(),
+            &[DomainEvent::UserDeleted {
+                user_id: UserId::from_bytes([1; 16]),
+            }],
+        );
+        assert_eq!(
+            subscription.next().await.unwrap().message.event_type,
+            "user.deleted"
+        );
+    }
+}
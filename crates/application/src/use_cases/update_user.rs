@@ -0,0 +1,238 @@
+use crate::bus::{Handler, Message};
+use crate::unit_of_work::modify_user_with_events;
+use async_trait::async_trait;
+use contracts::{
+    ports::{DynUnitOfWorkFactory, Email, User, UserId, UserName},
+    Action, CallerContext, DomainError, DynAuthorizationPolicy, Resource,
+};
+
+#[derive(Debug, Clone)]
+pub struct UpdateUserCmd {
+    pub id: UserId,
+    pub name: UserName,
+    /// 取代後的電子郵件，`None` 表示移除
+    pub email: Option<Email>,
+    /// 呼叫端預期的版本；`None` 表示不做前置條件檢查
+    pub expected_version: Option<u64>,
+}
+
+impl Message for UpdateUserCmd {
+    type Output = User;
+    const NAME: &'static str = "update_user";
+
+    fn span_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = vec![("id", self.id.to_string())];
+        if let Some(expected) = self.expected_version {
+            fields.push(("expected_version", expected.to_string()));
+        }
+        fields
+    }
+}
+
+// 具體實作
+
+pub struct UpdateUserSvc {
+    uow: DynUnitOfWorkFactory,
+    policy: DynAuthorizationPolicy,
+}
+
+impl UpdateUserSvc {
+    pub fn new(uow: DynUnitOfWorkFactory, policy: DynAuthorizationPolicy) -> Self {
+        Self { uow, policy }
+    }
+}
+
+#[async_trait]
+impl Handler<UpdateUserCmd> for UpdateUserSvc {
+    async fn handle(&self, ctx: &CallerContext, cmd: UpdateUserCmd) -> Result<User, DomainError> {
+        self.policy
+            .authorize(ctx, Action::UpdateUser, &Resource::User(&cmd.id))?;
+
+        // 在交易內載入目前狀態；若期間有其他寫入，儲存庫會以版本號衝突拒絕
+        modify_user_with_events(
+            self.uow.for_tenant(ctx.tenant()).as_ref(),
+            &cmd.id,
+            |user| {
+                // 1) 檢查前置條件
+                if let Some(expected) = cmd.expected_version {
+                    user.ensure_version(expected)?;
+                }
+
+                // 2) 套用業務規則
+                user.update_name(cmd.name)?;
+                user.change_email(cmd.email)
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorization::AllowAllPolicy;
+    use crate::user_cache::{CachingUnitOfWorkFactory, CachingUserRepository, UserCacheSettings};
+    use contracts::{DynCache, MockObservabilityPort, UserRepository};
+    use infra_memory::{
+        InMemoryCache, InMemoryOutbox, InMemoryUnitOfWorkFactory, InMemoryUserRepository,
+    };
+    use std::sync::Arc;
+
+    async fn setup() -> (UpdateUserSvc, InMemoryUnitOfWorkFactory, UserId) {
+        let repo = InMemoryUserRepository::new();
+        let user = User::new(
+            UserId::from_bytes([1; 16]),
+            UserName::parse("Alice").unwrap(),
+            None,
+        );
+        repo.save(&user).await.unwrap();
+
+        let uow = InMemoryUnitOfWorkFactory::new(repo.clone(), InMemoryOutbox::new());
+        let svc = UpdateUserSvc::new(Arc::new(uow.clone()), Arc::new(AllowAllPolicy));
+        (svc, uow, user.id)
+    }
+
+    #[tokio::test]
+    async fn test_update_user_success() {
+        let (svc, uow, id) = setup().await;
+
+        let user = svc
+            .handle(
+                &CallerContext::system(),
+                UpdateUserCmd {
+                    id: id.clone(),
+                    name: UserName::parse("Bob").unwrap(),
+                    email: None,
+                    expected_version: Some(1),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(user.name.as_str(), "Bob");
+        assert_eq!(user.version, 2);
+        assert_eq!(uow.users().find(&id).await.unwrap().version, 2);
+        assert_eq!(uow.outbox().messages()[0].event_type, "user.renamed");
+    }
+
+    #[tokio::test]
+    async fn test_update_user_version_mismatch() {
+        let (svc, uow, id) = setup().await;
+
+        let result = svc
+            .handle(
+                &CallerContext::system(),
+                UpdateUserCmd {
+                    id: id.clone(),
+                    name: UserName::parse("Bob").unwrap(),
+                    email: None,
+                    expected_version: Some(7),
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(DomainError::Conflict { .. })));
+        assert_eq!(uow.users().find(&id).await.unwrap().name.as_str(), "Alice");
+    }
+
+    #[tokio::test]
+    async fn test_update_user_without_precondition() {
+        let (svc, _uow, id) = setup().await;
+
+        let user = svc
+            .handle(
+                &CallerContext::system(),
+                UpdateUserCmd {
+                    id,
+                    name: UserName::parse("Bob").unwrap(),
+                    email: None,
+                    expected_version: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(user.version, 2);
+    }
+
+    #[tokio::test]
+    async fn test_update_checks_version_past_stale_cache() {
+        let (_svc, uow, id) = setup().await;
+        let cache: DynCache = Arc::new(InMemoryCache::new());
+        let mut observability = MockObservabilityPort::new();
+        observability.expect_on_cache_lookup().return_const(());
+        let cached = CachingUserRepository::new(
+            Arc::new(uow.users().clone()),
+            cache.clone(),
+            Arc::new(observability),
+            UserCacheSettings::default(),
+        );
+        let svc = UpdateUserSvc::new(
+            Arc::new(CachingUnitOfWorkFactory::new(Arc::new(uow.clone()), cache)),
+            Arc::new(AllowAllPolicy),
+        );
+
+        // 快取保留版本 1，儲存庫已被其他節點更新為版本 2
+        cached.find(&id).await.unwrap();
+        let mut current = uow.users().find(&id).await.unwrap();
+        current
+            .update_name(UserName::parse("Carol").unwrap())
+            .unwrap();
+        uow.users().save(&current).await.unwrap();
+
+        let user = svc
+            .handle(
+                &CallerContext::system(),
+                UpdateUserCmd {
+                    id: id.clone(),
+                    name: UserName::parse("Bob").unwrap(),
+                    email: None,
+                    expected_version: Some(2),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(user.version, 3);
+        assert_eq!(cached.find(&id).await.unwrap().name.as_str(), "Bob");
+    }
+
+    #[tokio::test]
+    async fn test_update_missing_user() {
+        let (svc, _uow, _id) = setup().await;
+
+        let result = svc
+            .handle(
+                &CallerContext::system(),
+                UpdateUserCmd {
+                    id: UserId::from_bytes([0; 16]),
+                    name: UserName::parse("Bob").unwrap(),
+                    email: None,
+                    expected_version: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(DomainError::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_span_fields_exclude_personal_data() {
+        let cmd = UpdateUserCmd {
+            id: UserId::from_bytes([1; 16]),
+            name: UserName::parse("Bob").unwrap(),
+            email: Some(Email::parse("bob@example.com").unwrap()),
+            expected_version: Some(2),
+        };
+
+        let fields = cmd.span_fields();
+
+        assert_eq!(
+            fields,
+            vec![
+                ("id", cmd.id.to_string()),
+                ("expected_version", "2".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,669 @@
+//=== User Read-Through Cache ===//
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+use contracts::{
+    AuditTimestamps, DomainError, DynCache, DynObservability, DynUnitOfWorkFactory, DynUserRepo,
+    Email, InsertManyFuture, OutboxRepository, TenantId, UnitOfWork, UnitOfWorkFactory,
+    UnitOfWorkFuture, User, UserId, UserListQuery, UserName, UserRepository, UserScanQuery,
+};
+use serde::{Deserialize, Serialize};
+
+/// 回報給可觀測性端口的快取名稱
+const CACHE_NAME: &str = "users";
+
+/// 寫入標記的存活時間，只需涵蓋寫入前已開始的查詢
+const WRITTEN_MARKER_TTL: Duration = Duration::from_secs(30);
+
+/// 用戶快取的存活時間設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserCacheSettings {
+    /// 存在的用戶保留多久
+    pub ttl: Duration,
+    /// 「用戶不存在」保留多久；設為零即停用負向快取
+    pub negative_ttl: Duration,
+}
+
+impl Default for UserCacheSettings {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(60),
+            negative_ttl: Duration::from_secs(5),
+        }
+    }
+}
+
+/// 快取鍵包含租戶，不同租戶的查詢結果（包含「不存在」）互不影響
+fn cache_key(tenant: &TenantId, id: &UserId) -> String {
+    format!("user:{tenant}:{id}")
+}
+
+/// 快取中保存的查詢結果
+#[derive(Serialize, Deserialize)]
+enum CachedLookup {
+    Found(CachedUser),
+    Missing,
+    /// 寫入提交後留下的標記，視為未命中；版本較舊的查詢結果不得再寫回快取
+    Written {
+        version: u64,
+    },
+}
+
+impl CachedLookup {
+    fn is_hit(&self) -> bool {
+        !matches!(self, Self::Written { .. })
+    }
+}
+
+/// 以寫入標記取代快取項目；寫入後的版本為 `user.version + 1`
+async fn mark_written(cache: &DynCache, key: &str, version: u64) {
+    // 失效失敗時舊資料最多保留到存活時間結束
+    let _ = cache
+        .set(key, &CachedLookup::Written { version }, WRITTEN_MARKER_TTL)
+        .await;
+}
+
+/// 快取中保存的用戶快照；未發佈的領域事件不會進入快取
+#[derive(Serialize, Deserialize)]
+struct CachedUser {
+    id: UserId,
+    name: UserName,
+    email: Option<Email>,
+    version: u64,
+    audit: Option<(SystemTime, SystemTime)>,
+}
+
+impl From<&User> for CachedUser {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id.clone(),
+            name: user.name.clone(),
+            email: user.email.clone(),
+            version: user.version,
+            audit: user.audit.map(|a| (a.created_at, a.updated_at)),
+        }
+    }
+}
+
+impl From<CachedUser> for User {
+    fn from(cached: CachedUser) -> Self {
+        let user =
+            User::reconstitute(cached.id, cached.name, cached.version).with_email(cached.email);
+        match cached.audit {
+            Some((created_at, updated_at)) => user.with_audit(AuditTimestamps {
+                created_at,
+                updated_at,
+            }),
+            None => user,
+        }
+    }
+}
+
+/// 讀取穿透的用戶儲存庫裝飾器
+///
+/// 只快取 `find` 與 `find_many` 的結果（包含 `NotFound`），其餘查詢直接交給內層儲存庫。
+/// 快取故障時退回內層儲存庫，不影響請求結果。
+///
+/// 寫入會留下帶版本的標記而非直接刪除項目，寫入前開始的查詢若較晚完成，
+/// 也不會把舊版本放回快取。
+pub struct CachingUserRepository {
+    inner: DynUserRepo,
+    cache: DynCache,
+    observability: DynObservability,
+    settings: UserCacheSettings,
+    tenant: TenantId,
+}
+
+impl CachingUserRepository {
+    pub fn new(
+        inner: DynUserRepo,
+        cache: DynCache,
+        observability: DynObservability,
+        settings: UserCacheSettings,
+    ) -> Self {
+        Self {
+            inner,
+            cache,
+            observability,
+            settings,
+            tenant: TenantId::default(),
+        }
+    }
+
+    fn key(&self, id: &UserId) -> String {
+        cache_key(&self.tenant, id)
+    }
+
+    async fn find_cached(&self, id: &UserId) -> Result<User, DomainError> {
+        let key = self.key(id);
+        let cached = self.cache.get::<CachedLookup>(&key).await.ok().flatten();
+        self.observability
+            .on_cache_lookup(
+                CACHE_NAME,
+                cached.as_ref().is_some_and(CachedLookup::is_hit),
+            )
+            .await;
+
+        match cached {
+            Some(CachedLookup::Found(user)) => return Ok(user.into()),
+            Some(CachedLookup::Missing) => {
+                return Err(DomainError::NotFound {
+                    message: format!("User {id} not found"),
+                })
+            }
+            Some(CachedLookup::Written { .. }) | None => {}
+        }
+
+        let result = self.inner.find(id).await;
+        match &result {
+            Ok(user) => {
+                self.store(&key, CachedLookup::Found(user.into()), self.settings.ttl)
+                    .await;
+            }
+            Err(DomainError::NotFound { .. }) if !self.settings.negative_ttl.is_zero() => {
+                self.store(&key, CachedLookup::Missing, self.settings.negative_ttl)
+                    .await;
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// 寫回查詢結果，但不覆蓋快取中較新的版本或寫入標記
+    ///
+    /// 讀取與寫回之間若有其他請求提交了變更，放棄寫回，下次查詢再從儲存庫載入。
+    /// 寫入失敗只代表下次仍需查詢儲存庫。
+    async fn store(&self, key: &str, lookup: CachedLookup, ttl: Duration) {
+        let newest = match self.cache.get::<CachedLookup>(key).await.ok().flatten() {
+            Some(CachedLookup::Found(user)) => Some(user.version),
+            Some(CachedLookup::Written { version }) => Some(version),
+            Some(CachedLookup::Missing) | None => None,
+        };
+        let stale = match (&lookup, newest) {
+            (_, None) => false,
+            (CachedLookup::Found(user), Some(newest)) => user.version < newest,
+            // 無法判斷「不存在」是否早於寫入，保守地不寫回
+            (_, Some(_)) => true,
+        };
+        if !stale {
+            let _ = self.cache.set(key, &lookup, ttl).await;
+        }
+    }
+
+    /// 先查快取，未命中的用戶以一次批次查詢補齊並寫回快取
+    async fn find_many_cached(&self, ids: &[UserId]) -> Result<Vec<User>, DomainError> {
+        let mut users = Vec::with_capacity(ids.len());
+        let mut misses = Vec::new();
+        for id in ids {
+            let cached = self
+                .cache
+                .get::<CachedLookup>(&self.key(id))
+                .await
+                .ok()
+                .flatten();
+            self.observability
+                .on_cache_lookup(
+                    CACHE_NAME,
+                    cached.as_ref().is_some_and(CachedLookup::is_hit),
+                )
+                .await;
+            match cached {
+                Some(CachedLookup::Found(user)) => users.push(user.into()),
+                Some(CachedLookup::Missing) => {}
+                Some(CachedLookup::Written { .. }) | None => misses.push(id.clone()),
+            }
+        }
+        if misses.is_empty() {
+            return Ok(users);
+        }
+
+        let found = self.inner.find_many(&misses).await?;
+        for user in &found {
+            self.store(
+                &self.key(&user.id),
+                CachedLookup::Found(user.into()),
+                self.settings.ttl,
+            )
+            .await;
+        }
+        if !self.settings.negative_ttl.is_zero() {
+            for id in misses
+                .iter()
+                .filter(|id| !found.iter().any(|u| &u.id == *id))
+            {
+                self.store(
+                    &self.key(id),
+                    CachedLookup::Missing,
+                    self.settings.negative_ttl,
+                )
+                .await;
+            }
+        }
+        users.extend(found);
+        Ok(users)
+    }
+}
+
+impl UserRepository for CachingUserRepository {
+    fn for_tenant(&self, tenant: &TenantId) -> Arc<dyn UserRepository> {
+        Arc::new(Self {
+            inner: self.inner.for_tenant(tenant),
+            cache: self.cache.clone(),
+            observability: self.observability.clone(),
+            settings: self.settings,
+            tenant: tenant.clone(),
+        })
+    }
+
+    fn find(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+        let id = id.clone();
+        Box::pin(async move { self.find_cached(&id).await })
+    }
+
+    fn find_including_deleted(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+        self.inner.find_including_deleted(id)
+    }
+
+    fn find_many(
+        &self,
+        ids: &[UserId],
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        let ids = ids.to_vec();
+        Box::pin(async move { self.find_many_cached(&ids).await })
+    }
+
+    fn list(
+        &self,
+        query: UserListQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        self.inner.list(query)
+    }
+
+    fn scan(
+        &self,
+        query: UserScanQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        self.inner.scan(query)
+    }
+
+    fn save(
+        &self,
+        user: &User,
+    ) -> Pin<Box<dyn Future<Output = Result<AuditTimestamps, DomainError>> + Send + '_>> {
+        let key = self.key(&user.id);
+        let version = user.version + 1;
+        let save = self.inner.save(user);
+        Box::pin(async move {
+            let audit = save.await?;
+            mark_written(&self.cache, &key, version).await;
+            Ok(audit)
+        })
+    }
+
+    fn insert_many(&self, users: &[User]) -> InsertManyFuture<'_> {
+        // 匯入時可能帶入先前查詢過的 ID，需清掉 `Missing` 快取
+        let keys: Vec<(String, u64)> = users
+            .iter()
+            .map(|u| (self.key(&u.id), u.version + 1))
+            .collect();
+        let insert = self.inner.insert_many(users);
+        Box::pin(async move {
+            let inserted = insert.await?;
+            for ((key, version), audit) in keys.iter().zip(&inserted) {
+                if audit.is_some() {
+                    mark_written(&self.cache, key, *version).await;
+                }
+            }
+            Ok(inserted)
+        })
+    }
+
+    fn purge_deleted_before(
+        &self,
+        cutoff: SystemTime,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, DomainError>> + Send + '_>> {
+        // 被清除的用戶早已軟刪除，快取中只可能是 `Missing` 或寫入標記，不需失效
+        self.inner.purge_deleted_before(cutoff)
+    }
+
+    fn shutdown(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        self.inner.shutdown()
+    }
+}
+
+/// 在交易提交後使寫入過的用戶快取失效的工作單元工廠
+///
+/// 寫入都經由工作單元進行，因此失效必須等到提交成功，
+/// 否則其他請求可能在提交前把舊資料重新放回快取。
+pub struct CachingUnitOfWorkFactory {
+    inner: DynUnitOfWorkFactory,
+    cache: DynCache,
+    tenant: TenantId,
+}
+
+impl CachingUnitOfWorkFactory {
+    pub fn new(inner: DynUnitOfWorkFactory, cache: DynCache) -> Self {
+        Self {
+            inner,
+            cache,
+            tenant: TenantId::default(),
+        }
+    }
+}
+
+impl UnitOfWorkFactory for CachingUnitOfWorkFactory {
+    fn begin(&self) -> UnitOfWorkFuture<'_> {
+        Box::pin(async move {
+            let inner = self.inner.begin().await?;
+            Ok(Box::new(CachingUnitOfWork {
+                inner,
+                cache: self.cache.clone(),
+                tenant: self.tenant.clone(),
+                written: Mutex::default(),
+            }) as Box<dyn UnitOfWork>)
+        })
+    }
+
+    fn for_tenant(&self, tenant: &TenantId) -> Arc<dyn UnitOfWorkFactory> {
+        Arc::new(Self {
+            inner: self.inner.for_tenant(tenant),
+            cache: self.cache.clone(),
+            tenant: tenant.clone(),
+        })
+    }
+}
+
+/// 記錄交易內寫入的用戶與寫入後的版本，提交成功後使其快取失效
+struct CachingUnitOfWork {
+    inner: Box<dyn UnitOfWork>,
+    cache: DynCache,
+    tenant: TenantId,
+    written: Mutex<Vec<(UserId, u64)>>,
+}
+
+impl UnitOfWork for CachingUnitOfWork {
+    fn users(&self) -> &dyn UserRepository {
+        self
+    }
+
+    fn outbox(&self) -> &dyn OutboxRepository {
+        self.inner.outbox()
+    }
+
+    fn commit(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>> {
+        let written = std::mem::take(&mut *self.written.lock().expect("written poisoned"));
+        let cache = Arc::clone(&self.cache);
+        let tenant = self.tenant.clone();
+        let commit = self.inner.commit();
+        Box::pin(async move {
+            commit.await?;
+            for (id, version) in &written {
+                mark_written(&cache, &cache_key(&tenant, id), *version).await;
+            }
+            Ok(())
+        })
+    }
+
+    fn rollback(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>> {
+        self.inner.rollback()
+    }
+}
+
+impl UserRepository for CachingUnitOfWork {
+    fn for_tenant(&self, tenant: &TenantId) -> Arc<dyn UserRepository> {
+        // 工作單元固定屬於開啟時的租戶，其他租戶的儲存庫不記錄寫入
+        self.inner.users().for_tenant(tenant)
+    }
+
+    fn find(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+        // 交易內的讀取必須看到未提交的變更，不經過快取
+        self.inner.users().find(id)
+    }
+
+    fn find_including_deleted(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+        self.inner.users().find_including_deleted(id)
+    }
+
+    fn find_many(
+        &self,
+        ids: &[UserId],
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        self.inner.users().find_many(ids)
+    }
+
+    fn list(
+        &self,
+        query: UserListQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        self.inner.users().list(query)
+    }
+
+    fn scan(
+        &self,
+        query: UserScanQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        self.inner.users().scan(query)
+    }
+
+    fn save(
+        &self,
+        user: &User,
+    ) -> Pin<Box<dyn Future<Output = Result<AuditTimestamps, DomainError>> + Send + '_>> {
+        self.written
+            .lock()
+            .expect("written poisoned")
+            .push((user.id.clone(), user.version + 1));
+        self.inner.users().save(user)
+    }
+
+    fn insert_many(&self, users: &[User]) -> InsertManyFuture<'_> {
+        self.written
+            .lock()
+            .expect("written poisoned")
+            .extend(users.iter().map(|u| (u.id.clone(), u.version + 1)));
+        self.inner.users().insert_many(users)
+    }
+
+    fn purge_deleted_before(
+        &self,
+        cutoff: SystemTime,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, DomainError>> + Send + '_>> {
+        self.inner.users().purge_deleted_before(cutoff)
+    }
+
+    fn shutdown(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        self.inner.users().shutdown()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::MockObservabilityPort;
+    use infra_memory::{InMemoryCache, InMemoryUnitOfWorkFactory, InMemoryUserRepository};
+
+    fn observability(hits: usize, misses: usize) -> DynObservability {
+        let mut mock = MockObservabilityPort::new();
+        mock.expect_on_cache_lookup()
+            .withf(|cache, hit| cache == CACHE_NAME && *hit)
+            .times(hits)
+            .return_const(());
+        mock.expect_on_cache_lookup()
+            .withf(|cache, hit| cache == CACHE_NAME && !*hit)
+            .times(misses)
+            .return_const(());
+        Arc::new(mock)
+    }
+
+    fn user(id: u128, name: &str) -> User {
+        User::new(UserId::from_u128(id), UserName::parse(name).unwrap(), None)
+    }
+
+    #[tokio::test]
+    async fn test_find_reads_through_and_save_invalidates() {
+        let store = InMemoryUserRepository::new();
+        let mut alice = user(1, "alice");
+        alice.mark_persisted(store.save(&alice).await.unwrap());
+
+        let cached = CachingUserRepository::new(
+            Arc::new(store.clone()),
+            Arc::new(InMemoryCache::new()),
+            observability(1, 2),
+            UserCacheSettings::default(),
+        );
+
+        // 第一次未命中，第二次命中；直接寫入底層儲存庫不會被看到
+        assert_eq!(cached.find(&alice.id).await.unwrap().name.as_str(), "alice");
+        let mut renamed = alice.clone();
+        renamed
+            .update_name(UserName::parse("alicia").unwrap())
+            .unwrap();
+        renamed.mark_persisted(store.save(&renamed).await.unwrap());
+        assert_eq!(cached.find(&alice.id).await.unwrap().name.as_str(), "alice");
+
+        // 經由裝飾器寫入後快取失效
+        let mut again = renamed.clone();
+        again.update_name(UserName::parse("ally").unwrap()).unwrap();
+        cached.save(&again).await.unwrap();
+        let found = cached.find(&alice.id).await.unwrap();
+        assert_eq!(found.name.as_str(), "ally");
+        assert_eq!(found.version, 3);
+    }
+
+    #[tokio::test]
+    async fn test_not_found_is_cached_until_commit_invalidates() {
+        let factory = InMemoryUnitOfWorkFactory::default();
+        let cache: DynCache = Arc::new(InMemoryCache::new());
+        let cached = CachingUserRepository::new(
+            Arc::new(factory.users().clone()),
+            cache.clone(),
+            observability(1, 2),
+            UserCacheSettings::default(),
+        );
+        let bob = user(2, "bob");
+
+        assert!(matches!(
+            cached.find(&bob.id).await,
+            Err(DomainError::NotFound { .. })
+        ));
+
+        // 提交前快取仍記得「不存在」
+        let uow_factory = CachingUnitOfWorkFactory::new(Arc::new(factory.clone()), cache);
+        let uow = uow_factory.begin().await.unwrap();
+        uow.users().save(&bob).await.unwrap();
+        assert!(matches!(
+            cached.find(&bob.id).await,
+            Err(DomainError::NotFound { .. })
+        ));
+
+        uow.commit().await.unwrap();
+        assert_eq!(cached.find(&bob.id).await.unwrap().name.as_str(), "bob");
+    }
+
+    #[tokio::test]
+    async fn test_lookup_older_than_commit_is_not_cached() {
+        // 查詢讀到的是提交前的快照，模擬在寫入前開始、提交後才完成的查詢
+        let snapshot = InMemoryUserRepository::new();
+        let mut alice = user(1, "alice");
+        alice.mark_persisted(snapshot.save(&alice).await.unwrap());
+
+        let factory = InMemoryUnitOfWorkFactory::default();
+        factory.users().save(&user(1, "alice")).await.unwrap();
+        let cache: DynCache = Arc::new(InMemoryCache::new());
+        let cached = CachingUserRepository::new(
+            Arc::new(snapshot),
+            cache.clone(),
+            observability(0, 2),
+            UserCacheSettings::default(),
+        );
+
+        let uow_factory = CachingUnitOfWorkFactory::new(Arc::new(factory), cache.clone());
+        let uow = uow_factory.begin().await.unwrap();
+        let mut renamed = alice.clone();
+        renamed
+            .update_name(UserName::parse("alicia").unwrap())
+            .unwrap();
+        uow.users().save(&renamed).await.unwrap();
+        uow.commit().await.unwrap();
+
+        // 舊版本照常回傳，但不會寫回快取；下次查詢仍未命中
+        assert_eq!(cached.find(&alice.id).await.unwrap().version, 1);
+        assert_eq!(cached.find(&alice.id).await.unwrap().version, 1);
+        let key = cache_key(&TenantId::default(), &alice.id);
+        assert!(matches!(
+            cache.get::<CachedLookup>(&key).await.unwrap(),
+            Some(CachedLookup::Written { version: 2 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_find_many_only_loads_misses() {
+        let store = InMemoryUserRepository::new();
+        let alice = user(1, "alice");
+        let bob = user(2, "bob");
+        store.save(&alice).await.unwrap();
+        store.save(&bob).await.unwrap();
+        let carol = user(3, "carol");
+
+        let cached = CachingUserRepository::new(
+            Arc::new(store.clone()),
+            Arc::new(InMemoryCache::new()),
+            observability(4, 3),
+            UserCacheSettings::default(),
+        );
+
+        // 先快取 alice，批次查詢時只有 bob 與 carol 未命中
+        cached.find(&alice.id).await.unwrap();
+        let ids = [alice.id.clone(), bob.id.clone(), carol.id.clone()];
+        let mut names: Vec<_> = cached
+            .find_many(&ids)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|u| u.name.as_str().to_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["alice", "bob"]);
+
+        // 第二次全部命中，carol 以負向快取略過
+        assert_eq!(cached.find_many(&ids).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_entries_are_scoped_by_tenant() {
+        let store = InMemoryUserRepository::new();
+        let acme = TenantId::parse("acme").unwrap();
+        let alice = user(1, "alice");
+        store.scoped(&acme).save(&alice).await.unwrap();
+
+        let cached = CachingUserRepository::new(
+            Arc::new(store),
+            Arc::new(InMemoryCache::new()),
+            observability(1, 2),
+            UserCacheSettings::default(),
+        );
+
+        // 預設租戶的「不存在」不會遮蔽 acme 的用戶
+        assert!(cached.find(&alice.id).await.is_err());
+        let scoped = cached.for_tenant(&acme);
+        assert_eq!(scoped.find(&alice.id).await.unwrap().name.as_str(), "alice");
+        assert_eq!(scoped.find(&alice.id).await.unwrap().name.as_str(), "alice");
+    }
+}
@@ -0,0 +1,417 @@
+//=== In-Process User Event Stream ===//
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use contracts::{
+    DomainError, DomainEvent, DynUnitOfWorkFactory, OutboxMessage, OutboxRepository, TenantId,
+    UnitOfWork, UnitOfWorkFactory, UnitOfWorkFuture, UserRepository,
+};
+use tokio::sync::broadcast;
+
+/// 未設定時保留的最近事件數量
+pub const DEFAULT_REPLAY_CAPACITY: usize = 1024;
+
+/// 串流事件 ID，格式為 `{epoch}-{seq}`
+///
+/// `epoch` 在每個實例啟動時隨機產生，`seq` 在本實例內單調遞增（跨租戶共用）；
+/// 重新啟動或連到其他實例後舊的 ID 不會被誤認為新序列中的位置。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventId {
+    pub epoch: u32,
+    pub seq: u64,
+}
+
+impl fmt::Display for EventId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:08x}-{}", self.epoch, self.seq)
+    }
+}
+
+impl std::str::FromStr for EventId {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DomainError::ValidationError {
+            message: format!("Invalid event ID {s:?}"),
+        };
+        let (epoch, seq) = s.split_once('-').ok_or_else(invalid)?;
+        Ok(Self {
+            epoch: u32::from_str_radix(epoch, 16).map_err(|_| invalid())?,
+            seq: seq.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// 已提交的用戶事件，`id` 作為 SSE 的事件 ID
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamedEvent {
+    pub id: EventId,
+    pub tenant: TenantId,
+    pub message: OutboxMessage,
+}
+
+struct StreamState {
+    next_id: u64,
+    replay: VecDeque<Arc<StreamedEvent>>,
+}
+
+/// 行程內的用戶事件廣播
+///
+/// 保留最近 `replay_capacity` 筆事件，讓斷線的訂閱者以最後收到的 ID 續傳。
+/// 只涵蓋本實例提交的變更；跨實例的通知應經由發件箱轉發。
+pub struct UserEventStream {
+    epoch: u32,
+    sender: broadcast::Sender<Arc<StreamedEvent>>,
+    state: Mutex<StreamState>,
+    replay_capacity: usize,
+}
+
+impl UserEventStream {
+    pub fn new(replay_capacity: usize) -> Self {
+        let replay_capacity = replay_capacity.max(1);
+        let (sender, _) = broadcast::channel(replay_capacity);
+        Self {
+            epoch: rand::random(),
+            sender,
+            state: Mutex::new(StreamState {
+                next_id: 1,
+                replay: VecDeque::with_capacity(replay_capacity),
+            }),
+            replay_capacity,
+        }
+    }
+
+    /// 發佈一組某租戶已提交的領域事件
+    pub fn publish(&self, tenant: &TenantId, events: &[DomainEvent]) {
+        // 編號、寫入重播緩衝與送出在同一把鎖內完成，訂閱者不會看到重複或缺漏
+        let mut state = self.state.lock().expect("event stream poisoned");
+        for event in events {
+            let streamed = Arc::new(StreamedEvent {
+                id: EventId {
+                    epoch: self.epoch,
+                    seq: state.next_id,
+                },
+                tenant: tenant.clone(),
+                message: OutboxMessage::from_event(event),
+            });
+            state.next_id += 1;
+            if state.replay.len() == self.replay_capacity {
+                state.replay.pop_front();
+            }
+            state.replay.push_back(streamed.clone());
+            // 沒有訂閱者時送出失敗，事件仍保留在重播緩衝中
+            let _ = self.sender.send(streamed);
+        }
+    }
+
+    /// 訂閱某租戶之後的事件；指定 `last_event_id` 時先重播緩衝中較新的事件
+    ///
+    /// ID 屬於其他 epoch、晚於目前序列，或之後的事件已被擠出緩衝時無法補齊，
+    /// 訂閱會標記為需要重新同步並只接收新事件。
+    pub fn subscribe(
+        &self,
+        tenant: &TenantId,
+        last_event_id: Option<EventId>,
+    ) -> UserEventSubscription {
+        let state = self.state.lock().expect("event stream poisoned");
+        let mut subscription = UserEventSubscription {
+            tenant: tenant.clone(),
+            replay: VecDeque::new(),
+            resync: None,
+            receiver: self.sender.subscribe(),
+        };
+        let Some(last) = last_event_id else {
+            return subscription;
+        };
+        let oldest = state
+            .replay
+            .front()
+            .map_or(state.next_id, |event| event.id.seq);
+        if last.epoch != self.epoch || last.seq >= state.next_id || last.seq + 1 < oldest {
+            subscription.resync = Some(EventId {
+                epoch: self.epoch,
+                seq: state.next_id - 1,
+            });
+            return subscription;
+        }
+        subscription.replay = state
+            .replay
+            .iter()
+            .filter(|event| event.id.seq > last.seq && &event.tenant == tenant)
+            .cloned()
+            .collect();
+        subscription
+    }
+}
+
+impl Default for UserEventStream {
+    fn default() -> Self {
+        Self::new(DEFAULT_REPLAY_CAPACITY)
+    }
+}
+
+/// 單一訂閱者的事件來源
+pub struct UserEventSubscription {
+    tenant: TenantId,
+    replay: VecDeque<Arc<StreamedEvent>>,
+    resync: Option<EventId>,
+    receiver: broadcast::Receiver<Arc<StreamedEvent>>,
+}
+
+impl std::fmt::Debug for UserEventSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserEventSubscription")
+            .field("tenant", &self.tenant)
+            .field("replay", &self.replay.len())
+            .field("resync", &self.resync)
+            .finish_non_exhaustive()
+    }
+}
+
+impl UserEventSubscription {
+    /// 無法從 `last_event_id` 續傳時回傳目前最新的事件 ID；
+    /// 客戶端應重新載入完整狀態，再從此 ID 之後接收事件
+    pub fn resync(&self) -> Option<EventId> {
+        self.resync
+    }
+
+    /// 下一筆事件
+    ///
+    /// 訂閱者跟不上而遺失事件時回傳 `None` 結束串流，
+    /// 客戶端以最後收到的 ID 重新連線即可從重播緩衝補齊。
+    pub async fn next(&mut self) -> Option<Arc<StreamedEvent>> {
+        if let Some(event) = self.replay.pop_front() {
+            return Some(event);
+        }
+        // `Lagged` 與 `Closed` 都結束串流；其他租戶的事件直接略過
+        loop {
+            let event = self.receiver.recv().await.ok()?;
+            if event.tenant == self.tenant {
+                return Some(event);
+            }
+        }
+    }
+}
+
+/// 交易提交成功後把寫入發件箱的事件發佈到 `UserEventStream` 的工作單元工廠
+pub struct PublishingUnitOfWorkFactory {
+    inner: DynUnitOfWorkFactory,
+    events: Arc<UserEventStream>,
+    tenant: TenantId,
+}
+
+impl PublishingUnitOfWorkFactory {
+    pub fn new(inner: DynUnitOfWorkFactory, events: Arc<UserEventStream>) -> Self {
+        Self {
+            inner,
+            events,
+            tenant: TenantId::default(),
+        }
+    }
+}
+
+impl UnitOfWorkFactory for PublishingUnitOfWorkFactory {
+    fn begin(&self) -> UnitOfWorkFuture<'_> {
+        Box::pin(async move {
+            let inner = self.inner.begin().await?;
+            Ok(Box::new(PublishingUnitOfWork {
+                inner,
+                events: self.events.clone(),
+                tenant: self.tenant.clone(),
+                appended: Mutex::default(),
+            }) as Box<dyn UnitOfWork>)
+        })
+    }
+
+    fn for_tenant(&self, tenant: &TenantId) -> Arc<dyn UnitOfWorkFactory> {
+        Arc::new(Self {
+            inner: self.inner.for_tenant(tenant),
+            events: self.events.clone(),
+            tenant: tenant.clone(),
+        })
+    }
+}
+
+/// 記錄交易內寫入發件箱的事件，提交成功後才發佈
+struct PublishingUnitOfWork {
+    inner: Box<dyn UnitOfWork>,
+    events: Arc<UserEventStream>,
+    tenant: TenantId,
+    appended: Mutex<Vec<DomainEvent>>,
+}
+
+impl UnitOfWork for PublishingUnitOfWork {
+    fn users(&self) -> &dyn UserRepository {
+        self.inner.users()
+    }
+
+    fn outbox(&self) -> &dyn OutboxRepository {
+        self
+    }
+
+    fn commit(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>> {
+        let appended = std::mem::take(&mut *self.appended.lock().expect("appended poisoned"));
+        let events = self.events.clone();
+        let tenant = self.tenant.clone();
+        let commit = self.inner.commit();
+        Box::pin(async move {
+            commit.await?;
+            events.publish(&tenant, &appended);
+            Ok(())
+        })
+    }
+
+    fn rollback(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>> {
+        self.inner.rollback()
+    }
+}
+
+impl OutboxRepository for PublishingUnitOfWork {
+    fn append(
+        &self,
+        events: &[DomainEvent],
+    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
+        self.appended
+            .lock()
+            .expect("appended poisoned")
+            .extend_from_slice(events);
+        self.inner.outbox().append(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::{User, UserId, UserName};
+    use infra_memory::InMemoryUnitOfWorkFactory;
+
+    impl UserEventStream {
+        fn event_id(&self, seq: u64) -> EventId {
+            EventId {
+                epoch: self.epoch,
+                seq,
+            }
+        }
+    }
+
+    fn deleted(id: u8) -> DomainEvent {
+        DomainEvent::UserDeleted {
+            user_id: UserId::from_bytes([id; 16]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resume_replays_only_newer_buffered_events() {
+        let stream = UserEventStream::new(2);
+        let tenant = TenantId::default();
+        stream.publish(&tenant, &[deleted(1), deleted(2), deleted(3)]);
+
+        // 緩衝只保留最後兩筆（序號 2、3）
+        let mut subscription = stream.subscribe(&tenant, Some(stream.event_id(2)));
+        stream.publish(&tenant, &[deleted(4)]);
+
+        assert_eq!(subscription.resync(), None);
+        assert_eq!(subscription.next().await.unwrap().id.seq, 3);
+        assert_eq!(subscription.next().await.unwrap().id.seq, 4);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_or_evicted_last_event_id_requires_resync() {
+        let stream = UserEventStream::new(2);
+        let tenant = TenantId::default();
+        stream.publish(&tenant, &[deleted(1), deleted(2), deleted(3), deleted(4)]);
+        let head = Some(stream.event_id(4));
+
+        // 緩衝保留序號 3、4：從序號 2 之後可以續傳，序號 2 本身已被擠出
+        assert_eq!(
+            stream.subscribe(&tenant, Some(stream.event_id(2))).resync(),
+            None
+        );
+        assert_eq!(
+            stream.subscribe(&tenant, Some(stream.event_id(1))).resync(),
+            head
+        );
+        assert_eq!(
+            stream.subscribe(&tenant, Some(stream.event_id(9))).resync(),
+            head
+        );
+
+        // 其他實例或重新啟動前的 ID
+        let other = UserEventStream::new(2);
+        other.publish(&tenant, &[deleted(4)]);
+        let mut subscription = stream.subscribe(&tenant, Some(other.event_id(1)));
+        assert_eq!(subscription.resync(), head);
+        stream.publish(&tenant, &[deleted(5)]);
+        assert_eq!(subscription.next().await.unwrap().id.seq, 5);
+    }
+
+    #[test]
+    fn test_event_id_round_trips() {
+        let id = EventId {
+            epoch: 0xbeef,
+            seq: 42,
+        };
+        assert_eq!(id.to_string(), "0000beef-42");
+        assert_eq!("0000beef-42".parse::<EventId>().unwrap(), id);
+        assert!("42".parse::<EventId>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber_ends_stream() {
+        let stream = UserEventStream::new(1);
+        let tenant = TenantId::default();
+        let mut subscription = stream.subscribe(&tenant, None);
+        stream.publish(&tenant, &[deleted(1), deleted(2)]);
+
+        assert!(subscription.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_events_are_published_only_after_commit() {
+        let stream = Arc::new(UserEventStream::default());
+        let factory = PublishingUnitOfWorkFactory::new(
+            Arc::new(InMemoryUnitOfWorkFactory::default()),
+            stream.clone(),
+        );
+        let mut subscription = stream.subscribe(&TenantId::default(), None);
+        let mut user = User::new(
+            UserId::from_bytes([1; 16]),
+            UserName::parse("Alice").unwrap(),
+            None,
+        );
+        let events = user.take_events();
+
+        let uow = factory.begin().await.unwrap();
+        uow.users().save(&user).await.unwrap();
+        uow.outbox().append(&events).await.unwrap();
+        uow.rollback().await.unwrap();
+
+        let uow = factory.begin().await.unwrap();
+        uow.users().save(&user).await.unwrap();
+        uow.outbox().append(&events).await.unwrap();
+        uow.commit().await.unwrap();
+
+        let event = subscription.next().await.unwrap();
+        assert_eq!(event.id.seq, 1);
+        assert_eq!(event.message.event_type, "user.created");
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_only_receive_their_tenant_events() {
+        let stream = UserEventStream::default();
+        let acme = TenantId::parse("acme").unwrap();
+        stream.publish(&acme, &[deleted(1)]);
+
+        let mut subscription = stream.subscribe(&TenantId::default(), Some(stream.event_id(1)));
+        stream.publish(&acme, &[deleted(2)]);
+        stream.publish(&TenantId::default(), &[deleted(3)]);
+
+        assert_eq!(subscription.next().await.unwrap().id.seq, 3);
+    }
+}
@@ -0,0 +1,70 @@
+//=== Deleted User Purge ===//
+
+use std::time::{Duration, SystemTime};
+
+use contracts::{AppError, DynUserRepo};
+
+/// 軟刪除用戶的預設保留期限（法遵要求至少 30 天）
+pub const DEFAULT_DELETED_USER_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// 清除工作 - 永久移除軟刪除超過保留期限的用戶
+pub struct DeletedUserPurger {
+    repo: DynUserRepo,
+    retention: Duration,
+}
+
+impl DeletedUserPurger {
+    pub fn new(repo: DynUserRepo, retention: Duration) -> Self {
+        Self { repo, retention }
+    }
+
+    /// 軟刪除後保留的時間
+    pub fn retention(&self) -> Duration {
+        self.retention
+    }
+
+    /// 清除已超過保留期限的用戶，回傳移除筆數
+    pub async fn purge_expired(&self) -> Result<u64, AppError> {
+        let cutoff = SystemTime::now()
+            .checked_sub(self.retention)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        Ok(self.repo.purge_deleted_before(cutoff).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::{User, UserId, UserName, UserRepository};
+    use infra_memory::InMemoryUserRepository;
+    use std::sync::Arc;
+
+    async fn save_deleted(repo: &InMemoryUserRepository, id: u8, deleted_at: SystemTime) {
+        let user = User::reconstitute(
+            UserId::from_bytes([id; 16]),
+            UserName::parse("A").unwrap(),
+            0,
+        )
+        .with_deleted_at(Some(deleted_at));
+        repo.save(&user).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_purge_respects_retention() {
+        let repo = InMemoryUserRepository::new();
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        save_deleted(&repo, 1, now - day * 31).await;
+        save_deleted(&repo, 2, now - day * 29).await;
+
+        let purger = DeletedUserPurger::new(Arc::new(repo.clone()), DEFAULT_DELETED_USER_RETENTION);
+        let purged = purger.purge_expired().await.unwrap();
+
+        assert_eq!(purged, 1);
+        assert_eq!(repo.len(), 1);
+        assert!(repo
+            .find_including_deleted(&UserId::from_bytes([2; 16]))
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,194 @@
+//=== Authorization Ports ===//
+
+use std::sync::Arc;
+
+use domain::{error::DomainError, ApiKeyId, TenantId, UserId};
+
+/// 通過驗證的呼叫者身份（與驗證方式無關）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    pub subject: String,
+    pub roles: Vec<String>,
+    pub scopes: Vec<String>,
+}
+
+impl Principal {
+    pub fn new(subject: impl Into<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            roles: Vec::new(),
+            scopes: Vec::new(),
+        }
+    }
+
+    pub fn with_roles<I, R>(mut self, roles: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<String>,
+    {
+        self.roles = roles.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_scopes<I, R>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<String>,
+    {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+/// 呼叫者
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Caller {
+    /// 未帶憑證的請求
+    Anonymous,
+    /// 通過驗證的使用者或服務
+    Authenticated(Principal),
+    /// 系統內部呼叫（背景工作、測試），不經授權檢查
+    System,
+}
+
+/// 呼叫者上下文 - 由表現層建立並傳入每個用例
+///
+/// 除了呼叫者身份，也帶有請求所屬的租戶；用例只會存取該租戶的資料。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallerContext {
+    caller: Caller,
+    tenant: TenantId,
+}
+
+impl CallerContext {
+    pub fn anonymous() -> Self {
+        Self::new(Caller::Anonymous)
+    }
+
+    pub fn authenticated(principal: Principal) -> Self {
+        Self::new(Caller::Authenticated(principal))
+    }
+
+    pub fn system() -> Self {
+        Self::new(Caller::System)
+    }
+
+    fn new(caller: Caller) -> Self {
+        Self {
+            caller,
+            tenant: TenantId::default(),
+        }
+    }
+
+    /// 指定請求所屬的租戶；未指定時為預設租戶
+    pub fn with_tenant(mut self, tenant: TenantId) -> Self {
+        self.tenant = tenant;
+        self
+    }
+
+    pub fn caller(&self) -> &Caller {
+        &self.caller
+    }
+
+    pub fn tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+
+    pub fn principal(&self) -> Option<&Principal> {
+        match &self.caller {
+            Caller::Authenticated(principal) => Some(principal),
+            _ => None,
+        }
+    }
+}
+
+/// 受保護的操作，對應設定檔中的權限名稱
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    CreateUser,
+    ReadUser,
+    ListUsers,
+    UpdateUser,
+    DeleteUser,
+    RestoreUser,
+    CreateApiKey,
+    RotateApiKey,
+    RevokeApiKey,
+}
+
+impl Action {
+    /// 權限名稱，格式為 `<資源>:<動作>`
+    pub fn permission(&self) -> &'static str {
+        match self {
+            Action::CreateUser => "users:create",
+            Action::ReadUser => "users:read",
+            Action::ListUsers => "users:list",
+            Action::UpdateUser => "users:update",
+            Action::DeleteUser => "users:delete",
+            Action::RestoreUser => "users:restore",
+            Action::CreateApiKey => "api_keys:create",
+            Action::RotateApiKey => "api_keys:rotate",
+            Action::RevokeApiKey => "api_keys:revoke",
+        }
+    }
+}
+
+/// 操作的目標資源，提供屬性型規則所需的屬性
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource<'a> {
+    /// 整個用戶集合（建立、列表）
+    Users,
+    /// 單一用戶
+    User(&'a UserId),
+    /// 整個 API 金鑰集合
+    ApiKeys,
+    /// 單一 API 金鑰
+    ApiKey(&'a ApiKeyId),
+}
+
+impl Resource<'_> {
+    /// 呼叫者是否為資源本身（用戶 ID 與 subject 相同）
+    pub fn is_owned_by(&self, principal: &Principal) -> bool {
+        match self {
+            Resource::User(id) => id.to_string() == principal.subject,
+            Resource::Users | Resource::ApiKeys | Resource::ApiKey(_) => false,
+        }
+    }
+}
+
+/// 授權策略端口 - 在應用服務內檢查呼叫者能否執行操作
+pub trait AuthorizationPolicy: Send + Sync {
+    fn is_allowed(&self, caller: &Caller, action: Action, resource: &Resource<'_>) -> bool;
+}
+
+impl dyn AuthorizationPolicy {
+    /// 檢查呼叫者權限，不允許時回傳 `DomainError::Forbidden`；系統呼叫一律放行
+    pub fn authorize(
+        &self,
+        ctx: &CallerContext,
+        action: Action,
+        resource: &Resource<'_>,
+    ) -> Result<(), DomainError> {
+        let allowed = match ctx.caller() {
+            Caller::System => true,
+            caller => self.is_allowed(caller, action, resource),
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(DomainError::Forbidden {
+                message: format!("Caller is not allowed to {}", action.permission()),
+            })
+        }
+    }
+}
+
+pub type DynAuthorizationPolicy = Arc<dyn AuthorizationPolicy>;
@@ -0,0 +1,47 @@
+//=== Cache Ports ===//
+
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
+
+use crate::error::AppError;
+
+pub type CacheFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, AppError>> + Send + 'a>>;
+
+/// 快取端口 - 以位元組儲存，型別化的存取透過 `dyn Cache` 上的 JSON 輔助方法
+pub trait Cache: Send + Sync {
+    fn get_bytes<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<Vec<u8>>>;
+
+    /// 寫入並設定存活時間，覆寫既有的值
+    fn set_bytes<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl: Duration) -> CacheFuture<'a, ()>;
+
+    /// 刪除；鍵不存在時視為成功
+    fn delete<'a>(&'a self, key: &'a str) -> CacheFuture<'a, ()>;
+}
+
+#[cfg(feature = "serde")]
+impl dyn Cache {
+    /// 讀取並以 JSON 反序列化；內容無法解析時視為未命中
+    pub async fn get<T: serde::de::DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, AppError> {
+        Ok(self
+            .get_bytes(key)
+            .await?
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok()))
+    }
+
+    /// 以 JSON 序列化後寫入
+    pub async fn set<T: serde::Serialize + ?Sized>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Duration,
+    ) -> Result<(), AppError> {
+        let bytes = serde_json::to_vec(value).map_err(|e| {
+            crate::error::InfraError::Cache(format!("Failed to serialize {key}: {e}"))
+        })?;
+        self.set_bytes(key, bytes, ttl).await
+    }
+}
+
+pub type DynCache = Arc<dyn Cache>;
@@ -15,6 +15,21 @@ pub enum AppError {
 
     #[error("Validation error: {0}")]
     Validation(String),
+
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
+
+    #[error("Request timed out: {0}")]
+    Timeout(String),
+
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
 }
 
 #[derive(Debug, Error)]
@@ -27,6 +42,9 @@ pub enum InfraError {
 
     #[error("Configuration error: {0}")]
     Config(String),
+
+    #[error("Cache error: {0}")]
+    Cache(String),
 }
 
 // HTTP 狀態碼映射已移除 - 由 presentation 層負責
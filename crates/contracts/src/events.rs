@@ -0,0 +1,135 @@
+use async_trait::async_trait;
+use domain::{DomainEvent, Email};
+use serde_json::json;
+use std::{sync::Arc, time::SystemTime};
+use uuid::Uuid;
+
+use crate::error::InfraError;
+
+/// 發件箱訊息 - 已序列化、等待轉發的領域事件
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxMessage {
+    pub id: Uuid,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub occurred_at: SystemTime,
+    pub attempts: u32,
+}
+
+impl OutboxMessage {
+    /// 將領域事件轉換為發件箱訊息
+    pub fn from_event(event: &DomainEvent) -> Self {
+        let payload = match event {
+            DomainEvent::UserCreated {
+                user_id,
+                name,
+                email,
+            } => json!({
+                "user_id": user_id.to_string(),
+                "name": name.as_str(),
+                "email": email.as_ref().map(Email::as_str),
+            }),
+            DomainEvent::UserRenamed {
+                user_id,
+                old_name,
+                new_name,
+            } => json!({
+                "user_id": user_id.to_string(),
+                "old_name": old_name.as_str(),
+                "new_name": new_name.as_str(),
+            }),
+            DomainEvent::UserEmailChanged { user_id, email } => json!({
+                "user_id": user_id.to_string(),
+                "email": email.as_ref().map(Email::as_str),
+            }),
+            DomainEvent::UserDeleted { user_id } | DomainEvent::UserRestored { user_id } => json!({
+                "user_id": user_id.to_string(),
+            }),
+        };
+
+        Self {
+            id: Uuid::now_v7(),
+            aggregate_type: event.aggregate_type().to_string(),
+            aggregate_id: event.aggregate_id(),
+            event_type: event.event_type().to_string(),
+            payload,
+            occurred_at: SystemTime::now(),
+            attempts: 0,
+        }
+    }
+}
+
+/// 發件箱讀取端口 - 供轉發器認領與標記訊息
+#[async_trait]
+pub trait OutboxStore: Send + Sync {
+    /// 認領最多 `limit` 筆尚未發佈的訊息
+    async fn claim_pending(&self, limit: u32) -> Result<Vec<OutboxMessage>, InfraError>;
+
+    /// 標記訊息已成功發佈
+    async fn mark_published(&self, id: Uuid) -> Result<(), InfraError>;
+
+    /// 記錄發佈失敗，訊息在 `retry_at` 之後才會被重新認領；
+    /// 在此之前同一聚合較晚的訊息也不會被認領，以維持順序
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: SystemTime,
+    ) -> Result<(), InfraError>;
+
+    /// 放棄轉發：訊息不再被認領（保留以供人工處理），同一聚合較晚的訊息繼續轉發
+    async fn mark_dead_lettered(&self, id: Uuid, error: &str) -> Result<(), InfraError>;
+}
+
+/// 事件發佈端口 - 將訊息送往下游
+#[async_trait]
+pub trait EventPublisher: Send + Sync {
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), InfraError>;
+}
+
+pub type DynOutboxStore = Arc<dyn OutboxStore>;
+pub type DynEventPublisher = Arc<dyn EventPublisher>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::{UserId, UserName};
+
+    const USER_ID: &str = "usr_01h455vb4pex5vsknk084sn02q";
+
+    #[test]
+    fn test_from_user_created_event() {
+        let event = DomainEvent::UserCreated {
+            user_id: UserId::parse(USER_ID).unwrap(),
+            name: UserName::parse("Alice").unwrap(),
+            email: Some(Email::parse("alice@example.com").unwrap()),
+        };
+
+        let message = OutboxMessage::from_event(&event);
+
+        assert_eq!(message.event_type, "user.created");
+        assert_eq!(message.aggregate_type, "user");
+        assert_eq!(message.aggregate_id, USER_ID);
+        assert_eq!(message.payload["user_id"], USER_ID);
+        assert_eq!(message.payload["name"], "Alice");
+        assert_eq!(message.payload["email"], "alice@example.com");
+        assert_eq!(message.attempts, 0);
+    }
+
+    #[test]
+    fn test_from_user_renamed_event() {
+        let event = DomainEvent::UserRenamed {
+            user_id: UserId::parse(USER_ID).unwrap(),
+            old_name: UserName::parse("Alice").unwrap(),
+            new_name: UserName::parse("Bob").unwrap(),
+        };
+
+        let message = OutboxMessage::from_event(&event);
+
+        assert_eq!(message.event_type, "user.renamed");
+        assert_eq!(message.payload["old_name"], "Alice");
+        assert_eq!(message.payload["new_name"], "Bob");
+    }
+}
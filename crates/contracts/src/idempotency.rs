@@ -0,0 +1,54 @@
+//=== Idempotency Ports ===//
+
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
+
+use crate::error::AppError;
+
+/// 已完成請求的回應，重試時原樣重播
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// 認領冪等鍵的結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyClaim {
+    /// 第一次看到此鍵（或舊記錄已過期），由本次請求執行
+    Acquired,
+    /// 已有記錄；`response` 為 `None` 表示原請求仍在處理中
+    Existing {
+        fingerprint: String,
+        response: Option<StoredResponse>,
+    },
+}
+
+pub type IdempotencyFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, AppError>> + Send + 'a>>;
+
+/// 冪等鍵儲存端口 - 記錄請求指紋與回應，讓重試的請求不會重複執行
+pub trait IdempotencyStore: Send + Sync {
+    /// 認領 `key`；處理中的記錄在 `lock_ttl` 後視為放棄，可被重新認領
+    fn claim<'a>(
+        &'a self,
+        key: &'a str,
+        fingerprint: &'a str,
+        lock_ttl: Duration,
+    ) -> IdempotencyFuture<'a, IdempotencyClaim>;
+
+    /// 保存回應，記錄在 `ttl` 後過期
+    fn complete<'a>(
+        &'a self,
+        key: &'a str,
+        response: StoredResponse,
+        ttl: Duration,
+    ) -> IdempotencyFuture<'a, ()>;
+
+    /// 放棄認領（例如請求失敗），讓用戶端可以用同一個鍵重試
+    fn release<'a>(&'a self, key: &'a str) -> IdempotencyFuture<'a, ()>;
+
+    /// 刪除過期記錄，回傳刪除的數量
+    fn purge_expired(&self) -> IdempotencyFuture<'_, u64>;
+}
+
+pub type DynIdempotencyStore = Arc<dyn IdempotencyStore>;
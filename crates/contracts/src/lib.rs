@@ -1,6 +1,16 @@
+pub mod authorization;
+pub mod cache;
 pub mod error;
+pub mod events;
+pub mod idempotency;
 pub mod ports;
+pub mod rate_limit;
 
+pub use authorization::*;
+pub use cache::*;
 pub use domain::error::DomainError;
 pub use error::{AppError, CoreError, InfraError};
+pub use events::*;
+pub use idempotency::*;
 pub use ports::*;
+pub use rate_limit::*;
@@ -2,7 +2,12 @@ use async_trait::async_trait;
 use std::sync::Arc;
 
 // Re-export domain types and ports
-pub use domain::{error::DomainError, user::User, UserId, UserRepository};
+pub use domain::{
+    error::DomainError, user::User, ApiKey, ApiKeyId, ApiKeyRepository, AuditTimestamps,
+    DomainEvent, Email, EntityKind, Id, InsertManyFuture, OutboxRepository, TenantId, UnitOfWork,
+    UnitOfWorkFactory, UnitOfWorkFuture, UserId, UserListQuery, UserName, UserRepository,
+    UserScanQuery,
+};
 pub use uuid::Uuid;
 
 //=== Application Layer Ports ===//
@@ -11,6 +16,34 @@ pub use uuid::Uuid;
 pub trait ObservabilityPort: Send + Sync {
     async fn on_request_start(&self, method: &str, path: &str);
     async fn on_request_end(&self, method: &str, path: &str, status: u16, latency: f64);
+
+    /// 請求因限流被拒絕；`key` 為限流鍵的種類（ip、api_key 等），不含實際值
+    async fn on_rate_limited(&self, _method: &str, _path: &str, _key: &str) {}
+
+    /// 快取查詢結果；負向快取（記住「不存在」）命中也算命中
+    async fn on_cache_lookup(&self, _cache: &str, _hit: bool) {}
+
+    /// 用例執行結束；`outcome` 為 `ok`、`error` 或 `timeout`，耗時包含所有重試
+    async fn on_use_case_end(&self, _use_case: &str, _outcome: &str, _duration: f64) {}
+
+    /// gRPC 呼叫結束；`method` 為完整方法名稱（`/user.v1.UserService/GetUser`），`code` 為狀態碼名稱
+    async fn on_grpc_call(&self, _method: &str, _code: &str, _duration: f64) {}
+
+    /// 請求因保護性限制被拒絕；`reason` 為 `timeout`、`overloaded` 或 `body_too_large`
+    async fn on_request_rejected(&self, _method: &str, _path: &str, _reason: &str) {}
+}
+
+//=== ID Generation Ports ===//
+/// ID 生成端口 - 產生原始的 128 位元識別碼，實體種類由呼叫端決定
+pub trait IdGenerator: Send + Sync {
+    fn next_raw(&self) -> u128;
+}
+
+impl dyn IdGenerator {
+    /// 產生指定實體種類的型別化 ID
+    pub fn next_id<T: EntityKind>(&self) -> Id<T> {
+        Id::from_u128(self.next_raw())
+    }
 }
 
 //=== Configuration Ports ===//
@@ -26,8 +59,11 @@ pub trait MetricsRegistry: Send + Sync {
 
 //=== Type Aliases ===//
 pub type DynUserRepo = Arc<dyn UserRepository>;
+pub type DynUnitOfWorkFactory = Arc<dyn UnitOfWorkFactory>;
 pub type DynObservability = Arc<dyn ObservabilityPort>;
 pub type DynMetricsRegistry = Arc<dyn MetricsRegistry>;
+pub type DynIdGenerator = Arc<dyn IdGenerator>;
+pub type DynApiKeyRepo = Arc<dyn ApiKeyRepository>;
 
 // Mock implementations for testing
 #[cfg(any(test, feature = "testing"))]
@@ -41,5 +77,10 @@ mock! {
     impl ObservabilityPort for ObservabilityPort {
         async fn on_request_start(&self, method: &str, path: &str);
         async fn on_request_end(&self, method: &str, path: &str, status: u16, latency: f64);
+        async fn on_rate_limited(&self, method: &str, path: &str, key: &str);
+        async fn on_cache_lookup(&self, cache: &str, hit: bool);
+        async fn on_use_case_end(&self, use_case: &str, outcome: &str, duration: f64);
+        async fn on_grpc_call(&self, method: &str, code: &str, duration: f64);
+        async fn on_request_rejected(&self, method: &str, path: &str, reason: &str);
     }
 }
@@ -0,0 +1,52 @@
+//=== Rate Limiting Ports ===//
+
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
+
+use crate::error::AppError;
+
+/// 固定時間窗的配額：每個時間窗最多 `requests` 個請求
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitQuota {
+    pub requests: u32,
+    pub window: Duration,
+}
+
+impl RateLimitQuota {
+    pub fn new(requests: u32, window: Duration) -> Self {
+        Self { requests, window }
+    }
+}
+
+/// 單次計數後的結果，足以產生 `RateLimit-*` 回應標頭
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// 距離目前時間窗結束的時間
+    pub reset_after: Duration,
+}
+
+impl RateLimitDecision {
+    /// 依時間窗內已計數的請求數（包含這一次）計算結果
+    pub fn from_count(quota: RateLimitQuota, count: u64, reset_after: Duration) -> Self {
+        let limit = u64::from(quota.requests);
+        Self {
+            allowed: count <= limit,
+            limit: quota.requests,
+            remaining: limit.saturating_sub(count) as u32,
+            reset_after,
+        }
+    }
+}
+
+pub type RateLimitFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<RateLimitDecision, AppError>> + Send + 'a>>;
+
+/// 限流計數儲存端口 - 以鍵計數並回傳是否仍在配額內
+pub trait RateLimitStore: Send + Sync {
+    /// 為 `key` 計數一次；第一次計數時開始新的時間窗
+    fn hit<'a>(&'a self, key: &'a str, quota: RateLimitQuota) -> RateLimitFuture<'a>;
+}
+
+pub type DynRateLimitStore = Arc<dyn RateLimitStore>;
@@ -0,0 +1,222 @@
+//=== API Key Aggregate ===//
+
+use std::time::SystemTime;
+
+use crate::{
+    error::DomainError,
+    id::{EntityKind, Id},
+    value_objects::TenantId,
+};
+
+/// API 金鑰實體種類
+#[derive(Debug)]
+pub enum ApiKeyKind {}
+
+impl EntityKind for ApiKeyKind {
+    const PREFIX: &'static str = "key";
+}
+
+/// API 金鑰唯一標識符
+pub type ApiKeyId = Id<ApiKeyKind>;
+
+/// 機器對機器呼叫使用的 API 金鑰
+///
+/// 只保存金鑰秘密部分的雜湊，明文在建立或輪替時回傳一次後即不再可得。
+/// 每把金鑰屬於一個租戶，只能存取該租戶的資料。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKey {
+    pub id: ApiKeyId,
+    pub tenant: TenantId,
+    pub name: String,
+    /// 金鑰秘密部分的雜湊
+    pub secret_hash: String,
+    pub scopes: Vec<String>,
+    pub created_at: SystemTime,
+    pub expires_at: Option<SystemTime>,
+    pub revoked_at: Option<SystemTime>,
+    pub last_used_at: Option<SystemTime>,
+}
+
+impl ApiKey {
+    /// 名稱的最大長度（位元組）
+    pub const MAX_NAME_LEN: usize = 100;
+
+    /// 發行新的 API 金鑰
+    pub fn issue(
+        id: ApiKeyId,
+        tenant: TenantId,
+        name: impl Into<String>,
+        secret_hash: String,
+        scopes: Vec<String>,
+        expires_at: Option<SystemTime>,
+        now: SystemTime,
+    ) -> Result<Self, DomainError> {
+        let name = name.into().trim().to_string();
+        if name.is_empty() || name.len() > Self::MAX_NAME_LEN {
+            return Err(DomainError::ValidationError {
+                message: format!(
+                    "API key name must be between 1 and {} bytes",
+                    Self::MAX_NAME_LEN
+                ),
+            });
+        }
+        if let Some(scope) = scopes
+            .iter()
+            .find(|scope| scope.is_empty() || scope.chars().any(char::is_whitespace))
+        {
+            return Err(DomainError::ValidationError {
+                message: format!("Invalid API key scope {scope:?}"),
+            });
+        }
+        if expires_at.is_some_and(|at| at <= now) {
+            return Err(DomainError::ValidationError {
+                message: "API key expiry must be in the future".to_string(),
+            });
+        }
+
+        Ok(Self {
+            id,
+            tenant,
+            name,
+            secret_hash,
+            scopes,
+            created_at: now,
+            expires_at,
+            revoked_at: None,
+            last_used_at: None,
+        })
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+
+    /// 確認金鑰可以用來驗證請求
+    pub fn ensure_usable(&self, now: SystemTime) -> Result<(), DomainError> {
+        if self.is_revoked() {
+            return Err(DomainError::InvalidOperation {
+                message: format!("API key {} has been revoked", self.id),
+            });
+        }
+        if self.is_expired(now) {
+            return Err(DomainError::InvalidOperation {
+                message: format!("API key {} has expired", self.id),
+            });
+        }
+        Ok(())
+    }
+
+    /// 確認金鑰屬於呼叫者的租戶；其他租戶的金鑰視同不存在，避免洩漏其 ID 是否有效
+    pub fn ensure_tenant(&self, tenant: &TenantId) -> Result<(), DomainError> {
+        if &self.tenant != tenant {
+            return Err(DomainError::NotFound {
+                message: format!("API key {} not found", self.id),
+            });
+        }
+        Ok(())
+    }
+
+    /// 更換秘密；舊的秘密立即失效
+    pub fn rotate(&mut self, secret_hash: String) -> Result<(), DomainError> {
+        if self.is_revoked() {
+            return Err(DomainError::InvalidOperation {
+                message: format!("API key {} has been revoked", self.id),
+            });
+        }
+        self.secret_hash = secret_hash;
+        Ok(())
+    }
+
+    /// 撤銷金鑰
+    pub fn revoke(&mut self, now: SystemTime) -> Result<(), DomainError> {
+        if self.is_revoked() {
+            return Err(DomainError::InvalidOperation {
+                message: format!("API key {} is already revoked", self.id),
+            });
+        }
+        self.revoked_at = Some(now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn issue(expires_at: Option<SystemTime>) -> Result<ApiKey, DomainError> {
+        ApiKey::issue(
+            ApiKeyId::from_u128(1),
+            TenantId::default(),
+            "ci-pipeline",
+            "hash".to_string(),
+            vec!["users:read".to_string()],
+            expires_at,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_000),
+        )
+    }
+
+    #[test]
+    fn test_issue_validates_input() {
+        let past = SystemTime::UNIX_EPOCH + Duration::from_secs(10);
+        assert!(matches!(
+            issue(Some(past)),
+            Err(DomainError::ValidationError { .. })
+        ));
+
+        let bad_scope = ApiKey::issue(
+            ApiKeyId::from_u128(1),
+            TenantId::default(),
+            "ci",
+            "hash".to_string(),
+            vec!["users read".to_string()],
+            None,
+            SystemTime::now(),
+        );
+        assert!(matches!(
+            bad_scope,
+            Err(DomainError::ValidationError { .. })
+        ));
+
+        let no_name = ApiKey::issue(
+            ApiKeyId::from_u128(1),
+            TenantId::default(),
+            "  ",
+            "hash".to_string(),
+            Vec::new(),
+            None,
+            SystemTime::now(),
+        );
+        assert!(matches!(no_name, Err(DomainError::ValidationError { .. })));
+    }
+
+    #[test]
+    fn test_expired_and_revoked_keys_are_unusable() {
+        let expires = SystemTime::UNIX_EPOCH + Duration::from_secs(2_000);
+        let mut key = issue(Some(expires)).unwrap();
+
+        assert!(key.ensure_usable(expires - Duration::from_secs(1)).is_ok());
+        assert!(key.ensure_usable(expires).is_err());
+
+        key.revoke(SystemTime::UNIX_EPOCH).unwrap();
+        assert!(key.ensure_usable(SystemTime::UNIX_EPOCH).is_err());
+        assert!(key.revoke(SystemTime::UNIX_EPOCH).is_err());
+    }
+
+    #[test]
+    fn test_revoked_key_cannot_be_rotated() {
+        let mut key = issue(None).unwrap();
+        key.rotate("new-hash".to_string()).unwrap();
+        assert_eq!(key.secret_hash, "new-hash");
+
+        key.revoke(SystemTime::now()).unwrap();
+        assert!(matches!(
+            key.rotate("other".to_string()),
+            Err(DomainError::InvalidOperation { .. })
+        ));
+    }
+}
@@ -2,10 +2,28 @@
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DomainError {
-    BusinessRule { message: String },
-    NotFound { message: String },
-    InvalidOperation { message: String },
-    ValidationError { message: String },
+    BusinessRule {
+        message: String,
+    },
+    NotFound {
+        message: String,
+    },
+    InvalidOperation {
+        message: String,
+    },
+    ValidationError {
+        message: String,
+    },
+    Conflict {
+        message: String,
+    },
+    Forbidden {
+        message: String,
+    },
+    /// 暫時性故障（連線中斷、逾時、交易序列化失敗），稍後重試可能成功
+    Unavailable {
+        message: String,
+    },
 }
 
 impl std::fmt::Display for DomainError {
@@ -19,10 +37,22 @@ impl std::fmt::Display for DomainError {
                 write!(f, "Invalid operation: {message}")
             }
             DomainError::ValidationError { message } => write!(f, "Validation error: {message}"),
+            DomainError::Conflict { message } => write!(f, "Conflict: {message}"),
+            DomainError::Forbidden { message } => write!(f, "Forbidden: {message}"),
+            DomainError::Unavailable { message } => {
+                write!(f, "Temporarily unavailable: {message}")
+            }
         }
     }
 }
 
+impl DomainError {
+    /// 是否為暫時性錯誤，重新執行整個操作可能成功
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, DomainError::Unavailable { .. })
+    }
+}
+
 impl std::error::Error for DomainError {}
 
 #[cfg(test)]
@@ -68,6 +98,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_conflict_error() {
+        let error = DomainError::Conflict {
+            message: "Version mismatch".to_string(),
+        };
+        assert!(matches!(error, DomainError::Conflict { .. }));
+        assert_eq!(error.to_string(), "Conflict: Version mismatch");
+    }
+
+    #[test]
+    fn test_forbidden_error() {
+        let error = DomainError::Forbidden {
+            message: "users:delete".to_string(),
+        };
+        assert!(matches!(error, DomainError::Forbidden { .. }));
+        assert_eq!(error.to_string(), "Forbidden: users:delete");
+    }
+
+    #[test]
+    fn test_only_unavailable_is_retryable() {
+        let error = DomainError::Unavailable {
+            message: "pool timed out".to_string(),
+        };
+        assert!(error.is_retryable());
+        assert_eq!(error.to_string(), "Temporarily unavailable: pool timed out");
+        assert!(!DomainError::Conflict {
+            message: "version".to_string()
+        }
+        .is_retryable());
+    }
+
     #[test]
     fn test_error_clone_and_equality() {
         let error1 = DomainError::ValidationError {
@@ -0,0 +1,85 @@
+//=== Pure Domain Events ===//
+
+use crate::{
+    id::UserId,
+    value_objects::{Email, UserName},
+};
+
+/// 領域事件 - 由聚合在狀態變更時產生
+#[derive(Debug, Clone, PartialEq)]
+pub enum DomainEvent {
+    UserCreated {
+        user_id: UserId,
+        name: UserName,
+        email: Option<Email>,
+    },
+    UserRenamed {
+        user_id: UserId,
+        old_name: UserName,
+        new_name: UserName,
+    },
+    UserEmailChanged {
+        user_id: UserId,
+        email: Option<Email>,
+    },
+    UserDeleted {
+        user_id: UserId,
+    },
+    UserRestored {
+        user_id: UserId,
+    },
+}
+
+impl DomainEvent {
+    /// 事件類型名稱（用於序列化與路由）
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            DomainEvent::UserCreated { .. } => "user.created",
+            DomainEvent::UserRenamed { .. } => "user.renamed",
+            DomainEvent::UserEmailChanged { .. } => "user.email_changed",
+            DomainEvent::UserDeleted { .. } => "user.deleted",
+            DomainEvent::UserRestored { .. } => "user.restored",
+        }
+    }
+
+    /// 產生事件的聚合類型
+    pub fn aggregate_type(&self) -> &'static str {
+        match self {
+            DomainEvent::UserCreated { .. }
+            | DomainEvent::UserRenamed { .. }
+            | DomainEvent::UserEmailChanged { .. }
+            | DomainEvent::UserDeleted { .. }
+            | DomainEvent::UserRestored { .. } => "user",
+        }
+    }
+
+    /// 產生事件的聚合 ID
+    pub fn aggregate_id(&self) -> String {
+        match self {
+            DomainEvent::UserCreated { user_id, .. }
+            | DomainEvent::UserRenamed { user_id, .. }
+            | DomainEvent::UserEmailChanged { user_id, .. }
+            | DomainEvent::UserDeleted { user_id }
+            | DomainEvent::UserRestored { user_id } => user_id.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_metadata() {
+        let user_id = UserId::from_bytes([1; 16]);
+        let event = DomainEvent::UserRenamed {
+            user_id: user_id.clone(),
+            old_name: UserName::parse("Old").unwrap(),
+            new_name: UserName::parse("New").unwrap(),
+        };
+
+        assert_eq!(event.event_type(), "user.renamed");
+        assert_eq!(event.aggregate_type(), "user");
+        assert_eq!(event.aggregate_id(), user_id.to_string());
+    }
+}
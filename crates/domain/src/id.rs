@@ -1,58 +1,285 @@
 //=== Pure Domain ID Types ===//
 
-/// 用戶唯一標識符 - 純領域類型
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct UserId(String);
+use std::{cmp::Ordering, fmt, hash, marker::PhantomData, str::FromStr};
 
-impl UserId {
-    /// 從字符串創建 UserId（由 Application 層調用）
-    pub fn from_string(id: String) -> Self {
-        Self(id)
+use crate::error::DomainError;
+
+/// 實體種類 - 決定型別化 ID 在對外表示時使用的前綴
+pub trait EntityKind: 'static {
+    /// 對外表示的前綴，例如 `usr`
+    const PREFIX: &'static str;
+}
+
+/// 型別化 ID - 不透明且已驗證的 128 位元識別碼
+///
+/// 對外表示為 `<前綴>_<26 碼 Crockford Base32>`（例如 `usr_01h455vb4pex5vsknk084sn02q`），
+/// 儲存時則是原始的 128 位元值（Postgres 中為 UUID）。型別參數讓不同實體的 ID
+/// 無法互相混用，新增聚合時只需定義一個 `EntityKind`。
+pub struct Id<T: EntityKind> {
+    value: u128,
+    kind: PhantomData<fn() -> T>,
+}
+
+/// Crockford Base32 字母表（小寫）
+const ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+const ENCODED_LEN: usize = 26;
+
+impl<T: EntityKind> Id<T> {
+    /// 從 128 位元值建立 ID（由 ID 生成器調用）
+    pub fn from_u128(value: u128) -> Self {
+        Self {
+            value,
+            kind: PhantomData,
+        }
+    }
+
+    /// 從 16 位元組建立 ID（大端序，與 UUID 的位元組順序一致）
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self::from_u128(u128::from_be_bytes(bytes))
+    }
+
+    /// 原始 128 位元值
+    pub fn as_u128(&self) -> u128 {
+        self.value
     }
 
-    /// 獲取內部字符串值
-    pub fn as_str(&self) -> &str {
-        &self.0
+    /// 原始位元組（供基礎設施層轉換為 UUID）
+    pub fn as_bytes(&self) -> [u8; 16] {
+        self.value.to_be_bytes()
     }
 
-    /// 轉換為字符串
-    pub fn into_string(self) -> String {
-        self.0
+    /// 此種類 ID 的前綴
+    pub fn prefix() -> &'static str {
+        T::PREFIX
+    }
+
+    /// 解析對外表示的 ID
+    ///
+    /// 接受帶前綴的標準格式，以及過渡期仍在使用的連字號 UUID 格式。
+    pub fn parse(input: &str) -> Result<Self, DomainError> {
+        let invalid = || DomainError::ValidationError {
+            message: format!("Invalid {} ID: {input:?}", T::PREFIX),
+        };
+
+        let value = match input.split_once('_') {
+            Some((prefix, encoded)) if prefix == T::PREFIX => {
+                decode_base32(encoded).ok_or_else(invalid)?
+            }
+            Some(_) => return Err(invalid()),
+            None => decode_uuid(input).ok_or_else(invalid)?,
+        };
+        Ok(Self::from_u128(value))
     }
 }
 
-impl std::fmt::Display for UserId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+fn decode_base32(encoded: &str) -> Option<u128> {
+    let bytes = encoded.as_bytes();
+    // 26 碼共 130 位元，首碼只能是 0-7 才不會溢位
+    if bytes.len() != ENCODED_LEN || bytes[0] > b'7' {
+        return None;
     }
+    bytes.iter().try_fold(0u128, |acc, &b| {
+        let digit = ALPHABET.iter().position(|&a| a == b.to_ascii_lowercase())?;
+        Some((acc << 5) | digit as u128)
+    })
 }
 
+fn decode_uuid(input: &str) -> Option<u128> {
+    let bytes = input.as_bytes();
+    if bytes.len() != 36 {
+        return None;
+    }
+    let mut value = 0u128;
+    for (i, &b) in bytes.iter().enumerate() {
+        if matches!(i, 8 | 13 | 18 | 23) {
+            if b != b'-' {
+                return None;
+            }
+            continue;
+        }
+        let digit = (b as char).to_digit(16)?;
+        value = (value << 4) | u128::from(digit);
+    }
+    Some(value)
+}
+
+impl<T: EntityKind> fmt::Display for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut encoded = [0u8; ENCODED_LEN];
+        for (i, slot) in encoded.iter_mut().enumerate() {
+            let shift = 5 * (ENCODED_LEN - 1 - i);
+            *slot = ALPHABET[((self.value >> shift) & 0x1f) as usize];
+        }
+        let encoded = std::str::from_utf8(&encoded).map_err(|_| fmt::Error)?;
+        write!(f, "{}_{encoded}", T::PREFIX)
+    }
+}
+
+impl<T: EntityKind> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Id({self})")
+    }
+}
+
+// 手動實作以避免對標記型別 `T` 加上多餘的 trait 約束
+impl<T: EntityKind> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        Self::from_u128(self.value)
+    }
+}
+
+impl<T: EntityKind> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: EntityKind> Eq for Id<T> {}
+
+impl<T: EntityKind> hash::Hash for Id<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<T: EntityKind> PartialOrd for Id<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: EntityKind> Ord for Id<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T: EntityKind> FromStr for Id<T> {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl<T: EntityKind> TryFrom<String> for Id<T> {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl<T: EntityKind> From<Id<T>> for String {
+    fn from(id: Id<T>) -> Self {
+        id.to_string()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: EntityKind> serde::Serialize for Id<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: EntityKind> serde::Deserialize<'de> for Id<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Self::parse(&raw).map_err(serde::de::Error::custom)
+    }
+}
+
+/// 用戶實體種類
+#[derive(Debug)]
+pub enum UserKind {}
+
+impl EntityKind for UserKind {
+    const PREFIX: &'static str = "usr";
+}
+
+/// 用戶唯一標識符
+pub type UserId = Id<UserKind>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const UUID: &str = "01890a5d-ac96-774b-bcce-b302099a8057";
+
+    #[test]
+    fn test_user_id_uses_prefixed_wire_format() {
+        let user_id = UserId::parse(UUID).unwrap();
+        let wire = user_id.to_string();
+
+        assert!(wire.starts_with("usr_"));
+        assert_eq!(wire.len(), 4 + ENCODED_LEN);
+        assert_eq!(UserId::parse(&wire).unwrap(), user_id);
+    }
+
     #[test]
-    fn test_user_id_creation() {
-        let id_str = "test-id-123".to_string();
-        let user_id = UserId::from_string(id_str.clone());
+    fn test_user_id_bytes_match_uuid_layout() {
+        let user_id = UserId::parse(UUID).unwrap();
 
-        assert_eq!(user_id.as_str(), "test-id-123");
-        assert_eq!(user_id.into_string(), id_str);
+        assert_eq!(user_id.as_bytes()[0], 0x01);
+        assert_eq!(user_id.as_bytes()[15], 0x57);
+        assert_eq!(UserId::from_bytes(user_id.as_bytes()), user_id);
     }
 
     #[test]
-    fn test_user_id_display() {
-        let user_id = UserId::from_string("display-test".to_string());
-        assert_eq!(format!("{user_id}"), "display-test");
+    fn test_extreme_values_round_trip() {
+        for value in [0, 1, u128::MAX] {
+            let id = UserId::from_u128(value);
+            assert_eq!(UserId::parse(&id.to_string()).unwrap().as_u128(), value);
+        }
+        assert_eq!(
+            UserId::from_u128(0).to_string(),
+            "usr_00000000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn test_parse_is_case_insensitive() {
+        let user_id = UserId::from_u128(0xdead_beef);
+        let upper = user_id.to_string().to_uppercase().replacen("USR", "usr", 1);
+
+        assert_eq!(UserId::parse(&upper).unwrap(), user_id);
     }
 
     #[test]
-    fn test_user_id_equality() {
-        let id1 = UserId::from_string("same-id".to_string());
-        let id2 = UserId::from_string("same-id".to_string());
-        let id3 = UserId::from_string("different-id".to_string());
+    fn test_user_id_rejects_invalid_input() {
+        for input in [
+            "",
+            "test-id",
+            "usr_",
+            "org_00000000000000000000000000",
+            "usr_80000000000000000000000000",
+            "usr_0000000000000000000000000u",
+            "01234567-89ab-cdef-0123-456789abcdeg",
+        ] {
+            assert!(
+                matches!(
+                    UserId::parse(input),
+                    Err(DomainError::ValidationError { .. })
+                ),
+                "{input:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ids_of_different_kinds_share_representation_rules() {
+        #[derive(Debug)]
+        enum OrderKind {}
+        impl EntityKind for OrderKind {
+            const PREFIX: &'static str = "ord";
+        }
+
+        let order_id = Id::<OrderKind>::from_u128(42);
 
-        assert_eq!(id1, id2);
-        assert_ne!(id1, id3);
+        assert!(order_id.to_string().starts_with("ord_"));
+        assert!(UserId::parse(&order_id.to_string()).is_err());
+        assert_eq!(Id::<OrderKind>::prefix(), "ord");
     }
 }
@@ -10,12 +10,18 @@
 // src/domain/mod.rs
 // 包含核心業務邏輯、實體和領域特定的錯誤。
 // 這一層不應該知道任何關於 Web 框架或數據庫的具體實現。
+pub mod api_key;
 pub mod error;
+pub mod events;
 pub mod id;
 pub mod ports;
 pub mod user;
+pub mod value_objects;
 
 // Re-export for convenience
+pub use api_key::*;
+pub use events::*;
 pub use id::*;
 pub use ports::*;
 pub use user::*;
+pub use value_objects::*;
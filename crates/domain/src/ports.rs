@@ -1,17 +1,199 @@
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
+use std::time::SystemTime;
 
-use crate::{error::DomainError, id::UserId, user::User};
+use crate::{
+    api_key::{ApiKey, ApiKeyId},
+    error::DomainError,
+    events::DomainEvent,
+    id::UserId,
+    user::{AuditTimestamps, User},
+    value_objects::TenantId,
+};
+
+/// 開啟工作單元的非同步結果
+pub type UnitOfWorkFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Box<dyn UnitOfWork>, DomainError>> + Send + 'a>>;
+
+/// 批次新增用戶的非同步結果，`None` 表示該用戶已存在而未寫入
+pub type InsertManyFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Vec<Option<AuditTimestamps>>, DomainError>> + Send + 'a>>;
+
+/// 用戶列表查詢條件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserListQuery {
+    pub limit: u32,
+    pub offset: u64,
+    /// 是否包含已軟刪除的用戶
+    pub include_deleted: bool,
+}
+
+impl Default for UserListQuery {
+    fn default() -> Self {
+        Self {
+            limit: 50,
+            offset: 0,
+            include_deleted: false,
+        }
+    }
+}
+
+/// 依 ID 順序逐批讀取用戶的條件（keyset 分頁），供匯出整個資料表使用
+///
+/// 以上一批最後一個 ID 作為 `after` 取得下一批，不受期間新增或刪除的資料影響。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserScanQuery {
+    /// 只回傳 ID 大於此值的用戶；`None` 表示從頭開始
+    pub after: Option<UserId>,
+    pub limit: u32,
+    /// 是否包含已軟刪除的用戶
+    pub include_deleted: bool,
+}
 
 /// 用戶儲存庫端口 - 屬於領域層（純 Rust 實現）
+///
+/// 已軟刪除的用戶不會出現在 `find` 與預設的 `list` 結果中。
+/// 每個儲存庫只能存取一個租戶的資料；直接建立的儲存庫屬於預設租戶，
+/// 用例以 `for_tenant` 取得呼叫者所屬租戶的儲存庫。
 pub trait UserRepository: Send + Sync {
+    /// 只能存取指定租戶資料的儲存庫（共用同一個連線池或交易）
+    fn for_tenant(&self, tenant: &TenantId) -> Arc<dyn UserRepository>;
+
     fn find(
         &self,
         id: &UserId,
     ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>>;
+
+    /// 查詢用戶，包含已軟刪除者（供還原使用）
+    fn find_including_deleted(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>>;
+
+    /// 一次查詢多個用戶，不存在或已軟刪除的用戶直接略過，回傳順序不保證
+    ///
+    /// 預設逐一呼叫 `find`；支援批次查詢的儲存庫應覆寫成單一查詢。
+    fn find_many(
+        &self,
+        ids: &[UserId],
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        let ids = ids.to_vec();
+        Box::pin(async move {
+            let mut users = Vec::with_capacity(ids.len());
+            for id in &ids {
+                match self.find(id).await {
+                    Ok(user) => users.push(user),
+                    Err(DomainError::NotFound { .. }) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(users)
+        })
+    }
+
+    /// 依建立時間排序分頁列出用戶
+    fn list(
+        &self,
+        query: UserListQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>>;
+
+    /// 依 ID 排序逐批讀取用戶
+    fn scan(
+        &self,
+        query: UserScanQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>>;
+
+    /// 儲存用戶並回傳儲存庫寫入的稽核時間戳記
     fn save(
         &self,
         user: &User,
-    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>>;
+    ) -> Pin<Box<dyn Future<Output = Result<AuditTimestamps, DomainError>> + Send + '_>>;
+
+    /// 批次新增用戶，回傳與輸入順序對應的稽核時間戳記
+    ///
+    /// ID 已存在（包含已軟刪除者）的用戶不會寫入，對應位置為 `None`。
+    /// 預設逐一查詢後呼叫 `save`；支援批次寫入的儲存庫應覆寫成單一語句。
+    fn insert_many(&self, users: &[User]) -> InsertManyFuture<'_> {
+        let users = users.to_vec();
+        Box::pin(async move {
+            let mut inserted = Vec::with_capacity(users.len());
+            for user in &users {
+                match self.find_including_deleted(&user.id).await {
+                    Ok(_) => inserted.push(None),
+                    Err(DomainError::NotFound { .. }) => {
+                        inserted.push(Some(self.save(user).await?))
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(inserted)
+        })
+    }
+
+    /// 永久移除在 `cutoff` 之前軟刪除的用戶，回傳移除筆數
+    ///
+    /// 這是維運用的清除工作，涵蓋所有租戶，不受儲存庫的租戶範圍限制。
+    fn purge_deleted_before(
+        &self,
+        cutoff: SystemTime,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, DomainError>> + Send + '_>>;
+
     fn shutdown(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
 }
+
+/// 交易式發件箱端口 - 將領域事件與聚合狀態寫入同一個交易
+pub trait OutboxRepository: Send + Sync {
+    fn append(
+        &self,
+        events: &[DomainEvent],
+    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>>;
+}
+
+/// 工作單元端口 - 讓多個儲存庫的操作在同一個交易中完成
+///
+/// 未呼叫 `commit` 即被丟棄的工作單元視同回滾。
+pub trait UnitOfWork: Send + Sync {
+    /// 交易範圍內的用戶儲存庫
+    fn users(&self) -> &dyn UserRepository;
+
+    /// 交易範圍內的發件箱
+    fn outbox(&self) -> &dyn OutboxRepository;
+
+    /// 提交交易
+    fn commit(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>>;
+
+    /// 回滾交易
+    fn rollback(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>>;
+}
+
+/// 工作單元工廠端口 - 負責開啟新的交易
+///
+/// 開啟的交易只能存取工廠所屬租戶的資料；直接建立的工廠屬於預設租戶。
+pub trait UnitOfWorkFactory: Send + Sync {
+    fn begin(&self) -> UnitOfWorkFuture<'_>;
+
+    /// 開啟指定租戶交易的工廠
+    fn for_tenant(&self, tenant: &TenantId) -> Arc<dyn UnitOfWorkFactory>;
+}
+
+/// API 金鑰儲存庫端口
+pub trait ApiKeyRepository: Send + Sync {
+    fn find(
+        &self,
+        id: &ApiKeyId,
+    ) -> Pin<Box<dyn Future<Output = Result<ApiKey, DomainError>> + Send + '_>>;
+
+    /// 新增或更新金鑰
+    fn save(
+        &self,
+        key: &ApiKey,
+    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>>;
+
+    /// 記錄最後使用時間（不影響其他欄位，避免與管理操作互相覆寫）
+    fn record_usage(
+        &self,
+        id: &ApiKeyId,
+        at: SystemTime,
+    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>>;
+}
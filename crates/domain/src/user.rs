@@ -1,46 +1,187 @@
-use crate::{error::DomainError, id::UserId};
+use std::time::SystemTime;
+
+use crate::{
+    error::DomainError,
+    events::DomainEvent,
+    id::UserId,
+    value_objects::{Email, UserName},
+};
+
+/// 稽核時間戳記 - 由儲存庫在寫入時產生
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditTimestamps {
+    pub created_at: SystemTime,
+    pub updated_at: SystemTime,
+}
 
 //=== Domain Entity ===//
 #[derive(Debug, Clone)]
 pub struct User {
     pub id: UserId,
-    pub name: String,
+    pub name: UserName,
+    pub email: Option<Email>,
+    /// 已持久化的版本號，0 表示尚未儲存
+    pub version: u64,
+    /// 建立與最後更新時間，尚未儲存時為 `None`
+    pub audit: Option<AuditTimestamps>,
+    /// 軟刪除時間，`Some` 表示已刪除
+    pub deleted_at: Option<SystemTime>,
+    events: Vec<DomainEvent>,
 }
 
 impl User {
-    /// 創建新用戶（包含業務規則驗證）
-    pub fn new(id: UserId, name: String) -> Result<Self, DomainError> {
-        Self::validate_name(&name)?;
-        Ok(Self { id, name })
+    /// 創建新用戶；名稱與電子郵件在值物件建構時已完成驗證
+    pub fn new(id: UserId, name: UserName, email: Option<Email>) -> Self {
+        let event = DomainEvent::UserCreated {
+            user_id: id.clone(),
+            name: name.clone(),
+            email: email.clone(),
+        };
+        Self {
+            id,
+            name,
+            email,
+            version: 0,
+            audit: None,
+            deleted_at: None,
+            events: vec![event],
+        }
     }
 
-    /// 更新用戶名稱
-    pub fn update_name(&mut self, new_name: String) -> Result<(), DomainError> {
-        Self::validate_name(&new_name)?;
-        self.name = new_name;
-        Ok(())
+    /// 從持久化資料重建用戶（不產生領域事件）
+    pub fn reconstitute(id: UserId, name: UserName, version: u64) -> Self {
+        Self {
+            id,
+            name,
+            email: None,
+            version,
+            audit: None,
+            deleted_at: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// 附上儲存庫保存的電子郵件
+    pub fn with_email(mut self, email: Option<Email>) -> Self {
+        self.email = email;
+        self
+    }
+
+    /// 附上儲存庫保存的稽核時間戳記
+    pub fn with_audit(mut self, audit: AuditTimestamps) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// 附上儲存庫保存的軟刪除時間
+    pub fn with_deleted_at(mut self, deleted_at: Option<SystemTime>) -> Self {
+        self.deleted_at = deleted_at;
+        self
+    }
+
+    /// 是否尚未持久化
+    pub fn is_new(&self) -> bool {
+        self.version == 0
     }
 
-    /// 驗證用戶名稱的業務規則
-    fn validate_name(name: &str) -> Result<(), DomainError> {
-        if name.trim().is_empty() {
-            return Err(DomainError::ValidationError {
-                message: "User name cannot be empty".to_string(),
+    /// 檢查呼叫端預期的版本是否與目前版本一致（樂觀鎖）
+    pub fn ensure_version(&self, expected: u64) -> Result<(), DomainError> {
+        if self.version != expected {
+            return Err(DomainError::Conflict {
+                message: format!(
+                    "User {} is at version {}, expected {expected}",
+                    self.id, self.version
+                ),
             });
         }
+        Ok(())
+    }
+
+    /// 儲存成功後推進版本號並記錄儲存庫寫入的時間戳記
+    pub fn mark_persisted(&mut self, audit: AuditTimestamps) {
+        self.version += 1;
+        self.audit = Some(audit);
+    }
+
+    /// 是否已被軟刪除
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
 
-        if name.len() > 100 {
-            return Err(DomainError::ValidationError {
-                message: "User name cannot exceed 100 characters".to_string(),
+    /// 軟刪除用戶；資料保留到清除工作依保留期限移除為止
+    pub fn delete(&mut self, at: SystemTime) -> Result<(), DomainError> {
+        if self.is_deleted() {
+            return Err(DomainError::InvalidOperation {
+                message: format!("User {} is already deleted", self.id),
             });
         }
+        self.deleted_at = Some(at);
+        self.events.push(DomainEvent::UserDeleted {
+            user_id: self.id.clone(),
+        });
+        Ok(())
+    }
 
-        if name.chars().any(|c| c.is_control()) {
-            return Err(DomainError::ValidationError {
-                message: "User name cannot contain control characters".to_string(),
+    /// 還原已軟刪除的用戶
+    pub fn restore(&mut self) -> Result<(), DomainError> {
+        if !self.is_deleted() {
+            return Err(DomainError::InvalidOperation {
+                message: format!("User {} is not deleted", self.id),
             });
         }
+        self.deleted_at = None;
+        self.events.push(DomainEvent::UserRestored {
+            user_id: self.id.clone(),
+        });
+        Ok(())
+    }
+
+    /// 更新用戶名稱
+    pub fn update_name(&mut self, new_name: UserName) -> Result<(), DomainError> {
+        self.ensure_active()?;
+        if new_name == self.name {
+            return Ok(());
+        }
+        let old_name = std::mem::replace(&mut self.name, new_name);
+        self.events.push(DomainEvent::UserRenamed {
+            user_id: self.id.clone(),
+            old_name,
+            new_name: self.name.clone(),
+        });
+        Ok(())
+    }
 
+    /// 變更電子郵件，`None` 表示移除
+    pub fn change_email(&mut self, email: Option<Email>) -> Result<(), DomainError> {
+        self.ensure_active()?;
+        if email == self.email {
+            return Ok(());
+        }
+        self.email = email;
+        self.events.push(DomainEvent::UserEmailChanged {
+            user_id: self.id.clone(),
+            email: self.email.clone(),
+        });
+        Ok(())
+    }
+
+    /// 尚未發佈的領域事件
+    pub fn pending_events(&self) -> &[DomainEvent] {
+        &self.events
+    }
+
+    /// 取出並清空尚未發佈的領域事件
+    pub fn take_events(&mut self) -> Vec<DomainEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// 已刪除的用戶不可再修改
+    fn ensure_active(&self) -> Result<(), DomainError> {
+        if self.is_deleted() {
+            return Err(DomainError::InvalidOperation {
+                message: format!("User {} is deleted", self.id),
+            });
+        }
         Ok(())
     }
 }
@@ -49,57 +190,181 @@ impl User {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_user_creation_success() {
-        let id = UserId::from_string("test-id".to_string());
-        let name = "Test User".to_string();
-
-        let user = User::new(id.clone(), name.clone()).unwrap();
-
-        assert_eq!(user.id, id);
-        assert_eq!(user.name, name);
+    fn test_id() -> UserId {
+        UserId::from_bytes([7; 16])
     }
 
-    #[test]
-    fn test_user_creation_empty_name() {
-        let id = UserId::from_string("test-id".to_string());
-        let result = User::new(id, "".to_string());
-
-        assert!(result.is_err());
-        match result.unwrap_err() {
-            DomainError::ValidationError { message } => {
-                assert!(message.contains("empty"));
-            }
-            _ => panic!("Expected ValidationError"),
-        }
+    fn name(value: &str) -> UserName {
+        UserName::parse(value).unwrap()
     }
 
     #[test]
-    fn test_user_creation_long_name() {
-        let id = UserId::from_string("test-id".to_string());
-        let long_name = "a".repeat(101);
-        let result = User::new(id, long_name);
+    fn test_user_creation_success() {
+        let id = test_id();
+        let email = Email::parse("test@example.com").unwrap();
+
+        let user = User::new(id.clone(), name("Test User"), Some(email.clone()));
 
-        assert!(result.is_err());
+        assert_eq!(user.id, id);
+        assert_eq!(user.name.as_str(), "Test User");
+        assert_eq!(user.email, Some(email));
     }
 
     #[test]
     fn test_user_update_name_success() {
-        let id = UserId::from_string("test-id".to_string());
-        let mut user = User::new(id, "Original".to_string()).unwrap();
+        let mut user = User::new(test_id(), name("Original"), None);
 
-        let result = user.update_name("Updated".to_string());
+        let result = user.update_name(name("Updated"));
         assert!(result.is_ok());
-        assert_eq!(user.name, "Updated");
+        assert_eq!(user.name.as_str(), "Updated");
     }
 
     #[test]
     fn test_user_clone() {
-        let id = UserId::from_string("test-id".to_string());
-        let user = User::new(id.clone(), "Original".to_string()).unwrap();
+        let user = User::new(test_id(), name("Original"), None);
 
         let cloned = user.clone();
         assert_eq!(user.id, cloned.id);
         assert_eq!(user.name, cloned.name);
     }
+
+    #[test]
+    fn test_user_creation_records_created_event() {
+        let id = test_id();
+        let mut user = User::new(id.clone(), name("Alice"), None);
+
+        let events = user.take_events();
+        assert_eq!(
+            events,
+            vec![DomainEvent::UserCreated {
+                user_id: id,
+                name: name("Alice"),
+                email: None,
+            }]
+        );
+        assert!(user.pending_events().is_empty());
+    }
+
+    #[test]
+    fn test_user_update_name_records_renamed_event() {
+        let id = test_id();
+        let mut user = User::reconstitute(id.clone(), name("Alice"), 1);
+
+        user.update_name(name("Bob")).unwrap();
+
+        assert_eq!(
+            user.pending_events(),
+            &[DomainEvent::UserRenamed {
+                user_id: id,
+                old_name: name("Alice"),
+                new_name: name("Bob"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_user_update_to_same_name_records_nothing() {
+        let mut user = User::reconstitute(test_id(), name("Alice"), 1);
+
+        user.update_name(name("Alice")).unwrap();
+
+        assert!(user.pending_events().is_empty());
+    }
+
+    #[test]
+    fn test_change_email_records_event() {
+        let id = test_id();
+        let mut user = User::reconstitute(id.clone(), name("Alice"), 1);
+        let email = Email::parse("alice@example.com").unwrap();
+
+        user.change_email(Some(email.clone())).unwrap();
+        user.change_email(Some(email.clone())).unwrap();
+
+        assert_eq!(
+            user.pending_events(),
+            &[DomainEvent::UserEmailChanged {
+                user_id: id,
+                email: Some(email),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_new_user_is_unversioned() {
+        let mut user = User::new(test_id(), name("A"), None);
+        assert!(user.is_new());
+
+        let now = SystemTime::now();
+        user.mark_persisted(AuditTimestamps {
+            created_at: now,
+            updated_at: now,
+        });
+        assert!(!user.is_new());
+        assert_eq!(user.version, 1);
+        assert_eq!(user.audit.map(|a| a.created_at), Some(now));
+    }
+
+    #[test]
+    fn test_ensure_version() {
+        let user = User::reconstitute(test_id(), name("A"), 3);
+
+        assert!(user.ensure_version(3).is_ok());
+        assert!(matches!(
+            user.ensure_version(2),
+            Err(DomainError::Conflict { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reconstitute_records_no_events() {
+        let user = User::reconstitute(test_id(), name("A"), 1);
+        assert!(user.pending_events().is_empty());
+    }
+
+    #[test]
+    fn test_delete_and_restore_record_events() {
+        let id = test_id();
+        let mut user = User::reconstitute(id.clone(), name("A"), 1);
+        let now = SystemTime::now();
+
+        user.delete(now).unwrap();
+        assert_eq!(user.deleted_at, Some(now));
+
+        user.restore().unwrap();
+        assert!(!user.is_deleted());
+        assert_eq!(
+            user.take_events(),
+            vec![
+                DomainEvent::UserDeleted {
+                    user_id: id.clone()
+                },
+                DomainEvent::UserRestored { user_id: id },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_delete_twice_is_rejected() {
+        let mut user = User::reconstitute(test_id(), name("A"), 1);
+        user.delete(SystemTime::now()).unwrap();
+
+        assert!(matches!(
+            user.delete(SystemTime::now()),
+            Err(DomainError::InvalidOperation { .. })
+        ));
+        assert!(matches!(
+            user.update_name(name("B")),
+            Err(DomainError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_restore_active_user_is_rejected() {
+        let mut user = User::reconstitute(test_id(), name("A"), 1);
+
+        assert!(matches!(
+            user.restore(),
+            Err(DomainError::InvalidOperation { .. })
+        ));
+    }
 }
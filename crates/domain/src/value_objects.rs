@@ -0,0 +1,294 @@
+//=== Pure Domain Value Objects ===//
+
+use std::fmt;
+
+use crate::error::DomainError;
+
+/// 用戶名稱 - 建構時即完成驗證的值物件
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct UserName(String);
+
+impl UserName {
+    /// 名稱最大長度（字元數，與 OpenAPI 的 `maxLength` 一致）
+    pub const MAX_LEN: usize = 100;
+
+    pub fn parse(name: impl Into<String>) -> Result<Self, DomainError> {
+        let name = name.into();
+        if name.trim().is_empty() {
+            return Err(DomainError::ValidationError {
+                message: "User name cannot be empty".to_string(),
+            });
+        }
+
+        if name.chars().count() > Self::MAX_LEN {
+            return Err(DomainError::ValidationError {
+                message: format!("User name cannot exceed {} characters", Self::MAX_LEN),
+            });
+        }
+
+        if name.chars().any(|c| c.is_control()) {
+            return Err(DomainError::ValidationError {
+                message: "User name cannot contain control characters".to_string(),
+            });
+        }
+
+        Ok(Self(name))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Display for UserName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for UserName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for UserName {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<UserName> for String {
+    fn from(name: UserName) -> Self {
+        name.0
+    }
+}
+
+/// 電子郵件地址 - 建構時驗證格式，網域部分統一為小寫
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct Email(String);
+
+impl Email {
+    /// 地址最大長度（RFC 5321）
+    pub const MAX_LEN: usize = 254;
+
+    pub fn parse(email: impl Into<String>) -> Result<Self, DomainError> {
+        let email = email.into();
+        let invalid = |reason: &str| DomainError::ValidationError {
+            message: format!("Invalid email address: {reason}"),
+        };
+
+        if email.len() > Self::MAX_LEN {
+            return Err(invalid("too long"));
+        }
+        if email.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(invalid("contains whitespace"));
+        }
+
+        let (local, domain) = email
+            .rsplit_once('@')
+            .ok_or_else(|| invalid("missing '@'"))?;
+        if local.is_empty() || local.len() > 64 || local.contains('@') {
+            return Err(invalid("malformed local part"));
+        }
+        let labels_ok = domain.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+        });
+        if !domain.contains('.') || !labels_ok {
+            return Err(invalid("malformed domain"));
+        }
+
+        Ok(Self(format!("{local}@{}", domain.to_lowercase())))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Display for Email {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Email {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for Email {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<Email> for String {
+    fn from(email: Email) -> Self {
+        email.0
+    }
+}
+
+/// 租戶識別碼 - 小寫英數字與連字號組成，符合 DNS 標籤規則，可直接作為子網域使用
+///
+/// 未啟用多租戶或請求沒有指定租戶時使用 [`TenantId::default`]（`default`）。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct TenantId(String);
+
+impl TenantId {
+    /// 識別碼最大長度（DNS 標籤上限）
+    pub const MAX_LEN: usize = 63;
+
+    /// 預設租戶；啟用多租戶前的資料都屬於此租戶
+    pub const DEFAULT: &'static str = "default";
+
+    pub fn parse(tenant: impl Into<String>) -> Result<Self, DomainError> {
+        let tenant = tenant.into();
+        let valid = !tenant.is_empty()
+            && tenant.len() <= Self::MAX_LEN
+            && !tenant.starts_with('-')
+            && !tenant.ends_with('-')
+            && tenant
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+        if !valid {
+            return Err(DomainError::ValidationError {
+                message: format!(
+                    "Invalid tenant ID {tenant:?}: use 1 to {} lowercase letters, digits or '-'",
+                    Self::MAX_LEN
+                ),
+            });
+        }
+        Ok(Self(tenant))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for TenantId {
+    fn default() -> Self {
+        Self(Self::DEFAULT.to_string())
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for TenantId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for TenantId {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<TenantId> for String {
+    fn from(tenant: TenantId) -> Self {
+        tenant.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_name_accepts_valid_name() {
+        let name = UserName::parse("Alice").unwrap();
+        assert_eq!(name.as_str(), "Alice");
+    }
+
+    #[test]
+    fn test_user_name_rejects_empty_name() {
+        match UserName::parse("   ") {
+            Err(DomainError::ValidationError { message }) => assert!(message.contains("empty")),
+            other => panic!("Expected ValidationError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_user_name_rejects_long_and_control_characters() {
+        assert!(UserName::parse("a".repeat(101)).is_err());
+        // 長度以字元計算，100 個中文字（300 位元組）仍然有效
+        assert!(UserName::parse("名".repeat(100)).is_ok());
+        assert!(UserName::parse("名".repeat(101)).is_err());
+        assert!(UserName::parse("bad\u{0007}name").is_err());
+    }
+
+    #[test]
+    fn test_email_normalizes_domain() {
+        let email = Email::parse("Alice@Example.COM").unwrap();
+        assert_eq!(email.as_str(), "Alice@example.com");
+    }
+
+    #[test]
+    fn test_email_rejects_malformed_addresses() {
+        for input in [
+            "",
+            "alice",
+            "@example.com",
+            "alice@",
+            "alice@localhost",
+            "alice@exa mple.com",
+            "alice@-example.com",
+            "alice@example..com",
+        ] {
+            assert!(Email::parse(input).is_err(), "{input:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_tenant_id_follows_dns_label_rules() {
+        assert_eq!(TenantId::parse("acme-01").unwrap().as_str(), "acme-01");
+        assert_eq!(TenantId::default().as_str(), "default");
+        for input in ["", "Acme", "-acme", "acme-", "acme.corp", "acme_corp", "*"] {
+            assert!(
+                TenantId::parse(input).is_err(),
+                "{input:?} should be rejected"
+            );
+        }
+        assert!(TenantId::parse("a".repeat(64)).is_err());
+    }
+}
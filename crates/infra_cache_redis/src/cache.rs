@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use contracts::{Cache, CacheFuture};
+use redis::{aio::ConnectionManager, AsyncCommands};
+
+use crate::cache_error;
+
+/// Redis 快取；所有鍵都加上命名空間前綴，避免與其他用途的鍵衝突
+#[derive(Clone)]
+pub struct RedisCache {
+    conn: ConnectionManager,
+    namespace: String,
+}
+
+impl RedisCache {
+    pub fn new(conn: ConnectionManager, namespace: impl Into<String>) -> Self {
+        Self {
+            conn,
+            namespace: namespace.into(),
+        }
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}:{key}", self.namespace)
+    }
+}
+
+/// Redis 的 PX 以毫秒計且不可為 0
+fn ttl_millis(ttl: Duration) -> u64 {
+    u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX).max(1)
+}
+
+impl Cache for RedisCache {
+    fn get_bytes<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<Vec<u8>>> {
+        let mut conn = self.conn.clone();
+        Box::pin(async move {
+            conn.get::<_, Option<Vec<u8>>>(self.key(key))
+                .await
+                .map_err(cache_error)
+        })
+    }
+
+    fn set_bytes<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl: Duration) -> CacheFuture<'a, ()> {
+        let mut conn = self.conn.clone();
+        Box::pin(async move {
+            conn.pset_ex::<_, _, ()>(self.key(key), value, ttl_millis(ttl))
+                .await
+                .map_err(cache_error)
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> CacheFuture<'a, ()> {
+        let mut conn = self.conn.clone();
+        Box::pin(async move { conn.del::<_, ()>(self.key(key)).await.map_err(cache_error) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ttl_is_rounded_to_at_least_one_millisecond() {
+        assert_eq!(ttl_millis(Duration::from_secs(2)), 2_000);
+        assert_eq!(ttl_millis(Duration::from_micros(10)), 1);
+    }
+}
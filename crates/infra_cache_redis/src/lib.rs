@@ -0,0 +1,20 @@
+//! Redis 基礎設施適配器：跨實例共用的快取與限流計數。
+
+pub mod cache;
+pub mod rate_limit;
+
+pub use cache::RedisCache;
+pub use rate_limit::RedisRateLimitStore;
+
+use contracts::{AppError, InfraError};
+use redis::aio::ConnectionManager;
+
+/// 建立會自動重新連線的 Redis 連線，可複製後在多個適配器間共用
+pub async fn connect(url: &str) -> Result<ConnectionManager, AppError> {
+    let client = redis::Client::open(url).map_err(cache_error)?;
+    client.get_connection_manager().await.map_err(cache_error)
+}
+
+pub(crate) fn cache_error(error: redis::RedisError) -> AppError {
+    AppError::Infrastructure(InfraError::Cache(error.to_string()))
+}
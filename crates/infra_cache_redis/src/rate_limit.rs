@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use contracts::{RateLimitDecision, RateLimitFuture, RateLimitQuota, RateLimitStore};
+use redis::{aio::ConnectionManager, Script};
+
+use crate::cache_error;
+
+/// 固定時間窗計數：第一次計數時設定過期時間，回傳 `{計數, 剩餘毫秒}`。
+/// 以腳本執行確保 INCR 與 PEXPIRE 的原子性，多個實例共用同一份計數。
+const HIT_SCRIPT: &str = r"
+local count = redis.call('INCR', KEYS[1])
+local ttl = redis.call('PTTL', KEYS[1])
+if ttl < 0 then
+    redis.call('PEXPIRE', KEYS[1], ARGV[1])
+    ttl = tonumber(ARGV[1])
+end
+return {count, ttl}
+";
+
+/// 以 Redis 保存的限流計數，讓配額在多個副本之間一致
+#[derive(Clone)]
+pub struct RedisRateLimitStore {
+    conn: ConnectionManager,
+    namespace: String,
+    script: Script,
+}
+
+impl RedisRateLimitStore {
+    pub fn new(conn: ConnectionManager, namespace: impl Into<String>) -> Self {
+        Self {
+            conn,
+            namespace: namespace.into(),
+            script: Script::new(HIT_SCRIPT),
+        }
+    }
+}
+
+fn decision(quota: RateLimitQuota, (count, ttl_millis): (u64, i64)) -> RateLimitDecision {
+    let reset_after = Duration::from_millis(u64::try_from(ttl_millis).unwrap_or(0));
+    RateLimitDecision::from_count(quota, count, reset_after)
+}
+
+impl RateLimitStore for RedisRateLimitStore {
+    fn hit<'a>(&'a self, key: &'a str, quota: RateLimitQuota) -> RateLimitFuture<'a> {
+        let mut conn = self.conn.clone();
+        Box::pin(async move {
+            let window_millis = u64::try_from(quota.window.as_millis())
+                .unwrap_or(u64::MAX)
+                .max(1);
+            let reply: (u64, i64) = self
+                .script
+                .key(format!("{}:{key}", self.namespace))
+                .arg(window_millis)
+                .invoke_async(&mut conn)
+                .await
+                .map_err(cache_error)?;
+            Ok(decision(quota, reply))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_reply_to_decision() {
+        let quota = RateLimitQuota::new(2, Duration::from_secs(60));
+
+        let allowed = decision(quota, (2, 1_500));
+        assert!(allowed.allowed);
+        assert_eq!(allowed.remaining, 0);
+        assert_eq!(allowed.reset_after, Duration::from_millis(1_500));
+
+        let rejected = decision(quota, (3, -1));
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.reset_after, Duration::ZERO);
+    }
+}
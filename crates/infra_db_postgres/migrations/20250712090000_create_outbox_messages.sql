@@ -0,0 +1,17 @@
+-- Transactional outbox for domain events
+CREATE TABLE IF NOT EXISTS outbox_messages (
+  id             UUID PRIMARY KEY,
+  aggregate_type TEXT NOT NULL,
+  aggregate_id   TEXT NOT NULL,
+  event_type     TEXT NOT NULL,
+  payload        JSONB NOT NULL,
+  occurred_at    TIMESTAMPTZ NOT NULL,
+  published_at   TIMESTAMPTZ,
+  attempts       INT NOT NULL DEFAULT 0,
+  last_error     TEXT,
+  locked_until   TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS outbox_messages_pending_idx
+  ON outbox_messages (occurred_at)
+  WHERE published_at IS NULL;
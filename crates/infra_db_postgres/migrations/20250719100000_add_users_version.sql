@@ -0,0 +1,2 @@
+-- Optimistic concurrency control for users
+ALTER TABLE users ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 1;
@@ -0,0 +1,2 @@
+-- Optional email address for users
+ALTER TABLE users ADD COLUMN IF NOT EXISTS email TEXT;
@@ -0,0 +1,11 @@
+-- API keys for machine-to-machine clients; only a hash of the secret is stored
+CREATE TABLE IF NOT EXISTS api_keys (
+  id           UUID PRIMARY KEY,
+  name         TEXT NOT NULL,
+  secret_hash  TEXT NOT NULL,
+  scopes       TEXT[] NOT NULL DEFAULT '{}',
+  created_at   TIMESTAMPTZ NOT NULL,
+  expires_at   TIMESTAMPTZ,
+  revoked_at   TIMESTAMPTZ,
+  last_used_at TIMESTAMPTZ
+);
@@ -0,0 +1,12 @@
+-- Idempotency-Key records: request fingerprint plus the stored response for replay
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+  key              TEXT PRIMARY KEY,
+  fingerprint      TEXT NOT NULL,
+  response_status  SMALLINT,
+  response_headers JSONB,
+  response_body    BYTEA,
+  created_at       TIMESTAMPTZ NOT NULL DEFAULT now(),
+  expires_at       TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_expires_at ON idempotency_keys (expires_at);
@@ -0,0 +1,25 @@
+-- Row-level tenant isolation for users.
+-- Every repository statement filters on tenant_id explicitly; the policy is a
+-- backstop enforced inside the transaction that sets app.tenant_id. The
+-- maintenance purge sets app.tenant_id to '*' to span all tenants.
+ALTER TABLE users
+  ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+
+DROP INDEX IF EXISTS users_created_at_idx;
+CREATE INDEX IF NOT EXISTS users_tenant_created_at_idx
+  ON users (tenant_id, created_at, id)
+  WHERE deleted_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS users_tenant_id_idx
+  ON users (tenant_id, id);
+
+ALTER TABLE users ENABLE ROW LEVEL SECURITY;
+ALTER TABLE users FORCE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS users_tenant_isolation ON users;
+CREATE POLICY users_tenant_isolation ON users
+  USING (
+    current_setting('app.tenant_id', true) = '*'
+    OR tenant_id = current_setting('app.tenant_id', true)
+  )
+  WITH CHECK (tenant_id = current_setting('app.tenant_id', true));
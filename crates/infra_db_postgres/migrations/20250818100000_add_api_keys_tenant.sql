@@ -0,0 +1,5 @@
+-- API keys belong to one tenant; the key's tenant is checked against the
+-- tenant a request asks for. Authentication looks keys up by id before the
+-- tenant is known, so this table is not covered by row-level security.
+ALTER TABLE api_keys
+  ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
@@ -0,0 +1,14 @@
+-- Messages that keep failing are dead-lettered instead of blocking the relay.
+-- locked_until doubles as the retry time after a failed publish.
+ALTER TABLE outbox_messages
+  ADD COLUMN IF NOT EXISTS dead_lettered_at TIMESTAMPTZ;
+
+DROP INDEX IF EXISTS outbox_messages_pending_idx;
+CREATE INDEX IF NOT EXISTS outbox_messages_pending_idx
+  ON outbox_messages (occurred_at)
+  WHERE published_at IS NULL AND dead_lettered_at IS NULL;
+
+-- Lets the relay hold back later messages of an aggregate whose earlier message is retrying
+CREATE INDEX IF NOT EXISTS outbox_messages_aggregate_pending_idx
+  ON outbox_messages (aggregate_type, aggregate_id, occurred_at)
+  WHERE published_at IS NULL AND dead_lettered_at IS NULL;
@@ -0,0 +1,7 @@
+-- User ids are unique per tenant. With a global primary key, an insert in one
+-- tenant could learn that an id was already taken by another tenant.
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_pkey;
+ALTER TABLE users ADD CONSTRAINT users_pkey PRIMARY KEY (tenant_id, id);
+
+-- The new primary key covers (tenant_id, id) lookups.
+DROP INDEX IF EXISTS users_tenant_id_idx;
@@ -0,0 +1,146 @@
+use crate::error::DbError;
+use crate::models::ApiKeyRow;
+use contracts::{ApiKey, ApiKeyId, DomainError, TenantId};
+use domain::ApiKeyRepository;
+use sqlx::{Pool, Postgres};
+use std::{future::Future, pin::Pin, time::SystemTime};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+const API_KEY_COLUMNS: &str =
+    "id, tenant_id, name, secret_hash, scopes, created_at, expires_at, revoked_at, last_used_at";
+
+/// Postgres 的 API 金鑰儲存庫
+#[derive(Clone)]
+pub struct PostgresApiKeyRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresApiKeyRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn to_uuid(id: &ApiKeyId) -> Uuid {
+    Uuid::from_bytes(id.as_bytes())
+}
+
+impl TryFrom<ApiKeyRow> for ApiKey {
+    type Error = DomainError;
+
+    fn try_from(row: ApiKeyRow) -> Result<Self, Self::Error> {
+        Ok(ApiKey {
+            id: ApiKeyId::from_bytes(row.id.into_bytes()),
+            tenant: TenantId::parse(row.tenant_id)?,
+            name: row.name,
+            secret_hash: row.secret_hash,
+            scopes: row.scopes,
+            created_at: row.created_at.into(),
+            expires_at: row.expires_at.map(SystemTime::from),
+            revoked_at: row.revoked_at.map(SystemTime::from),
+            last_used_at: row.last_used_at.map(SystemTime::from),
+        })
+    }
+}
+
+impl ApiKeyRepository for PostgresApiKeyRepository {
+    fn find(
+        &self,
+        id: &ApiKeyId,
+    ) -> Pin<Box<dyn Future<Output = Result<ApiKey, DomainError>> + Send + '_>> {
+        let uuid = to_uuid(id);
+        Box::pin(async move {
+            let row: ApiKeyRow = sqlx::query_as(&format!(
+                "SELECT {API_KEY_COLUMNS} FROM api_keys WHERE id = $1"
+            ))
+            .bind(uuid)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::from(DbError::from(e)))?;
+            row.try_into()
+        })
+    }
+
+    fn save(
+        &self,
+        key: &ApiKey,
+    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
+        let key = key.clone();
+        Box::pin(async move {
+            sqlx::query(
+                r#"INSERT INTO api_keys (id, name, secret_hash, scopes, created_at, expires_at, revoked_at, tenant_id)
+                   VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                   ON CONFLICT (id) DO UPDATE
+                   SET name = EXCLUDED.name,
+                       secret_hash = EXCLUDED.secret_hash,
+                       scopes = EXCLUDED.scopes,
+                       expires_at = EXCLUDED.expires_at,
+                       revoked_at = EXCLUDED.revoked_at"#,
+            )
+            .bind(to_uuid(&key.id))
+            .bind(&key.name)
+            .bind(&key.secret_hash)
+            .bind(&key.scopes)
+            .bind(OffsetDateTime::from(key.created_at))
+            .bind(key.expires_at.map(OffsetDateTime::from))
+            .bind(key.revoked_at.map(OffsetDateTime::from))
+            .bind(key.tenant.as_str())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::from(DbError::from(e)))?;
+            Ok(())
+        })
+    }
+
+    fn record_usage(
+        &self,
+        id: &ApiKeyId,
+        at: SystemTime,
+    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
+        let uuid = to_uuid(id);
+        Box::pin(async move {
+            // 只往前推進，避免較慢的請求覆寫較新的時間
+            sqlx::query(
+                r#"UPDATE api_keys SET last_used_at = $2
+                   WHERE id = $1 AND (last_used_at IS NULL OR last_used_at < $2)"#,
+            )
+            .bind(uuid)
+            .bind(OffsetDateTime::from(at))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::from(DbError::from(e)))?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_conversion() {
+        let created_at = OffsetDateTime::now_utc();
+        let uuid = Uuid::now_v7();
+        let row = ApiKeyRow {
+            id: uuid,
+            tenant_id: "acme".to_string(),
+            name: "ci".to_string(),
+            secret_hash: "hash".to_string(),
+            scopes: vec!["users:read".to_string()],
+            created_at,
+            expires_at: None,
+            revoked_at: Some(created_at),
+            last_used_at: None,
+        };
+
+        let key = ApiKey::try_from(row).unwrap();
+
+        assert_eq!(to_uuid(&key.id), uuid);
+        assert!(key.id.to_string().starts_with("key_"));
+        assert_eq!(key.scopes, vec!["users:read".to_string()]);
+        assert!(key.is_revoked());
+        assert_eq!(key.tenant.as_str(), "acme");
+    }
+}
@@ -21,6 +21,11 @@ impl From<DbError> for DomainError {
             DbError::Sqlx(sqlx::Error::RowNotFound) => DomainError::NotFound {
                 message: "Entity not found".to_string(),
             },
+            DbError::Sqlx(sqlx::Error::PoolTimedOut | sqlx::Error::Io(_)) => {
+                DomainError::Unavailable {
+                    message: "Database connection unavailable".to_string(),
+                }
+            }
             DbError::Sqlx(sqlx_err) => {
                 if let Some(db_err) = sqlx_err.as_database_error() {
                     if db_err.is_unique_violation() {
@@ -28,6 +33,12 @@ impl From<DbError> for DomainError {
                             message: "Duplicate entry".to_string(),
                         };
                     }
+                    // serialization_failure 與 deadlock_detected：整個交易重來即可
+                    if matches!(db_err.code().as_deref(), Some("40001" | "40P01")) {
+                        return DomainError::Unavailable {
+                            message: "Transaction aborted by a concurrent update".to_string(),
+                        };
+                    }
                 }
                 DomainError::InvalidOperation {
                     message: "Database operation failed".to_string(),
@@ -36,3 +47,17 @@ impl From<DbError> for DomainError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_failures_are_retryable() {
+        let error = DomainError::from(DbError::from(sqlx::Error::PoolTimedOut));
+        assert!(error.is_retryable());
+
+        let error = DomainError::from(DbError::from(sqlx::Error::RowNotFound));
+        assert!(matches!(error, DomainError::NotFound { .. }));
+    }
+}
@@ -0,0 +1,217 @@
+use crate::error::DbError;
+use crate::models::IdempotencyRow;
+use contracts::{
+    AppError, IdempotencyClaim, IdempotencyFuture, IdempotencyStore, InfraError, StoredResponse,
+};
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+
+/// 認領時與並行的釋放競爭，最多重試的次數
+const CLAIM_ATTEMPTS: usize = 2;
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::Infrastructure(InfraError::from(DbError::from(e)))
+}
+
+/// Postgres 的冪等鍵記錄，由所有副本共用
+#[derive(Clone)]
+pub struct PostgresIdempotencyStore {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresIdempotencyStore {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    /// 插入新記錄，或取代已過期的記錄；成功時回傳 `true`
+    async fn try_insert(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        lock_ttl: Duration,
+    ) -> Result<bool, AppError> {
+        let inserted = sqlx::query(
+            r#"INSERT INTO idempotency_keys (key, fingerprint, expires_at)
+               VALUES ($1, $2, now() + make_interval(secs => $3))
+               ON CONFLICT (key) DO UPDATE
+               SET fingerprint = EXCLUDED.fingerprint,
+                   response_status = NULL,
+                   response_headers = NULL,
+                   response_body = NULL,
+                   created_at = now(),
+                   expires_at = EXCLUDED.expires_at
+               WHERE idempotency_keys.expires_at <= now()"#,
+        )
+        .bind(key)
+        .bind(fingerprint)
+        .bind(lock_ttl.as_secs_f64())
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?
+        .rows_affected();
+        Ok(inserted == 1)
+    }
+
+    async fn find(&self, key: &str) -> Result<Option<IdempotencyClaim>, AppError> {
+        let row: Option<IdempotencyRow> = sqlx::query_as(
+            r#"SELECT fingerprint, response_status, response_headers, response_body
+               FROM idempotency_keys
+               WHERE key = $1 AND expires_at > now()"#,
+        )
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+        row.map(IdempotencyClaim::try_from).transpose()
+    }
+}
+
+impl TryFrom<IdempotencyRow> for IdempotencyClaim {
+    type Error = AppError;
+
+    fn try_from(row: IdempotencyRow) -> Result<Self, Self::Error> {
+        let response = match (row.response_status, row.response_body) {
+            (Some(status), Some(body)) => {
+                let headers = match row.response_headers {
+                    Some(headers) => serde_json::from_value(headers).map_err(|e| {
+                        AppError::Infrastructure(InfraError::Database(format!(
+                            "Invalid stored idempotent response headers: {e}"
+                        )))
+                    })?,
+                    None => Vec::new(),
+                };
+                Some(StoredResponse {
+                    status: status as u16,
+                    headers,
+                    body,
+                })
+            }
+            _ => None,
+        };
+        Ok(IdempotencyClaim::Existing {
+            fingerprint: row.fingerprint,
+            response,
+        })
+    }
+}
+
+impl IdempotencyStore for PostgresIdempotencyStore {
+    fn claim<'a>(
+        &'a self,
+        key: &'a str,
+        fingerprint: &'a str,
+        lock_ttl: Duration,
+    ) -> IdempotencyFuture<'a, IdempotencyClaim> {
+        Box::pin(async move {
+            for _ in 0..CLAIM_ATTEMPTS {
+                if self.try_insert(key, fingerprint, lock_ttl).await? {
+                    return Ok(IdempotencyClaim::Acquired);
+                }
+                // 記錄可能在插入與查詢之間被釋放，此時重新認領
+                if let Some(existing) = self.find(key).await? {
+                    return Ok(existing);
+                }
+            }
+            Err(AppError::Infrastructure(InfraError::Database(format!(
+                "Unable to claim idempotency key after {CLAIM_ATTEMPTS} attempts"
+            ))))
+        })
+    }
+
+    fn complete<'a>(
+        &'a self,
+        key: &'a str,
+        response: StoredResponse,
+        ttl: Duration,
+    ) -> IdempotencyFuture<'a, ()> {
+        Box::pin(async move {
+            let headers = serde_json::to_value(&response.headers).map_err(|e| {
+                AppError::Infrastructure(InfraError::Database(format!(
+                    "Unable to encode idempotent response headers: {e}"
+                )))
+            })?;
+            sqlx::query(
+                r#"UPDATE idempotency_keys
+                   SET response_status = $2,
+                       response_headers = $3,
+                       response_body = $4,
+                       expires_at = now() + make_interval(secs => $5)
+                   WHERE key = $1"#,
+            )
+            .bind(key)
+            .bind(response.status as i16)
+            .bind(headers)
+            .bind(response.body)
+            .bind(ttl.as_secs_f64())
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+            Ok(())
+        })
+    }
+
+    fn release<'a>(&'a self, key: &'a str) -> IdempotencyFuture<'a, ()> {
+        Box::pin(async move {
+            sqlx::query("DELETE FROM idempotency_keys WHERE key = $1 AND response_status IS NULL")
+                .bind(key)
+                .execute(&self.pool)
+                .await
+                .map_err(db_error)?;
+            Ok(())
+        })
+    }
+
+    fn purge_expired(&self) -> IdempotencyFuture<'_, u64> {
+        Box::pin(async move {
+            let purged = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= now()")
+                .execute(&self.pool)
+                .await
+                .map_err(db_error)?
+                .rows_affected();
+            Ok(purged)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_conversion() {
+        let pending = IdempotencyClaim::try_from(IdempotencyRow {
+            fingerprint: "fp".to_string(),
+            response_status: None,
+            response_headers: None,
+            response_body: None,
+        })
+        .unwrap();
+        assert_eq!(
+            pending,
+            IdempotencyClaim::Existing {
+                fingerprint: "fp".to_string(),
+                response: None,
+            }
+        );
+
+        let completed = IdempotencyClaim::try_from(IdempotencyRow {
+            fingerprint: "fp".to_string(),
+            response_status: Some(201),
+            response_headers: Some(serde_json::json!([["content-type", "application/json"]])),
+            response_body: Some(b"{}".to_vec()),
+        })
+        .unwrap();
+        assert_eq!(
+            completed,
+            IdempotencyClaim::Existing {
+                fingerprint: "fp".to_string(),
+                response: Some(StoredResponse {
+                    status: 201,
+                    headers: vec![("content-type".to_string(), "application/json".to_string())],
+                    body: b"{}".to_vec(),
+                }),
+            }
+        );
+    }
+}
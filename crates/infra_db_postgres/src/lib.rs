@@ -1,3 +1,7 @@
+pub mod api_key_repo;
 pub mod error;
+pub mod idempotency;
 pub mod models;
+pub mod outbox;
+pub mod unit_of_work;
 pub mod user_repo;
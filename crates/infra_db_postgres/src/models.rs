@@ -1,7 +1,58 @@
 use sqlx::{types::Uuid, FromRow};
+use time::OffsetDateTime;
 
 #[derive(Debug, FromRow)]
 pub(crate) struct UserRow {
     pub id: Uuid,
     pub name: String,
+    pub email: Option<String>,
+    pub version: i64,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+    pub deleted_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, FromRow)]
+pub(crate) struct AuditRow {
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, FromRow)]
+pub(crate) struct InsertedUserRow {
+    pub id: Uuid,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, FromRow)]
+pub(crate) struct OutboxRow {
+    pub id: Uuid,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub occurred_at: OffsetDateTime,
+    pub attempts: i32,
+}
+
+#[derive(Debug, FromRow)]
+pub(crate) struct ApiKeyRow {
+    pub id: Uuid,
+    pub tenant_id: String,
+    pub name: String,
+    pub secret_hash: String,
+    pub scopes: Vec<String>,
+    pub created_at: OffsetDateTime,
+    pub expires_at: Option<OffsetDateTime>,
+    pub revoked_at: Option<OffsetDateTime>,
+    pub last_used_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, FromRow)]
+pub(crate) struct IdempotencyRow {
+    pub fingerprint: String,
+    pub response_status: Option<i16>,
+    pub response_headers: Option<serde_json::Value>,
+    pub response_body: Option<Vec<u8>>,
 }
@@ -0,0 +1,187 @@
+use crate::error::DbError;
+use crate::models::OutboxRow;
+use async_trait::async_trait;
+use contracts::{DomainError, DomainEvent, InfraError, OutboxMessage, OutboxStore};
+use sqlx::{PgExecutor, Pool, Postgres};
+use std::time::SystemTime;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// 認領後的訊息在租約期間不會被其他轉發器重複取得
+const CLAIM_LEASE_SECONDS: f64 = 30.0;
+
+/// 將領域事件寫入發件箱（可在連線池或交易上執行）
+pub(crate) async fn append_events<'e, E>(
+    executor: E,
+    events: &[DomainEvent],
+) -> Result<(), DomainError>
+where
+    E: PgExecutor<'e>,
+{
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let messages: Vec<OutboxMessage> = events.iter().map(OutboxMessage::from_event).collect();
+    let ids: Vec<Uuid> = messages.iter().map(|m| m.id).collect();
+    let aggregate_types: Vec<String> = messages.iter().map(|m| m.aggregate_type.clone()).collect();
+    let aggregate_ids: Vec<String> = messages.iter().map(|m| m.aggregate_id.clone()).collect();
+    let event_types: Vec<String> = messages.iter().map(|m| m.event_type.clone()).collect();
+    let payloads: Vec<serde_json::Value> = messages.iter().map(|m| m.payload.clone()).collect();
+    let occurred_at: Vec<OffsetDateTime> = messages
+        .iter()
+        .map(|m| OffsetDateTime::from(m.occurred_at))
+        .collect();
+
+    sqlx::query(
+        r#"INSERT INTO outbox_messages
+               (id, aggregate_type, aggregate_id, event_type, payload, occurred_at)
+           SELECT * FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::text[], $5::jsonb[], $6::timestamptz[])"#,
+    )
+    .bind(ids)
+    .bind(aggregate_types)
+    .bind(aggregate_ids)
+    .bind(event_types)
+    .bind(payloads)
+    .bind(occurred_at)
+    .execute(executor)
+    .await
+    .map_err(|e| DomainError::from(DbError::from(e)))?;
+    Ok(())
+}
+
+/// Postgres 發件箱 - 供轉發器認領與標記訊息
+#[derive(Clone)]
+pub struct PostgresOutbox {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresOutbox {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OutboxStore for PostgresOutbox {
+    async fn claim_pending(&self, limit: u32) -> Result<Vec<OutboxMessage>, InfraError> {
+        let rows: Vec<OutboxRow> = sqlx::query_as(
+            r#"UPDATE outbox_messages
+               SET locked_until = now() + make_interval(secs => $2)
+               WHERE id IN (
+                   SELECT id FROM outbox_messages m
+                   WHERE published_at IS NULL
+                     AND dead_lettered_at IS NULL
+                     AND (locked_until IS NULL OR locked_until < now())
+                     AND NOT EXISTS (
+                         SELECT 1 FROM outbox_messages earlier
+                         WHERE earlier.aggregate_type = m.aggregate_type
+                           AND earlier.aggregate_id = m.aggregate_id
+                           AND earlier.occurred_at < m.occurred_at
+                           AND earlier.published_at IS NULL
+                           AND earlier.dead_lettered_at IS NULL
+                           AND earlier.attempts > 0
+                     )
+                   ORDER BY occurred_at
+                   LIMIT $1
+                   FOR UPDATE SKIP LOCKED
+               )
+               RETURNING id, aggregate_type, aggregate_id, event_type, payload, occurred_at, attempts"#,
+        )
+        .bind(i64::from(limit))
+        .bind(CLAIM_LEASE_SECONDS)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(DbError::from)?;
+
+        let mut messages: Vec<OutboxMessage> = rows.into_iter().map(OutboxMessage::from).collect();
+        messages.sort_by_key(|m| m.occurred_at);
+        Ok(messages)
+    }
+
+    async fn mark_published(&self, id: Uuid) -> Result<(), InfraError> {
+        sqlx::query(
+            "UPDATE outbox_messages SET published_at = now(), locked_until = NULL WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(DbError::from)?;
+        Ok(())
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: SystemTime,
+    ) -> Result<(), InfraError> {
+        // 租約欄位同時作為重試時間，認領時跳過尚未到期的訊息
+        sqlx::query(
+            r#"UPDATE outbox_messages
+               SET attempts = attempts + 1, last_error = $2, locked_until = $3
+               WHERE id = $1"#,
+        )
+        .bind(id)
+        .bind(error)
+        .bind(OffsetDateTime::from(retry_at))
+        .execute(&self.pool)
+        .await
+        .map_err(DbError::from)?;
+        Ok(())
+    }
+
+    async fn mark_dead_lettered(&self, id: Uuid, error: &str) -> Result<(), InfraError> {
+        sqlx::query(
+            r#"UPDATE outbox_messages
+               SET attempts = attempts + 1, last_error = $2, locked_until = NULL,
+                   dead_lettered_at = now()
+               WHERE id = $1"#,
+        )
+        .bind(id)
+        .bind(error)
+        .execute(&self.pool)
+        .await
+        .map_err(DbError::from)?;
+        Ok(())
+    }
+}
+
+impl From<OutboxRow> for OutboxMessage {
+    fn from(row: OutboxRow) -> Self {
+        Self {
+            id: row.id,
+            aggregate_type: row.aggregate_type,
+            aggregate_id: row.aggregate_id,
+            event_type: row.event_type,
+            payload: row.payload,
+            occurred_at: row.occurred_at.into(),
+            attempts: u32::try_from(row.attempts).unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_conversion() {
+        let now = OffsetDateTime::now_utc();
+        let row = OutboxRow {
+            id: Uuid::now_v7(),
+            aggregate_type: "user".to_string(),
+            aggregate_id: "user-1".to_string(),
+            event_type: "user.created".to_string(),
+            payload: serde_json::json!({ "name": "Alice" }),
+            occurred_at: now,
+            attempts: 2,
+        };
+
+        let message = OutboxMessage::from(row);
+
+        assert_eq!(message.event_type, "user.created");
+        assert_eq!(message.attempts, 2);
+        assert_eq!(message.occurred_at, SystemTime::from(now));
+    }
+}
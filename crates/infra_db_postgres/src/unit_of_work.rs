@@ -0,0 +1,234 @@
+use crate::error::DbError;
+use crate::outbox::append_events;
+use crate::user_repo::{
+    begin_for_tenant, find_user, find_users, insert_users, list_users, purge_users, save_user,
+    scan_users,
+};
+use contracts::{
+    AuditTimestamps, DomainError, DomainEvent, TenantId, User, UserId, UserListQuery, UserScanQuery,
+};
+use domain::{
+    InsertManyFuture, OutboxRepository, UnitOfWork, UnitOfWorkFactory, UnitOfWorkFuture,
+    UserRepository,
+};
+use sqlx::{Pool, Postgres, Transaction};
+use std::{future::Future, pin::Pin, sync::Arc, time::SystemTime};
+use tokio::sync::Mutex;
+
+/// 交易在提交或回滾後即被取走，之後的操作都會失敗
+type SharedTx = Arc<Mutex<Option<Transaction<'static, Postgres>>>>;
+
+fn transaction_closed() -> DomainError {
+    DomainError::InvalidOperation {
+        message: "Transaction already completed".to_string(),
+    }
+}
+
+/// Postgres 工作單元工廠 - 每次 `begin` 開啟一個新的資料庫交易
+///
+/// 交易開始時設定 `app.tenant_id`，整個交易都受該租戶的 RLS 政策限制。
+#[derive(Clone)]
+pub struct PostgresUnitOfWorkFactory {
+    pool: Pool<Postgres>,
+    tenant: TenantId,
+}
+
+impl PostgresUnitOfWorkFactory {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self {
+            pool,
+            tenant: TenantId::default(),
+        }
+    }
+}
+
+impl UnitOfWorkFactory for PostgresUnitOfWorkFactory {
+    fn begin(&self) -> UnitOfWorkFuture<'_> {
+        Box::pin(async move {
+            let tx = begin_for_tenant(&self.pool, self.tenant.as_str()).await?;
+            let tx: SharedTx = Arc::new(Mutex::new(Some(tx)));
+            Ok(Box::new(PostgresUnitOfWork {
+                users: PostgresTxUserRepository {
+                    tx: tx.clone(),
+                    tenant: self.tenant.clone(),
+                },
+                outbox: PostgresTxOutbox { tx: tx.clone() },
+                tx,
+            }) as Box<dyn UnitOfWork>)
+        })
+    }
+
+    fn for_tenant(&self, tenant: &TenantId) -> Arc<dyn UnitOfWorkFactory> {
+        Arc::new(Self {
+            pool: self.pool.clone(),
+            tenant: tenant.clone(),
+        })
+    }
+}
+
+/// Postgres 工作單元 - 持有交易並提供交易範圍內的儲存庫
+pub struct PostgresUnitOfWork {
+    tx: SharedTx,
+    users: PostgresTxUserRepository,
+    outbox: PostgresTxOutbox,
+}
+
+impl UnitOfWork for PostgresUnitOfWork {
+    fn users(&self) -> &dyn UserRepository {
+        &self.users
+    }
+
+    fn outbox(&self) -> &dyn OutboxRepository {
+        &self.outbox
+    }
+
+    fn commit(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>> {
+        Box::pin(async move {
+            let tx = self.tx.lock().await.take().ok_or_else(transaction_closed)?;
+            tx.commit()
+                .await
+                .map_err(|e| DomainError::from(DbError::from(e)))
+        })
+    }
+
+    fn rollback(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>> {
+        Box::pin(async move {
+            let tx = self.tx.lock().await.take().ok_or_else(transaction_closed)?;
+            tx.rollback()
+                .await
+                .map_err(|e| DomainError::from(DbError::from(e)))
+        })
+    }
+}
+
+/// 交易範圍內的用戶儲存庫
+///
+/// `for_tenant` 取得的儲存庫共用同一個交易；交易仍受開啟時租戶的 RLS 政策限制，
+/// 因此無法存取其他租戶的資料。
+pub struct PostgresTxUserRepository {
+    tx: SharedTx,
+    tenant: TenantId,
+}
+
+impl UserRepository for PostgresTxUserRepository {
+    fn for_tenant(&self, tenant: &TenantId) -> Arc<dyn UserRepository> {
+        Arc::new(Self {
+            tx: self.tx.clone(),
+            tenant: tenant.clone(),
+        })
+    }
+
+    fn find(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+        let id = id.clone();
+        Box::pin(async move {
+            let mut guard = self.tx.lock().await;
+            let tx = guard.as_mut().ok_or_else(transaction_closed)?;
+            find_user(&mut **tx, &self.tenant, &id, false).await
+        })
+    }
+
+    fn find_including_deleted(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+        let id = id.clone();
+        Box::pin(async move {
+            let mut guard = self.tx.lock().await;
+            let tx = guard.as_mut().ok_or_else(transaction_closed)?;
+            find_user(&mut **tx, &self.tenant, &id, true).await
+        })
+    }
+
+    fn find_many(
+        &self,
+        ids: &[UserId],
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        let ids = ids.to_vec();
+        Box::pin(async move {
+            let mut guard = self.tx.lock().await;
+            let tx = guard.as_mut().ok_or_else(transaction_closed)?;
+            find_users(&mut **tx, &self.tenant, &ids).await
+        })
+    }
+
+    fn list(
+        &self,
+        query: UserListQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut guard = self.tx.lock().await;
+            let tx = guard.as_mut().ok_or_else(transaction_closed)?;
+            list_users(&mut **tx, &self.tenant, query).await
+        })
+    }
+
+    fn scan(
+        &self,
+        query: UserScanQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut guard = self.tx.lock().await;
+            let tx = guard.as_mut().ok_or_else(transaction_closed)?;
+            scan_users(&mut **tx, &self.tenant, query).await
+        })
+    }
+
+    fn save(
+        &self,
+        user: &User,
+    ) -> Pin<Box<dyn Future<Output = Result<AuditTimestamps, DomainError>> + Send + '_>> {
+        let user = user.clone();
+        Box::pin(async move {
+            let mut guard = self.tx.lock().await;
+            let tx = guard.as_mut().ok_or_else(transaction_closed)?;
+            save_user(&mut **tx, &self.tenant, &user).await
+        })
+    }
+
+    fn insert_many(&self, users: &[User]) -> InsertManyFuture<'_> {
+        let users = users.to_vec();
+        Box::pin(async move {
+            let mut guard = self.tx.lock().await;
+            let tx = guard.as_mut().ok_or_else(transaction_closed)?;
+            insert_users(&mut **tx, &self.tenant, &users).await
+        })
+    }
+
+    fn purge_deleted_before(
+        &self,
+        cutoff: SystemTime,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, DomainError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut guard = self.tx.lock().await;
+            let tx = guard.as_mut().ok_or_else(transaction_closed)?;
+            purge_users(&mut **tx, cutoff).await
+        })
+    }
+
+    fn shutdown(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        // 連線池由非交易的儲存庫負責關閉
+        Box::pin(async {})
+    }
+}
+
+/// 交易範圍內的發件箱
+pub struct PostgresTxOutbox {
+    tx: SharedTx,
+}
+
+impl OutboxRepository for PostgresTxOutbox {
+    fn append(
+        &self,
+        events: &[DomainEvent],
+    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
+        let events = events.to_vec();
+        Box::pin(async move {
+            let mut guard = self.tx.lock().await;
+            let tx = guard.as_mut().ok_or_else(transaction_closed)?;
+            append_events(&mut **tx, &events).await
+        })
+    }
+}
@@ -1,14 +1,28 @@
 use crate::error::DbError;
-use crate::models::UserRow;
-use contracts::{DomainError, User, UserId};
-use domain::UserRepository;
-use sqlx::{postgres::PgPoolOptions, Pool, Postgres};
-use std::{future::Future, pin::Pin};
+use crate::models::{AuditRow, InsertedUserRow, UserRow};
+use contracts::{
+    AuditTimestamps, DomainError, Email, TenantId, User, UserId, UserListQuery, UserName,
+    UserScanQuery,
+};
+use domain::{InsertManyFuture, UserRepository};
+use sqlx::{postgres::PgPoolOptions, PgExecutor, Pool, Postgres, Transaction};
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc, time::SystemTime};
+use time::OffsetDateTime;
 use uuid::Uuid;
 
+const USER_COLUMNS: &str = "id, name, email, version, created_at, updated_at, deleted_at";
+
+/// 清除作業用來跨越所有租戶的 `app.tenant_id` 值（不是合法的租戶 ID）
+const ALL_TENANTS: &str = "*";
+
+/// Postgres 用戶儲存庫
+///
+/// 每個語句都以 `tenant_id` 過濾；另外在設定了 `app.tenant_id` 的短交易中執行，
+/// 讓資料列層級安全性（RLS）政策作為第二道防線。
 #[derive(Clone)]
 pub struct PostgresUserRepository {
     pool: Pool<Postgres>,
+    tenant: TenantId,
 }
 
 impl PostgresUserRepository {
@@ -17,56 +31,429 @@ impl PostgresUserRepository {
             .max_connections(max_conn)
             .connect(url)
             .await?;
-        Ok(Self { pool })
+        Ok(Self::from_pool(pool))
+    }
+
+    /// 使用既有連線池建立儲存庫（與工作單元共用同一個池）
+    pub fn from_pool(pool: Pool<Postgres>) -> Self {
+        Self {
+            pool,
+            tenant: TenantId::default(),
+        }
+    }
+
+    /// 取得底層連線池
+    pub fn pool(&self) -> &Pool<Postgres> {
+        &self.pool
+    }
+}
+
+fn to_uuid(id: &UserId) -> Uuid {
+    Uuid::from_bytes(id.as_bytes())
+}
+
+/// 開啟交易並設定 RLS 政策使用的 `app.tenant_id`（僅在此交易內有效）
+pub(crate) async fn begin_for_tenant(
+    pool: &Pool<Postgres>,
+    tenant: &str,
+) -> Result<Transaction<'static, Postgres>, DomainError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| DomainError::from(DbError::from(e)))?;
+    sqlx::query("SELECT set_config('app.tenant_id', $1, true)")
+        .bind(tenant)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::from(DbError::from(e)))?;
+    Ok(tx)
+}
+
+async fn commit<T>(tx: Transaction<'static, Postgres>, value: T) -> Result<T, DomainError> {
+    tx.commit()
+        .await
+        .map_err(|e| DomainError::from(DbError::from(e)))?;
+    Ok(value)
+}
+
+/// 查詢單一用戶（可在連線池或交易上執行）
+///
+/// `include_deleted` 為 false 時，已軟刪除的用戶視為不存在。
+pub(crate) async fn find_user<'e, E>(
+    executor: E,
+    tenant: &TenantId,
+    id: &UserId,
+    include_deleted: bool,
+) -> Result<User, DomainError>
+where
+    E: PgExecutor<'e>,
+{
+    let uuid = to_uuid(id);
+
+    let row: UserRow = sqlx::query_as(&format!(
+        r#"SELECT {USER_COLUMNS} FROM users
+           WHERE id = $1 AND tenant_id = $3 AND ($2 OR deleted_at IS NULL)"#
+    ))
+    .bind(uuid)
+    .bind(include_deleted)
+    .bind(tenant.as_str())
+    .fetch_one(executor)
+    .await
+    .map_err(|e| DomainError::from(DbError::from(e)))?;
+
+    row.try_into()
+}
+
+/// 以單一查詢取得多個未刪除的用戶，不存在者略過
+pub(crate) async fn find_users<'e, E>(
+    executor: E,
+    tenant: &TenantId,
+    ids: &[UserId],
+) -> Result<Vec<User>, DomainError>
+where
+    E: PgExecutor<'e>,
+{
+    let uuids: Vec<Uuid> = ids.iter().map(to_uuid).collect();
+
+    let rows: Vec<UserRow> = sqlx::query_as(&format!(
+        r#"SELECT {USER_COLUMNS} FROM users
+           WHERE id = ANY($1) AND tenant_id = $2 AND deleted_at IS NULL"#
+    ))
+    .bind(uuids)
+    .bind(tenant.as_str())
+    .fetch_all(executor)
+    .await
+    .map_err(|e| DomainError::from(DbError::from(e)))?;
+
+    rows.into_iter().map(User::try_from).collect()
+}
+
+/// 依建立時間分頁列出用戶
+pub(crate) async fn list_users<'e, E>(
+    executor: E,
+    tenant: &TenantId,
+    query: UserListQuery,
+) -> Result<Vec<User>, DomainError>
+where
+    E: PgExecutor<'e>,
+{
+    let offset = i64::try_from(query.offset).unwrap_or(i64::MAX);
+
+    let rows: Vec<UserRow> = sqlx::query_as(&format!(
+        r#"SELECT {USER_COLUMNS} FROM users
+           WHERE tenant_id = $4 AND ($1 OR deleted_at IS NULL)
+           ORDER BY created_at, id
+           LIMIT $2 OFFSET $3"#
+    ))
+    .bind(query.include_deleted)
+    .bind(i64::from(query.limit))
+    .bind(offset)
+    .bind(tenant.as_str())
+    .fetch_all(executor)
+    .await
+    .map_err(|e| DomainError::from(DbError::from(e)))?;
+
+    rows.into_iter().map(User::try_from).collect()
+}
+
+/// 依 ID 排序逐批讀取用戶（keyset 分頁，使用主鍵索引）
+pub(crate) async fn scan_users<'e, E>(
+    executor: E,
+    tenant: &TenantId,
+    query: UserScanQuery,
+) -> Result<Vec<User>, DomainError>
+where
+    E: PgExecutor<'e>,
+{
+    let rows: Vec<UserRow> = sqlx::query_as(&format!(
+        r#"SELECT {USER_COLUMNS} FROM users
+           WHERE tenant_id = $4 AND ($1::uuid IS NULL OR id > $1)
+             AND ($2 OR deleted_at IS NULL)
+           ORDER BY id
+           LIMIT $3"#
+    ))
+    .bind(query.after.as_ref().map(to_uuid))
+    .bind(query.include_deleted)
+    .bind(i64::from(query.limit))
+    .bind(tenant.as_str())
+    .fetch_all(executor)
+    .await
+    .map_err(|e| DomainError::from(DbError::from(e)))?;
+
+    rows.into_iter().map(User::try_from).collect()
+}
+
+/// 以單一語句批次新增用戶，已存在的 ID 略過
+///
+/// 回傳值與輸入順序對應；略過的用戶為 `None`。主鍵為（租戶, ID），
+/// 只有同一個租戶已使用的 ID 會被略過，其他租戶的資料不影響結果。
+pub(crate) async fn insert_users<'e, E>(
+    executor: E,
+    tenant: &TenantId,
+    users: &[User],
+) -> Result<Vec<Option<AuditTimestamps>>, DomainError>
+where
+    E: PgExecutor<'e>,
+{
+    if users.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ids: Vec<Uuid> = users.iter().map(|u| to_uuid(&u.id)).collect();
+    let names: Vec<&str> = users.iter().map(|u| u.name.as_str()).collect();
+    let emails: Vec<Option<&str>> = users
+        .iter()
+        .map(|u| u.email.as_ref().map(Email::as_str))
+        .collect();
+    let deleted_at: Vec<Option<OffsetDateTime>> = users
+        .iter()
+        .map(|u| u.deleted_at.map(OffsetDateTime::from))
+        .collect();
+
+    let rows: Vec<InsertedUserRow> = sqlx::query_as(
+        r#"INSERT INTO users
+             (id, tenant_id, name, email, version, created_at, updated_at, deleted_at)
+           SELECT id, $5, name, email, 1, now(), now(), deleted_at
+           FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::timestamptz[])
+                AS input(id, name, email, deleted_at)
+           ON CONFLICT (tenant_id, id) DO NOTHING
+           RETURNING id, created_at, updated_at"#,
+    )
+    .bind(&ids)
+    .bind(names)
+    .bind(emails)
+    .bind(deleted_at)
+    .bind(tenant.as_str())
+    .fetch_all(executor)
+    .await
+    .map_err(|e| DomainError::from(DbError::from(e)))?;
+
+    let mut inserted: HashMap<Uuid, AuditTimestamps> = rows
+        .into_iter()
+        .map(|row| {
+            let audit = AuditTimestamps {
+                created_at: row.created_at.into(),
+                updated_at: row.updated_at.into(),
+            };
+            (row.id, audit)
+        })
+        .collect();
+    Ok(ids.iter().map(|id| inserted.remove(id)).collect())
+}
+
+/// 永久移除在 `cutoff` 之前軟刪除的用戶（所有租戶）
+pub(crate) async fn purge_users<'e, E>(executor: E, cutoff: SystemTime) -> Result<u64, DomainError>
+where
+    E: PgExecutor<'e>,
+{
+    let done = sqlx::query("DELETE FROM users WHERE deleted_at < $1")
+        .bind(OffsetDateTime::from(cutoff))
+        .execute(executor)
+        .await
+        .map_err(|e| DomainError::from(DbError::from(e)))?;
+
+    Ok(done.rows_affected())
+}
+
+fn version_conflict(user: &User) -> DomainError {
+    DomainError::Conflict {
+        message: format!(
+            "User {} was modified concurrently (expected version {})",
+            user.id, user.version
+        ),
+    }
+}
+
+/// 新增或更新用戶（可在連線池或交易上執行）
+///
+/// 新用戶以 INSERT 寫入版本 1；既有用戶只有在版本號相符時才會更新，
+/// 否則回傳 `DomainError::Conflict`。`created_at`/`updated_at` 由資料庫時間維護。
+pub(crate) async fn save_user<'e, E>(
+    executor: E,
+    tenant: &TenantId,
+    user: &User,
+) -> Result<AuditTimestamps, DomainError>
+where
+    E: PgExecutor<'e>,
+{
+    let uuid = to_uuid(&user.id);
+    let expected = i64::try_from(user.version).map_err(|_| DomainError::InvalidOperation {
+        message: "Version out of range".to_string(),
+    })?;
+    let deleted_at = user.deleted_at.map(OffsetDateTime::from);
+    let email = user.email.as_ref().map(Email::as_str);
+
+    let result = if user.is_new() {
+        sqlx::query_as::<_, AuditRow>(
+            r#"INSERT INTO users
+                 (id, tenant_id, name, email, version, created_at, updated_at, deleted_at)
+               VALUES ($1, $5, $2, $4, 1, now(), now(), $3)
+               RETURNING created_at, updated_at"#,
+        )
+        .bind(uuid)
+        .bind(user.name.as_str())
+        .bind(deleted_at)
+        .bind(email)
+        .bind(tenant.as_str())
+        .fetch_optional(executor)
+        .await
+    } else {
+        sqlx::query_as::<_, AuditRow>(
+            r#"UPDATE users
+               SET name = $2, email = $5, deleted_at = $4,
+                   version = version + 1, updated_at = now()
+               WHERE id = $1 AND tenant_id = $6 AND version = $3
+               RETURNING created_at, updated_at"#,
+        )
+        .bind(uuid)
+        .bind(user.name.as_str())
+        .bind(expected)
+        .bind(deleted_at)
+        .bind(email)
+        .bind(tenant.as_str())
+        .fetch_optional(executor)
+        .await
+    };
+
+    match result {
+        Ok(Some(row)) => Ok(row.into()),
+        Ok(None) => Err(version_conflict(user)),
+        Err(e) if is_unique_violation(&e) => Err(version_conflict(user)),
+        Err(e) => Err(DomainError::from(DbError::from(e))),
+    }
+}
+
+fn is_unique_violation(error: &sqlx::Error) -> bool {
+    error
+        .as_database_error()
+        .is_some_and(|db_err| db_err.is_unique_violation())
+}
+
+impl From<AuditRow> for AuditTimestamps {
+    fn from(row: AuditRow) -> Self {
+        AuditTimestamps {
+            created_at: row.created_at.into(),
+            updated_at: row.updated_at.into(),
+        }
+    }
+}
+
+/// 資料列在載入時重新經過值物件驗證，不合法的資料不會進入領域層
+impl TryFrom<UserRow> for User {
+    type Error = DomainError;
+
+    fn try_from(row: UserRow) -> Result<Self, Self::Error> {
+        let user_id = UserId::from_bytes(row.id.into_bytes());
+        let email = row.email.map(Email::parse).transpose()?;
+        Ok(User::reconstitute(
+            user_id,
+            UserName::parse(row.name)?,
+            u64::try_from(row.version).unwrap_or_default(),
+        )
+        .with_email(email)
+        .with_audit(AuditTimestamps {
+            created_at: row.created_at.into(),
+            updated_at: row.updated_at.into(),
+        })
+        .with_deleted_at(row.deleted_at.map(SystemTime::from)))
     }
 }
 
 impl UserRepository for PostgresUserRepository {
+    fn for_tenant(&self, tenant: &TenantId) -> Arc<dyn UserRepository> {
+        Arc::new(Self {
+            pool: self.pool.clone(),
+            tenant: tenant.clone(),
+        })
+    }
+
     fn find(
         &self,
         id: &UserId,
     ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
-        let id_string = id.as_str().to_string();
-        let pool = self.pool.clone();
+        let id = id.clone();
+        Box::pin(async move {
+            let mut tx = begin_for_tenant(&self.pool, self.tenant.as_str()).await?;
+            let user = find_user(&mut *tx, &self.tenant, &id, false).await?;
+            commit(tx, user).await
+        })
+    }
+
+    fn find_including_deleted(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+        let id = id.clone();
+        Box::pin(async move {
+            let mut tx = begin_for_tenant(&self.pool, self.tenant.as_str()).await?;
+            let user = find_user(&mut *tx, &self.tenant, &id, true).await?;
+            commit(tx, user).await
+        })
+    }
+
+    fn find_many(
+        &self,
+        ids: &[UserId],
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        let ids = ids.to_vec();
+        Box::pin(async move {
+            let mut tx = begin_for_tenant(&self.pool, self.tenant.as_str()).await?;
+            let users = find_users(&mut *tx, &self.tenant, &ids).await?;
+            commit(tx, users).await
+        })
+    }
+
+    fn list(
+        &self,
+        query: UserListQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut tx = begin_for_tenant(&self.pool, self.tenant.as_str()).await?;
+            let users = list_users(&mut *tx, &self.tenant, query).await?;
+            commit(tx, users).await
+        })
+    }
+
+    fn scan(
+        &self,
+        query: UserScanQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
         Box::pin(async move {
-            let uuid = Uuid::parse_str(&id_string).map_err(|_| DomainError::InvalidOperation {
-                message: "Invalid ID format".to_string(),
-            })?;
-
-            let row: Result<UserRow, sqlx::Error> =
-                sqlx::query_as("SELECT id, name FROM users WHERE id = $1")
-                    .bind(uuid)
-                    .fetch_one(&pool)
-                    .await;
-
-            let row = row.map_err(|e| DomainError::from(DbError::from(e)))?;
-            let user_id = UserId::from_string(row.id.to_string());
-            User::new(user_id, row.name)
+            let mut tx = begin_for_tenant(&self.pool, self.tenant.as_str()).await?;
+            let users = scan_users(&mut *tx, &self.tenant, query).await?;
+            commit(tx, users).await
         })
     }
 
     fn save(
         &self,
         user: &User,
-    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
-        let id_string = user.id.as_str().to_string();
-        let name = user.name.clone();
-        let pool = self.pool.clone();
+    ) -> Pin<Box<dyn Future<Output = Result<AuditTimestamps, DomainError>> + Send + '_>> {
+        let user = user.clone();
+        Box::pin(async move {
+            let mut tx = begin_for_tenant(&self.pool, self.tenant.as_str()).await?;
+            let audit = save_user(&mut *tx, &self.tenant, &user).await?;
+            commit(tx, audit).await
+        })
+    }
+
+    fn insert_many(&self, users: &[User]) -> InsertManyFuture<'_> {
+        let users = users.to_vec();
+        Box::pin(async move {
+            let mut tx = begin_for_tenant(&self.pool, self.tenant.as_str()).await?;
+            let inserted = insert_users(&mut *tx, &self.tenant, &users).await?;
+            commit(tx, inserted).await
+        })
+    }
+
+    fn purge_deleted_before(
+        &self,
+        cutoff: SystemTime,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, DomainError>> + Send + '_>> {
         Box::pin(async move {
-            let uuid = Uuid::parse_str(&id_string).map_err(|_| DomainError::InvalidOperation {
-                message: "Invalid ID format".to_string(),
-            })?;
-
-            sqlx::query(
-                r#"INSERT INTO users (id, name) VALUES ($1, $2)
-                   ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name"#,
-            )
-            .bind(uuid)
-            .bind(name)
-            .execute(&pool)
-            .await
-            .map_err(|e| DomainError::from(DbError::from(e)))?;
-            Ok(())
+            let mut tx = begin_for_tenant(&self.pool, ALL_TENANTS).await?;
+            let purged = purge_users(&mut *tx, cutoff).await?;
+            commit(tx, purged).await
         })
     }
 
@@ -82,18 +469,50 @@ impl UserRepository for PostgresUserRepository {
 mod tests {
     use super::*;
 
+    fn row(name: &str, email: Option<&str>) -> UserRow {
+        let created_at = OffsetDateTime::now_utc();
+        UserRow {
+            id: Uuid::now_v7(),
+            name: name.to_string(),
+            email: email.map(str::to_string),
+            version: 3,
+            created_at,
+            updated_at: created_at,
+            deleted_at: Some(created_at),
+        }
+    }
+
     #[test]
     fn test_user_conversion() {
-        let uuid = Uuid::now_v7();
-        let user_row = UserRow {
-            id: uuid,
-            name: "Test User".to_string(),
-        };
+        let user_row = row("Test User", Some("test@example.com"));
+        let uuid = user_row.id;
+        let created_at = user_row.created_at;
+
+        let user = User::try_from(user_row).unwrap();
 
-        let user_id = UserId::from_string(uuid.to_string());
-        let user = User::new(user_id.clone(), user_row.name.clone()).unwrap();
+        assert_eq!(user.id.as_u128(), uuid.as_u128());
+        assert!(user.id.to_string().starts_with("usr_"));
+        assert_eq!(to_uuid(&user.id), uuid);
+        assert_eq!(user.name.as_str(), "Test User");
+        assert_eq!(
+            user.email.as_ref().map(Email::as_str),
+            Some("test@example.com")
+        );
+        assert_eq!(user.version, 3);
+        assert_eq!(
+            user.audit.map(|a| a.created_at),
+            Some(SystemTime::from(created_at))
+        );
+        assert!(user.is_deleted());
+        assert!(user.pending_events().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_row_is_rejected() {
+        let result = User::try_from(row("", None));
+        assert!(matches!(result, Err(DomainError::ValidationError { .. })));
 
-        assert_eq!(user.id, user_id);
-        assert_eq!(user.name, user_row.name);
+        let result = User::try_from(row("Test User", Some("not-an-email")));
+        assert!(matches!(result, Err(DomainError::ValidationError { .. })));
     }
 }
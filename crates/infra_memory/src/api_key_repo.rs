@@ -0,0 +1,110 @@
+use contracts::{ApiKey, ApiKeyId, DomainError};
+use domain::ApiKeyRepository;
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, RwLock},
+    time::SystemTime,
+};
+
+/// 記憶體內的 API 金鑰儲存庫，複製後共用同一份資料
+#[derive(Clone, Default)]
+pub struct InMemoryApiKeyRepository {
+    keys: Arc<RwLock<HashMap<ApiKeyId, ApiKey>>>,
+}
+
+impl InMemoryApiKeyRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 直接讀取儲存的金鑰（供測試檢查）
+    pub fn get(&self, id: &ApiKeyId) -> Option<ApiKey> {
+        self.keys
+            .read()
+            .expect("api key store poisoned")
+            .get(id)
+            .cloned()
+    }
+}
+
+fn not_found(id: &ApiKeyId) -> DomainError {
+    DomainError::NotFound {
+        message: format!("API key {id} not found"),
+    }
+}
+
+impl ApiKeyRepository for InMemoryApiKeyRepository {
+    fn find(
+        &self,
+        id: &ApiKeyId,
+    ) -> Pin<Box<dyn Future<Output = Result<ApiKey, DomainError>> + Send + '_>> {
+        let result = self.get(id).ok_or_else(|| not_found(id));
+        Box::pin(async move { result })
+    }
+
+    fn save(
+        &self,
+        key: &ApiKey,
+    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
+        self.keys
+            .write()
+            .expect("api key store poisoned")
+            .insert(key.id.clone(), key.clone());
+        Box::pin(async { Ok(()) })
+    }
+
+    fn record_usage(
+        &self,
+        id: &ApiKeyId,
+        at: SystemTime,
+    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
+        let result = match self
+            .keys
+            .write()
+            .expect("api key store poisoned")
+            .get_mut(id)
+        {
+            Some(key) => {
+                key.last_used_at = Some(at);
+                Ok(())
+            }
+            None => Err(not_found(id)),
+        };
+        Box::pin(async move { result })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::TenantId;
+
+    #[tokio::test]
+    async fn test_record_usage_only_touches_last_used() {
+        let repo = InMemoryApiKeyRepository::new();
+        let key = ApiKey::issue(
+            ApiKeyId::from_u128(1),
+            TenantId::default(),
+            "ci",
+            "hash".to_string(),
+            Vec::new(),
+            None,
+            SystemTime::now(),
+        )
+        .unwrap();
+        repo.save(&key).await.unwrap();
+
+        let used_at = SystemTime::now();
+        repo.record_usage(&key.id, used_at).await.unwrap();
+
+        let stored = repo.find(&key.id).await.unwrap();
+        assert_eq!(stored.last_used_at, Some(used_at));
+        assert_eq!(stored.secret_hash, key.secret_hash);
+        assert!(matches!(
+            repo.record_usage(&ApiKeyId::from_u128(2), used_at).await,
+            Err(DomainError::NotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,112 @@
+use contracts::{Cache, CacheFuture};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// 超過此數量的鍵時，寫入前先清掉已過期的項目
+const SWEEP_THRESHOLD: usize = 10_000;
+
+struct Entry {
+    value: Vec<u8>,
+    expires_at: Instant,
+}
+
+/// 記憶體內的快取，複製後共用同一份資料；過期項目在讀取或清理時移除
+#[derive(Clone, Default)]
+pub struct InMemoryCache {
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl InMemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn get_at(&self, key: &str, now: Instant) -> Option<Vec<u8>> {
+        let mut entries = self.entries.lock().expect("cache poisoned");
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > now => Some(entry.value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn set_at(&self, key: &str, value: Vec<u8>, ttl: Duration, now: Instant) {
+        let mut entries = self.entries.lock().expect("cache poisoned");
+        if entries.len() >= SWEEP_THRESHOLD {
+            entries.retain(|_, entry| entry.expires_at > now);
+        }
+        entries.insert(
+            key.to_string(),
+            Entry {
+                value,
+                expires_at: now + ttl,
+            },
+        );
+    }
+}
+
+impl Cache for InMemoryCache {
+    fn get_bytes<'a>(&'a self, key: &'a str) -> CacheFuture<'a, Option<Vec<u8>>> {
+        let value = self.get_at(key, Instant::now());
+        Box::pin(async move { Ok(value) })
+    }
+
+    fn set_bytes<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl: Duration) -> CacheFuture<'a, ()> {
+        self.set_at(key, value, ttl, Instant::now());
+        Box::pin(async { Ok(()) })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> CacheFuture<'a, ()> {
+        self.entries.lock().expect("cache poisoned").remove(key);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let cache = InMemoryCache::new();
+        let now = Instant::now();
+        cache.set_at("k", b"v".to_vec(), Duration::from_secs(5), now);
+
+        assert_eq!(cache.get_at("k", now), Some(b"v".to_vec()));
+        assert_eq!(cache.get_at("k", now + Duration::from_secs(5)), None);
+    }
+
+    #[tokio::test]
+    async fn test_delete_through_port() {
+        let cache = InMemoryCache::new();
+        cache
+            .set_bytes("k", b"v".to_vec(), Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        cache.delete("k").await.unwrap();
+        cache.delete("missing").await.unwrap();
+
+        assert_eq!(cache.get_bytes("k").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_typed_values_round_trip_as_json() {
+        let cache: contracts::DynCache = Arc::new(InMemoryCache::new());
+        let ttl = Duration::from_secs(60);
+        cache.set("names", &["a", "b"], ttl).await.unwrap();
+
+        let names: Option<Vec<String>> = cache.get("names").await.unwrap();
+        assert_eq!(names, Some(vec!["a".to_string(), "b".to_string()]));
+
+        // 內容無法解析成要求的型別時視為未命中
+        let wrong: Option<u64> = cache.get("names").await.unwrap();
+        assert_eq!(wrong, None);
+    }
+}
@@ -0,0 +1,186 @@
+use contracts::{IdempotencyClaim, IdempotencyFuture, IdempotencyStore, StoredResponse};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// 超過此數量的記錄時，認領前先清掉已過期的記錄
+const SWEEP_THRESHOLD: usize = 10_000;
+
+struct Record {
+    fingerprint: String,
+    response: Option<StoredResponse>,
+    expires_at: Instant,
+}
+
+/// 記憶體內的冪等鍵記錄，僅在單一實例內有效
+#[derive(Clone, Default)]
+pub struct InMemoryIdempotencyStore {
+    records: Arc<Mutex<HashMap<String, Record>>>,
+}
+
+impl InMemoryIdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn claim_at(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        lock_ttl: Duration,
+        now: Instant,
+    ) -> IdempotencyClaim {
+        let mut records = self.records.lock().expect("idempotency store poisoned");
+        if records.len() >= SWEEP_THRESHOLD {
+            records.retain(|_, record| record.expires_at > now);
+        }
+
+        if let Some(record) = records.get(key).filter(|record| record.expires_at > now) {
+            return IdempotencyClaim::Existing {
+                fingerprint: record.fingerprint.clone(),
+                response: record.response.clone(),
+            };
+        }
+        records.insert(
+            key.to_string(),
+            Record {
+                fingerprint: fingerprint.to_string(),
+                response: None,
+                expires_at: now + lock_ttl,
+            },
+        );
+        IdempotencyClaim::Acquired
+    }
+
+    fn complete_at(&self, key: &str, response: StoredResponse, ttl: Duration, now: Instant) {
+        let mut records = self.records.lock().expect("idempotency store poisoned");
+        if let Some(record) = records.get_mut(key) {
+            record.response = Some(response);
+            record.expires_at = now + ttl;
+        }
+    }
+
+    fn purge_at(&self, now: Instant) -> u64 {
+        let mut records = self.records.lock().expect("idempotency store poisoned");
+        let before = records.len();
+        records.retain(|_, record| record.expires_at > now);
+        (before - records.len()) as u64
+    }
+}
+
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    fn claim<'a>(
+        &'a self,
+        key: &'a str,
+        fingerprint: &'a str,
+        lock_ttl: Duration,
+    ) -> IdempotencyFuture<'a, IdempotencyClaim> {
+        let claim = self.claim_at(key, fingerprint, lock_ttl, Instant::now());
+        Box::pin(async move { Ok(claim) })
+    }
+
+    fn complete<'a>(
+        &'a self,
+        key: &'a str,
+        response: StoredResponse,
+        ttl: Duration,
+    ) -> IdempotencyFuture<'a, ()> {
+        self.complete_at(key, response, ttl, Instant::now());
+        Box::pin(async move { Ok(()) })
+    }
+
+    fn release<'a>(&'a self, key: &'a str) -> IdempotencyFuture<'a, ()> {
+        // 已完成的記錄保留到過期
+        let mut records = self.records.lock().expect("idempotency store poisoned");
+        if records
+            .get(key)
+            .is_some_and(|record| record.response.is_none())
+        {
+            records.remove(key);
+        }
+        drop(records);
+        Box::pin(async move { Ok(()) })
+    }
+
+    fn purge_expired(&self) -> IdempotencyFuture<'_, u64> {
+        let purged = self.purge_at(Instant::now());
+        Box::pin(async move { Ok(purged) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response() -> StoredResponse {
+        StoredResponse {
+            status: 201,
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: b"{}".to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_claim_complete_and_expire() {
+        let store = InMemoryIdempotencyStore::new();
+        let lock_ttl = Duration::from_secs(30);
+        let start = Instant::now();
+
+        assert_eq!(
+            store.claim_at("k", "fp", lock_ttl, start),
+            IdempotencyClaim::Acquired
+        );
+        assert_eq!(
+            store.claim_at("k", "fp", lock_ttl, start),
+            IdempotencyClaim::Existing {
+                fingerprint: "fp".to_string(),
+                response: None,
+            }
+        );
+
+        store.complete_at("k", response(), Duration::from_secs(3600), start);
+        assert_eq!(
+            store.claim_at("k", "other", lock_ttl, start + Duration::from_secs(60)),
+            IdempotencyClaim::Existing {
+                fingerprint: "fp".to_string(),
+                response: Some(response()),
+            }
+        );
+
+        let expired = start + Duration::from_secs(3601);
+        assert_eq!(store.purge_at(expired), 1);
+        assert_eq!(
+            store.claim_at("k", "other", lock_ttl, expired),
+            IdempotencyClaim::Acquired
+        );
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_claims_can_be_reclaimed() {
+        let store = InMemoryIdempotencyStore::new();
+        let start = Instant::now();
+        store.claim_at("k", "fp", Duration::from_secs(30), start);
+
+        // 處理中的記錄超過鎖定時間後視為放棄
+        assert_eq!(
+            store.claim_at(
+                "k",
+                "fp",
+                Duration::from_secs(30),
+                start + Duration::from_secs(31)
+            ),
+            IdempotencyClaim::Acquired
+        );
+
+        store.release("k").await.unwrap();
+        assert_eq!(
+            store
+                .claim("k", "fp", Duration::from_secs(30))
+                .await
+                .unwrap(),
+            IdempotencyClaim::Acquired
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! 記憶體內的基礎設施適配器，供測試與單節點執行使用。
+
+pub mod api_key_repo;
+pub mod cache;
+pub mod idempotency;
+pub mod outbox;
+pub mod rate_limit;
+pub mod unit_of_work;
+pub mod user_repo;
+
+pub use api_key_repo::InMemoryApiKeyRepository;
+pub use cache::InMemoryCache;
+pub use idempotency::InMemoryIdempotencyStore;
+pub use outbox::InMemoryOutbox;
+pub use rate_limit::InMemoryRateLimitStore;
+pub use unit_of_work::{InMemoryUnitOfWork, InMemoryUnitOfWorkFactory};
+pub use user_repo::InMemoryUserRepository;
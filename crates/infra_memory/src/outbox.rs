@@ -0,0 +1,193 @@
+use async_trait::async_trait;
+use contracts::{DomainError, DomainEvent, InfraError, OutboxMessage, OutboxStore};
+use domain::OutboxRepository;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+struct StoredMessage {
+    message: OutboxMessage,
+    published: bool,
+    last_error: Option<String>,
+    /// 發佈失敗後，在此之前不會被重新認領
+    retry_at: Option<SystemTime>,
+    dead_lettered: bool,
+}
+
+impl StoredMessage {
+    fn is_pending(&self) -> bool {
+        !self.published && !self.dead_lettered
+    }
+
+    fn same_aggregate(&self, other: &StoredMessage) -> bool {
+        self.message.aggregate_type == other.message.aggregate_type
+            && self.message.aggregate_id == other.message.aggregate_id
+    }
+}
+
+/// 記憶體內的發件箱，複製後共用同一份資料
+#[derive(Clone, Default)]
+pub struct InMemoryOutbox {
+    messages: Arc<Mutex<Vec<StoredMessage>>>,
+}
+
+impl InMemoryOutbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 所有已寫入的訊息（含已發佈）
+    pub fn messages(&self) -> Vec<OutboxMessage> {
+        self.lock().iter().map(|m| m.message.clone()).collect()
+    }
+
+    /// 尚未發佈的訊息數量（不含死信）
+    pub fn pending_count(&self) -> usize {
+        self.lock().iter().filter(|m| m.is_pending()).count()
+    }
+
+    /// 指定訊息是否已列為死信
+    pub fn is_dead_lettered(&self, id: Uuid) -> bool {
+        self.lock()
+            .iter()
+            .any(|m| m.message.id == id && m.dead_lettered)
+    }
+
+    /// 指定訊息最近一次的發佈錯誤
+    pub fn last_error(&self, id: Uuid) -> Option<String> {
+        self.lock()
+            .iter()
+            .find(|m| m.message.id == id)
+            .and_then(|m| m.last_error.clone())
+    }
+
+    pub(crate) fn push_events(&self, events: &[DomainEvent]) {
+        let mut messages = self.lock();
+        messages.extend(events.iter().map(|event| StoredMessage {
+            message: OutboxMessage::from_event(event),
+            published: false,
+            last_error: None,
+            retry_at: None,
+            dead_lettered: false,
+        }));
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<StoredMessage>> {
+        self.messages.lock().expect("outbox poisoned")
+    }
+
+    fn update(&self, id: Uuid, f: impl FnOnce(&mut StoredMessage)) -> Result<(), InfraError> {
+        let mut messages = self.lock();
+        let stored = messages
+            .iter_mut()
+            .find(|m| m.message.id == id)
+            .ok_or_else(|| InfraError::Database(format!("Outbox message {id} not found")))?;
+        f(stored);
+        Ok(())
+    }
+}
+
+impl OutboxRepository for InMemoryOutbox {
+    fn append(
+        &self,
+        events: &[DomainEvent],
+    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
+        self.push_events(events);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+#[async_trait]
+impl OutboxStore for InMemoryOutbox {
+    async fn claim_pending(&self, limit: u32) -> Result<Vec<OutboxMessage>, InfraError> {
+        let now = SystemTime::now();
+        let messages = self.lock();
+        // 同一聚合有較早的訊息在等待重試時，較晚的訊息不認領
+        Ok(messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.is_pending() && m.retry_at.is_none_or(|at| at <= now))
+            .filter(|(index, m)| {
+                !messages[..*index].iter().any(|earlier| {
+                    earlier.is_pending()
+                        && earlier.message.attempts > 0
+                        && earlier.same_aggregate(m)
+                })
+            })
+            .take(limit as usize)
+            .map(|(_, m)| m.message.clone())
+            .collect())
+    }
+
+    async fn mark_published(&self, id: Uuid) -> Result<(), InfraError> {
+        self.update(id, |m| m.published = true)
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: SystemTime,
+    ) -> Result<(), InfraError> {
+        self.update(id, |m| {
+            m.message.attempts += 1;
+            m.last_error = Some(error.to_string());
+            m.retry_at = Some(retry_at);
+        })
+    }
+
+    async fn mark_dead_lettered(&self, id: Uuid, error: &str) -> Result<(), InfraError> {
+        self.update(id, |m| {
+            m.message.attempts += 1;
+            m.last_error = Some(error.to_string());
+            m.dead_lettered = true;
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::{UserId, UserName};
+
+    fn created(id: u8) -> DomainEvent {
+        DomainEvent::UserCreated {
+            user_id: UserId::from_bytes([id; 16]),
+            name: UserName::parse("Alice").unwrap(),
+            email: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_claim_and_publish() {
+        let outbox = InMemoryOutbox::new();
+        outbox.append(&[created(1), created(2)]).await.unwrap();
+
+        let claimed = outbox.claim_pending(1).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+
+        outbox.mark_published(claimed[0].id).await.unwrap();
+        assert_eq!(outbox.pending_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mark_failed_keeps_message_pending() {
+        let outbox = InMemoryOutbox::new();
+        outbox.append(&[created(1)]).await.unwrap();
+        let id = outbox.claim_pending(10).await.unwrap()[0].id;
+
+        outbox
+            .mark_failed(id, "broker down", SystemTime::now())
+            .await
+            .unwrap();
+
+        let retried = outbox.claim_pending(10).await.unwrap();
+        assert_eq!(retried[0].attempts, 1);
+        assert_eq!(outbox.last_error(id).as_deref(), Some("broker down"));
+    }
+}
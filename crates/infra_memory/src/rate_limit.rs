@@ -0,0 +1,93 @@
+use contracts::{RateLimitDecision, RateLimitFuture, RateLimitQuota, RateLimitStore};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// 超過此數量的計數鍵時，清掉已過期的時間窗
+const SWEEP_THRESHOLD: usize = 10_000;
+
+struct Window {
+    started_at: Instant,
+    length: Duration,
+    count: u64,
+}
+
+impl Window {
+    fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.started_at) >= self.length
+    }
+}
+
+/// 記憶體內的固定時間窗限流計數，僅在單一實例內有效
+#[derive(Clone, Default)]
+pub struct InMemoryRateLimitStore {
+    windows: Arc<Mutex<HashMap<String, Window>>>,
+}
+
+impl InMemoryRateLimitStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn hit_at(&self, key: &str, quota: RateLimitQuota, now: Instant) -> RateLimitDecision {
+        let mut windows = self.windows.lock().expect("rate limit store poisoned");
+        if windows.len() >= SWEEP_THRESHOLD {
+            windows.retain(|_, window| !window.is_expired(now));
+        }
+
+        let window = windows.entry(key.to_string()).or_insert_with(|| Window {
+            started_at: now,
+            length: quota.window,
+            count: 0,
+        });
+        if window.is_expired(now) {
+            *window = Window {
+                started_at: now,
+                length: quota.window,
+                count: 0,
+            };
+        }
+        window.count += 1;
+
+        let reset_after = window.length - now.duration_since(window.started_at);
+        RateLimitDecision::from_count(quota, window.count, reset_after)
+    }
+}
+
+impl RateLimitStore for InMemoryRateLimitStore {
+    fn hit<'a>(&'a self, key: &'a str, quota: RateLimitQuota) -> RateLimitFuture<'a> {
+        let decision = self.hit_at(key, quota, Instant::now());
+        Box::pin(async move { Ok(decision) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_window_counts_per_key_and_resets() {
+        let store = InMemoryRateLimitStore::new();
+        let quota = RateLimitQuota::new(2, Duration::from_secs(10));
+        let start = Instant::now();
+
+        let first = store.hit_at("a", quota, start);
+        assert!(first.allowed);
+        assert_eq!(first.remaining, 1);
+        assert!(store.hit_at("a", quota, start).allowed);
+
+        let rejected = store.hit_at("a", quota, start + Duration::from_secs(4));
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.remaining, 0);
+        assert_eq!(rejected.reset_after, Duration::from_secs(6));
+
+        assert!(store.hit_at("b", quota, start).allowed);
+        assert!(
+            store
+                .hit_at("a", quota, start + Duration::from_secs(10))
+                .allowed
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use crate::outbox::InMemoryOutbox;
+use crate::user_repo::{check_version, not_found, persisted, InMemoryUserRepository};
+use contracts::{
+    AuditTimestamps, DomainError, DomainEvent, TenantId, User, UserId, UserListQuery, UserScanQuery,
+};
+use domain::{OutboxRepository, UnitOfWork, UnitOfWorkFactory, UnitOfWorkFuture, UserRepository};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+/// 記憶體內的工作單元工廠 - 變更在提交前只存在於工作單元內
+///
+/// 工作單元的租戶範圍取決於 `users` 儲存庫所屬的租戶。
+#[derive(Clone, Default)]
+pub struct InMemoryUnitOfWorkFactory {
+    users: InMemoryUserRepository,
+    outbox: InMemoryOutbox,
+}
+
+impl InMemoryUnitOfWorkFactory {
+    /// 以既有的用戶儲存庫與發件箱作為提交目標
+    pub fn new(users: InMemoryUserRepository, outbox: InMemoryOutbox) -> Self {
+        Self { users, outbox }
+    }
+
+    /// 已提交資料所在的用戶儲存庫
+    pub fn users(&self) -> &InMemoryUserRepository {
+        &self.users
+    }
+
+    /// 已提交事件所在的發件箱
+    pub fn outbox(&self) -> &InMemoryOutbox {
+        &self.outbox
+    }
+}
+
+impl UnitOfWorkFactory for InMemoryUnitOfWorkFactory {
+    fn begin(&self) -> UnitOfWorkFuture<'_> {
+        let uow = InMemoryUnitOfWork {
+            users: StagedUserRepository {
+                committed: self.users.clone(),
+                staged: Arc::default(),
+            },
+            outbox: StagedOutbox {
+                committed: self.outbox.clone(),
+                staged: Mutex::default(),
+            },
+        };
+        Box::pin(async move { Ok(Box::new(uow) as Box<dyn UnitOfWork>) })
+    }
+
+    fn for_tenant(&self, tenant: &TenantId) -> Arc<dyn UnitOfWorkFactory> {
+        Arc::new(Self::new(self.users.scoped(tenant), self.outbox.clone()))
+    }
+}
+
+/// 記憶體內的工作單元
+pub struct InMemoryUnitOfWork {
+    users: StagedUserRepository,
+    outbox: StagedOutbox,
+}
+
+impl UnitOfWork for InMemoryUnitOfWork {
+    fn users(&self) -> &dyn UserRepository {
+        &self.users
+    }
+
+    fn outbox(&self) -> &dyn OutboxRepository {
+        &self.outbox
+    }
+
+    fn commit(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>> {
+        let staged = std::mem::take(&mut *self.users.staged.lock().expect("staged poisoned"));
+        // 提交時以交易開始前看到的版本再與已提交資料比對一次
+        let writes = staged
+            .into_iter()
+            .map(|((tenant, _), s)| (tenant, s.expected_version, s.current))
+            .collect();
+        let result = self.users.committed.apply_all(writes);
+        if result.is_ok() {
+            let events = std::mem::take(&mut *self.outbox.staged.lock().expect("staged poisoned"));
+            self.outbox.committed.push_events(&events);
+        }
+        Box::pin(async move { result })
+    }
+
+    fn rollback(self: Box<Self>) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// 暫存的寫入：交易內最新狀態與首次寫入時預期的已提交版本
+struct StagedUser {
+    expected_version: u64,
+    current: User,
+}
+
+/// 先讀取暫存變更，再回退到已提交資料的用戶儲存庫
+struct StagedUserRepository {
+    committed: InMemoryUserRepository,
+    /// 以（租戶, 用戶 ID）為鍵，與已提交資料相同
+    staged: Arc<Mutex<HashMap<(TenantId, UserId), StagedUser>>>,
+}
+
+impl StagedUserRepository {
+    fn get(&self, id: &UserId) -> Option<User> {
+        let staged = self
+            .staged
+            .lock()
+            .expect("staged poisoned")
+            .get(&(self.committed.tenant().clone(), id.clone()))
+            .map(|s| s.current.clone());
+        staged.or_else(|| self.committed.get(id))
+    }
+}
+
+impl UserRepository for StagedUserRepository {
+    fn for_tenant(&self, tenant: &TenantId) -> Arc<dyn UserRepository> {
+        // 共用同一份暫存變更，提交時一併寫入
+        Arc::new(StagedUserRepository {
+            committed: self.committed.scoped(tenant),
+            staged: self.staged.clone(),
+        })
+    }
+
+    fn find(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+        let result = self
+            .get(id)
+            .filter(|u| !u.is_deleted())
+            .ok_or_else(|| not_found(id));
+        Box::pin(async move { result })
+    }
+
+    fn find_including_deleted(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+        let result = self.get(id).ok_or_else(|| not_found(id));
+        Box::pin(async move { result })
+    }
+
+    fn list(
+        &self,
+        query: UserListQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        // 交易內的列表只反映已提交資料，與 READ COMMITTED 下的其他連線一致
+        self.committed.list(query)
+    }
+
+    fn scan(
+        &self,
+        query: UserScanQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        self.committed.scan(query)
+    }
+
+    fn save(
+        &self,
+        user: &User,
+    ) -> Pin<Box<dyn Future<Output = Result<AuditTimestamps, DomainError>> + Send + '_>> {
+        let stored = persisted(user, SystemTime::now());
+        let audit = stored
+            .audit
+            .expect("persisted users carry audit timestamps");
+        let mut staged = self.staged.lock().expect("staged poisoned");
+        let key = (self.committed.tenant().clone(), user.id.clone());
+        let result = match staged.get_mut(&key) {
+            Some(existing) => {
+                check_version(Some(&existing.current), &user.id, user.version).map(|()| {
+                    existing.current = stored;
+                })
+            }
+            None => check_version(
+                self.committed.get(&user.id).as_ref(),
+                &user.id,
+                user.version,
+            )
+            .map(|()| {
+                staged.insert(
+                    key,
+                    StagedUser {
+                        expected_version: user.version,
+                        current: stored,
+                    },
+                );
+            }),
+        };
+        let result = result.map(|()| audit);
+        Box::pin(async move { result })
+    }
+
+    fn purge_deleted_before(
+        &self,
+        cutoff: SystemTime,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, DomainError>> + Send + '_>> {
+        // 清除屬於維運作業，不參與交易暫存
+        self.committed.purge_deleted_before(cutoff)
+    }
+
+    fn shutdown(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async {})
+    }
+}
+
+/// 提交前暫存事件的發件箱
+struct StagedOutbox {
+    committed: InMemoryOutbox,
+    staged: Mutex<Vec<DomainEvent>>,
+}
+
+impl OutboxRepository for StagedOutbox {
+    fn append(
+        &self,
+        events: &[DomainEvent],
+    ) -> Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send + '_>> {
+        self.staged
+            .lock()
+            .expect("staged poisoned")
+            .extend_from_slice(events);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::UserName;
+
+    fn user(id: u8) -> User {
+        User::new(
+            UserId::from_bytes([id; 16]),
+            UserName::parse("Alice").unwrap(),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_commit_publishes_staged_changes() {
+        let factory = InMemoryUnitOfWorkFactory::default();
+        let uow = factory.begin().await.unwrap();
+        let user = user(1);
+
+        uow.users().save(&user).await.unwrap();
+        uow.outbox().append(user.pending_events()).await.unwrap();
+        assert!(uow.users().find(&user.id).await.is_ok());
+        assert!(factory.users().is_empty());
+        assert_eq!(factory.outbox().pending_count(), 0);
+
+        uow.commit().await.unwrap();
+        assert!(factory.users().find(&user.id).await.is_ok());
+        assert_eq!(factory.outbox().pending_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_discards_staged_changes() {
+        let factory = InMemoryUnitOfWorkFactory::default();
+        let uow = factory.begin().await.unwrap();
+
+        let user = user(1);
+        uow.users().save(&user).await.unwrap();
+        uow.outbox().append(user.pending_events()).await.unwrap();
+        uow.rollback().await.unwrap();
+
+        assert!(factory.users().is_empty());
+        assert_eq!(factory.outbox().pending_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_commit_detects_concurrent_modification() {
+        let factory = InMemoryUnitOfWorkFactory::default();
+        factory.users().save(&user(1)).await.unwrap();
+        let loaded = factory.users().get(&UserId::from_bytes([1; 16])).unwrap();
+
+        let uow = factory.begin().await.unwrap();
+        let mut renamed = loaded.clone();
+        renamed
+            .update_name(UserName::parse("Bob").unwrap())
+            .unwrap();
+        uow.users().save(&renamed).await.unwrap();
+
+        // 另一個寫入者先提交
+        let mut other = loaded;
+        other
+            .update_name(UserName::parse("Carol").unwrap())
+            .unwrap();
+        factory.users().save(&other).await.unwrap();
+
+        assert!(matches!(
+            uow.commit().await,
+            Err(DomainError::Conflict { .. })
+        ));
+        assert_eq!(
+            factory.users().get(&renamed.id).unwrap().name.as_str(),
+            "Carol"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dropped_unit_of_work_discards_changes() {
+        let factory = InMemoryUnitOfWorkFactory::default();
+        {
+            let uow = factory.begin().await.unwrap();
+            uow.users().save(&user(1)).await.unwrap();
+        }
+
+        assert!(factory.users().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tenant_factory_commits_into_its_tenant() {
+        let factory = InMemoryUnitOfWorkFactory::default();
+        let acme = TenantId::parse("acme").unwrap();
+        let uow = factory.for_tenant(&acme).begin().await.unwrap();
+        let user = user(1);
+
+        uow.users().save(&user).await.unwrap();
+        assert!(uow
+            .users()
+            .for_tenant(&TenantId::default())
+            .find(&user.id)
+            .await
+            .is_err());
+        uow.commit().await.unwrap();
+
+        assert!(factory.users().is_empty());
+        assert!(factory.users().scoped(&acme).get(&user.id).is_some());
+    }
+}
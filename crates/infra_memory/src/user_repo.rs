@@ -0,0 +1,400 @@
+use contracts::{
+    AuditTimestamps, DomainError, TenantId, User, UserId, UserListQuery, UserScanQuery,
+};
+use domain::UserRepository;
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, RwLock},
+    time::SystemTime,
+};
+
+/// 以（租戶, 用戶 ID）為鍵，與 Postgres 的主鍵相同；不同租戶可以使用相同的 ID
+type UserKey = (TenantId, UserId);
+
+/// 記憶體內的用戶儲存庫，複製後共用同一份資料
+///
+/// 與 Postgres 實作相同，儲存時會檢查版本號並在不一致時回傳衝突；
+/// 所有租戶的資料存放在一起，每個儲存庫只看得到自己租戶的用戶。
+#[derive(Clone, Default)]
+pub struct InMemoryUserRepository {
+    users: Arc<RwLock<HashMap<UserKey, User>>>,
+    tenant: TenantId,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 共用同一份資料、只能存取指定租戶的儲存庫
+    pub fn scoped(&self, tenant: &TenantId) -> Self {
+        Self {
+            users: self.users.clone(),
+            tenant: tenant.clone(),
+        }
+    }
+
+    pub fn tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+
+    /// 目前租戶儲存的用戶數量
+    pub fn len(&self) -> usize {
+        self.users
+            .read()
+            .expect("user store poisoned")
+            .keys()
+            .filter(|(tenant, _)| tenant == &self.tenant)
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn get(&self, id: &UserId) -> Option<User> {
+        self.users
+            .read()
+            .expect("user store poisoned")
+            .get(&(self.tenant.clone(), id.clone()))
+            .cloned()
+    }
+
+    /// 未被軟刪除的用戶
+    pub(crate) fn get_active(&self, id: &UserId) -> Option<User> {
+        self.get(id).filter(|u| !u.is_deleted())
+    }
+
+    /// 以全有或全無的方式套用一組寫入，任一版本不符即全部放棄
+    ///
+    /// 每筆寫入為（所屬租戶, 寫入者預期的已提交版本, 寫入後的狀態）。
+    pub(crate) fn apply_all(&self, writes: Vec<(TenantId, u64, User)>) -> Result<(), DomainError> {
+        let mut store = self.users.write().expect("user store poisoned");
+        for (tenant, expected, user) in &writes {
+            let current = store.get(&(tenant.clone(), user.id.clone()));
+            check_version(current, &user.id, *expected)?;
+        }
+        for (tenant, _, user) in writes {
+            store.insert((tenant, user.id.clone()), user);
+        }
+        Ok(())
+    }
+
+    /// 目前租戶的用戶（可依條件過濾）
+    fn tenant_users(&self, filter: impl Fn(&User) -> bool) -> Vec<User> {
+        self.users
+            .read()
+            .expect("user store poisoned")
+            .iter()
+            .filter(|((tenant, _), user)| tenant == &self.tenant && filter(user))
+            .map(|(_, user)| user.clone())
+            .collect()
+    }
+
+    fn list_users(&self, query: UserListQuery) -> Vec<User> {
+        let mut users = self.tenant_users(|u| query.include_deleted || !u.is_deleted());
+        users.sort_by(|a, b| {
+            let created = |u: &User| u.audit.map(|a| a.created_at);
+            created(a).cmp(&created(b)).then_with(|| a.id.cmp(&b.id))
+        });
+        users
+            .into_iter()
+            .skip(usize::try_from(query.offset).unwrap_or(usize::MAX))
+            .take(query.limit as usize)
+            .collect()
+    }
+
+    fn scan_users(&self, query: UserScanQuery) -> Vec<User> {
+        let mut users = self.tenant_users(|u| {
+            (query.include_deleted || !u.is_deleted())
+                && query.after.as_ref().is_none_or(|after| &u.id > after)
+        });
+        users.sort_by(|a, b| a.id.cmp(&b.id));
+        users.truncate(query.limit as usize);
+        users
+    }
+
+    /// 清除所有租戶中過期的軟刪除用戶
+    fn purge(&self, cutoff: SystemTime) -> u64 {
+        let mut store = self.users.write().expect("user store poisoned");
+        let before = store.len();
+        store.retain(|_, user| user.deleted_at.is_none_or(|at| at >= cutoff));
+        (before - store.len()) as u64
+    }
+}
+
+pub(crate) fn not_found(id: &UserId) -> DomainError {
+    DomainError::NotFound {
+        message: format!("User {id} not found"),
+    }
+}
+
+/// 比對目前儲存的版本與寫入者預期的版本
+pub(crate) fn check_version(
+    current: Option<&User>,
+    id: &UserId,
+    expected: u64,
+) -> Result<(), DomainError> {
+    let current_version = current.map(|u| u.version).unwrap_or(0);
+    if current_version != expected {
+        return Err(version_conflict(id, expected));
+    }
+    Ok(())
+}
+
+pub(crate) fn version_conflict(id: &UserId, expected: u64) -> DomainError {
+    DomainError::Conflict {
+        message: format!("User {id} was modified concurrently (expected version {expected})"),
+    }
+}
+
+/// 寫入後的狀態：版本號加一、更新時間戳記且不保留事件
+pub(crate) fn persisted(user: &User, now: SystemTime) -> User {
+    let audit = AuditTimestamps {
+        created_at: user.audit.map_or(now, |a| a.created_at),
+        updated_at: now,
+    };
+    let mut stored = user.clone();
+    stored.take_events();
+    stored.mark_persisted(audit);
+    stored
+}
+
+impl UserRepository for InMemoryUserRepository {
+    fn for_tenant(&self, tenant: &TenantId) -> Arc<dyn UserRepository> {
+        Arc::new(self.scoped(tenant))
+    }
+
+    fn find(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+        let result = self.get_active(id).ok_or_else(|| not_found(id));
+        Box::pin(async move { result })
+    }
+
+    fn find_including_deleted(
+        &self,
+        id: &UserId,
+    ) -> Pin<Box<dyn Future<Output = Result<User, DomainError>> + Send + '_>> {
+        let result = self.get(id).ok_or_else(|| not_found(id));
+        Box::pin(async move { result })
+    }
+
+    fn find_many(
+        &self,
+        ids: &[UserId],
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        let users = ids.iter().filter_map(|id| self.get_active(id)).collect();
+        Box::pin(async move { Ok(users) })
+    }
+
+    fn list(
+        &self,
+        query: UserListQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        let users = self.list_users(query);
+        Box::pin(async move { Ok(users) })
+    }
+
+    fn scan(
+        &self,
+        query: UserScanQuery,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<User>, DomainError>> + Send + '_>> {
+        let users = self.scan_users(query);
+        Box::pin(async move { Ok(users) })
+    }
+
+    fn save(
+        &self,
+        user: &User,
+    ) -> Pin<Box<dyn Future<Output = Result<AuditTimestamps, DomainError>> + Send + '_>> {
+        let stored = persisted(user, SystemTime::now());
+        let audit = stored
+            .audit
+            .expect("persisted users carry audit timestamps");
+        let result = self
+            .apply_all(vec![(self.tenant.clone(), user.version, stored)])
+            .map(|()| audit);
+        Box::pin(async move { result })
+    }
+
+    fn purge_deleted_before(
+        &self,
+        cutoff: SystemTime,
+    ) -> Pin<Box<dyn Future<Output = Result<u64, DomainError>> + Send + '_>> {
+        let purged = self.purge(cutoff);
+        Box::pin(async move { Ok(purged) })
+    }
+
+    fn shutdown(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::UserName;
+
+    fn new_user(id: u8) -> User {
+        User::new(
+            UserId::from_bytes([id; 16]),
+            UserName::parse("Alice").unwrap(),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_save_and_find() {
+        let repo = InMemoryUserRepository::new();
+        let user = new_user(1);
+
+        repo.save(&user).await.unwrap();
+        let found = repo.find(&user.id).await.unwrap();
+
+        assert_eq!(found.name.as_str(), "Alice");
+        assert_eq!(found.version, 1);
+        assert_eq!(repo.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_find_missing_user() {
+        let repo = InMemoryUserRepository::new();
+        let result = repo.find(&UserId::from_bytes([0; 16])).await;
+
+        assert!(matches!(result, Err(DomainError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_stale_save_conflicts() {
+        let repo = InMemoryUserRepository::new();
+        repo.save(&new_user(1)).await.unwrap();
+
+        let mut first = repo.find(&UserId::from_bytes([1; 16])).await.unwrap();
+        let mut second = first.clone();
+
+        first
+            .update_name(UserName::parse("First").unwrap())
+            .unwrap();
+        repo.save(&first).await.unwrap();
+
+        second
+            .update_name(UserName::parse("Second").unwrap())
+            .unwrap();
+        let result = repo.save(&second).await;
+
+        assert!(matches!(result, Err(DomainError::Conflict { .. })));
+        assert_eq!(repo.find(&first.id).await.unwrap().name.as_str(), "First");
+    }
+
+    #[tokio::test]
+    async fn test_inserting_existing_user_conflicts() {
+        let repo = InMemoryUserRepository::new();
+        repo.save(&new_user(1)).await.unwrap();
+
+        let result = repo.save(&new_user(1)).await;
+
+        assert!(matches!(result, Err(DomainError::Conflict { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_save_stamps_audit_timestamps() {
+        let repo = InMemoryUserRepository::new();
+        let user = new_user(1);
+
+        let created = repo.save(&user).await.unwrap();
+        let mut loaded = repo.find(&user.id).await.unwrap();
+        assert_eq!(loaded.audit, Some(created));
+
+        loaded.update_name(UserName::parse("Bob").unwrap()).unwrap();
+        let updated = repo.save(&loaded).await.unwrap();
+
+        assert_eq!(updated.created_at, created.created_at);
+        assert!(updated.updated_at >= created.updated_at);
+    }
+
+    #[tokio::test]
+    async fn test_soft_deleted_users_are_hidden() {
+        let repo = InMemoryUserRepository::new();
+        repo.save(&new_user(1)).await.unwrap();
+        repo.save(&new_user(2)).await.unwrap();
+
+        let id = UserId::from_bytes([1; 16]);
+        let mut user = repo.find(&id).await.unwrap();
+        user.delete(SystemTime::now()).unwrap();
+        repo.save(&user).await.unwrap();
+
+        assert!(matches!(
+            repo.find(&id).await,
+            Err(DomainError::NotFound { .. })
+        ));
+        assert!(repo.find_including_deleted(&id).await.unwrap().is_deleted());
+        assert_eq!(repo.list(UserListQuery::default()).await.unwrap().len(), 1);
+        let all = UserListQuery {
+            include_deleted: true,
+            ..UserListQuery::default()
+        };
+        assert_eq!(repo.list(all).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_purge_removes_only_expired_deletions() {
+        let repo = InMemoryUserRepository::new();
+        let now = SystemTime::now();
+        let hour = std::time::Duration::from_secs(3600);
+        for (id, deleted_at) in [(1, Some(now - hour * 2)), (2, Some(now)), (3, None)] {
+            let user = User::reconstitute(
+                UserId::from_bytes([id; 16]),
+                UserName::parse("A").unwrap(),
+                0,
+            )
+            .with_deleted_at(deleted_at);
+            repo.save(&user).await.unwrap();
+        }
+
+        let purged = repo.purge_deleted_before(now - hour).await.unwrap();
+
+        assert_eq!(purged, 1);
+        assert_eq!(repo.len(), 2);
+        assert!(repo.get(&UserId::from_bytes([1; 16])).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tenants_only_see_their_own_users() {
+        let repo = InMemoryUserRepository::new();
+        let acme = repo.scoped(&TenantId::parse("acme").unwrap());
+        let user = new_user(1);
+        acme.save(&user).await.unwrap();
+
+        assert!(matches!(
+            repo.find(&user.id).await,
+            Err(DomainError::NotFound { .. })
+        ));
+        assert!(repo
+            .list(UserListQuery::default())
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(acme.find(&user.id).await.unwrap().name.as_str(), "Alice");
+
+        // 另一個租戶可以使用相同 ID，但不能覆寫 acme 的用戶
+        let mut forged = acme.find(&user.id).await.unwrap();
+        forged
+            .update_name(UserName::parse("Mallory").unwrap())
+            .unwrap();
+        assert!(matches!(
+            repo.save(&forged).await,
+            Err(DomainError::Conflict { .. })
+        ));
+        assert_eq!(acme.find(&user.id).await.unwrap().name.as_str(), "Alice");
+
+        let mut other = new_user(1);
+        other.update_name(UserName::parse("Bob").unwrap()).unwrap();
+        repo.save(&other).await.unwrap();
+        assert_eq!(repo.find(&user.id).await.unwrap().name.as_str(), "Bob");
+        assert_eq!(acme.find(&user.id).await.unwrap().name.as_str(), "Alice");
+    }
+}
@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use contracts::{EventPublisher, InfraError, OutboxMessage};
+
+/// 以結構化日誌輸出領域事件的發佈器
+///
+/// 作為第一個 `EventPublisher` 實作，便於在接上訊息中介軟體前觀察事件流。
+#[derive(Debug, Clone, Default)]
+pub struct LogEventPublisher;
+
+impl LogEventPublisher {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl EventPublisher for LogEventPublisher {
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), InfraError> {
+        tracing::info!(
+            target: "domain_events",
+            event_id = %message.id,
+            event_type = %message.event_type,
+            aggregate_type = %message.aggregate_type,
+            aggregate_id = %message.aggregate_id,
+            payload = %message.payload,
+            "Domain event published"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::{DomainEvent, UserId, UserName};
+
+    #[tokio::test]
+    async fn test_publish_always_succeeds() {
+        let message = OutboxMessage::from_event(&DomainEvent::UserCreated {
+            user_id: UserId::from_bytes([1; 16]),
+            name: UserName::parse("Alice").unwrap(),
+            email: None,
+        });
+
+        assert!(LogEventPublisher::new().publish(&message).await.is_ok());
+    }
+}
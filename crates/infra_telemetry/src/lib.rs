@@ -1,4 +1,5 @@
 pub mod config;
 pub mod error;
+pub mod event_publisher;
 pub mod metrics;
 pub mod telemetry; // 👈 新增
@@ -9,12 +9,24 @@ use opentelemetry::{
 const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
 const HTTP_REQUESTS_DURATION: &str = "http_requests_duration_seconds";
 const HTTP_REQUESTS_IN_FLIGHT: &str = "http_requests_in_flight";
+const HTTP_RATE_LIMITED_TOTAL: &str = "http_rate_limited_total";
+const HTTP_REQUESTS_REJECTED_TOTAL: &str = "http_requests_rejected_total";
+const CACHE_LOOKUPS_TOTAL: &str = "cache_lookups_total";
+const USE_CASE_DURATION: &str = "use_case_duration_seconds";
+const GRPC_REQUESTS_TOTAL: &str = "grpc_requests_total";
+const GRPC_REQUESTS_DURATION: &str = "grpc_requests_duration_seconds";
 
 #[derive(Clone)]
 pub struct Metrics {
     http_requests_total: Counter<u64>,
     http_requests_duration_seconds: Histogram<f64>,
     http_requests_in_flight: opentelemetry::metrics::UpDownCounter<i64>,
+    http_rate_limited_total: Counter<u64>,
+    http_requests_rejected_total: Counter<u64>,
+    cache_lookups_total: Counter<u64>,
+    use_case_duration_seconds: Histogram<f64>,
+    grpc_requests_total: Counter<u64>,
+    grpc_requests_duration_seconds: Histogram<f64>,
 }
 
 impl Metrics {
@@ -35,6 +47,32 @@ impl Metrics {
                 .i64_up_down_counter(HTTP_REQUESTS_IN_FLIGHT)
                 .with_description("Number of in-flight HTTP requests")
                 .build(),
+            http_rate_limited_total: meter
+                .u64_counter(HTTP_RATE_LIMITED_TOTAL)
+                .with_description("HTTP requests rejected by rate limiting")
+                .build(),
+            http_requests_rejected_total: meter
+                .u64_counter(HTTP_REQUESTS_REJECTED_TOTAL)
+                .with_description(
+                    "HTTP requests rejected by timeouts, load shedding or body size limits",
+                )
+                .build(),
+            cache_lookups_total: meter
+                .u64_counter(CACHE_LOOKUPS_TOTAL)
+                .with_description("Cache lookups by cache name and result (hit or miss)")
+                .build(),
+            use_case_duration_seconds: meter
+                .f64_histogram(USE_CASE_DURATION)
+                .with_description("Use case execution time in seconds, including retries")
+                .build(),
+            grpc_requests_total: meter
+                .u64_counter(GRPC_REQUESTS_TOTAL)
+                .with_description("Total gRPC calls by method and status code")
+                .build(),
+            grpc_requests_duration_seconds: meter
+                .f64_histogram(GRPC_REQUESTS_DURATION)
+                .with_description("gRPC call latency in seconds")
+                .build(),
         }
     }
 
@@ -67,6 +105,44 @@ impl Metrics {
         self.http_requests_duration_seconds
             .record(latency, &status_labels);
     }
+
+    pub fn on_rate_limited(&self, method: &str, path: &str, key: &str) {
+        let mut labels = Self::create_labels(method, path);
+        labels.push(KeyValue::new("key", key.to_owned()));
+        self.http_rate_limited_total.add(1, &labels);
+    }
+
+    pub fn on_request_rejected(&self, method: &str, path: &str, reason: &str) {
+        let mut labels = Self::create_labels(method, path);
+        labels.push(KeyValue::new("reason", reason.to_owned()));
+        self.http_requests_rejected_total.add(1, &labels);
+    }
+
+    pub fn on_cache_lookup(&self, cache: &str, hit: bool) {
+        let labels = [
+            KeyValue::new("cache", cache.to_owned()),
+            KeyValue::new("result", if hit { "hit" } else { "miss" }),
+        ];
+        self.cache_lookups_total.add(1, &labels);
+    }
+
+    pub fn on_use_case_end(&self, use_case: &str, outcome: &str, duration: f64) {
+        let labels = [
+            KeyValue::new("use_case", use_case.to_owned()),
+            KeyValue::new("outcome", outcome.to_owned()),
+        ];
+        self.use_case_duration_seconds.record(duration, &labels);
+    }
+
+    pub fn on_grpc_call(&self, method: &str, code: &str, duration: f64) {
+        let labels = [
+            KeyValue::new("method", method.to_owned()),
+            KeyValue::new("code", code.to_owned()),
+        ];
+        self.grpc_requests_total.add(1, &labels);
+        self.grpc_requests_duration_seconds
+            .record(duration, &labels[..1]);
+    }
 }
 
 impl Default for Metrics {
@@ -90,6 +166,26 @@ impl contracts::ObservabilityPort for Metrics {
     async fn on_request_end(&self, method: &str, path: &str, status: u16, latency: f64) {
         Metrics::on_request_end(self, method, path, status, latency);
     }
+
+    async fn on_rate_limited(&self, method: &str, path: &str, key: &str) {
+        Metrics::on_rate_limited(self, method, path, key);
+    }
+
+    async fn on_request_rejected(&self, method: &str, path: &str, reason: &str) {
+        Metrics::on_request_rejected(self, method, path, reason);
+    }
+
+    async fn on_cache_lookup(&self, cache: &str, hit: bool) {
+        Metrics::on_cache_lookup(self, cache, hit);
+    }
+
+    async fn on_use_case_end(&self, use_case: &str, outcome: &str, duration: f64) {
+        Metrics::on_use_case_end(self, use_case, outcome, duration);
+    }
+
+    async fn on_grpc_call(&self, method: &str, code: &str, duration: f64) {
+        Metrics::on_grpc_call(self, method, code, duration);
+    }
 }
 
 #[cfg(test)]
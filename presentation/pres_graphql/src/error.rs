@@ -0,0 +1,65 @@
+use async_graphql::ErrorExtensions;
+use contracts::{AppError, DomainError};
+
+/// 應用錯誤轉為 GraphQL 錯誤，`extensions.code` 與 HTTP 錯誤回應的 `code` 相同
+#[derive(Debug)]
+pub struct GraphqlError(pub AppError);
+
+impl From<AppError> for GraphqlError {
+    fn from(e: AppError) -> Self {
+        Self(e)
+    }
+}
+
+impl From<DomainError> for GraphqlError {
+    fn from(e: DomainError) -> Self {
+        Self(AppError::Domain(e))
+    }
+}
+
+impl GraphqlError {
+    /// 穩定的機器可讀代碼
+    pub fn code(&self) -> &'static str {
+        match &self.0 {
+            AppError::Domain(DomainError::ValidationError { .. }) => "VALIDATION_ERROR",
+            AppError::Domain(DomainError::NotFound { .. }) => "NOT_FOUND",
+            AppError::Domain(DomainError::BusinessRule { .. }) => "BUSINESS_RULE_VIOLATION",
+            AppError::Domain(DomainError::InvalidOperation { .. }) => "INVALID_OPERATION",
+            AppError::Domain(DomainError::Conflict { .. }) => "CONFLICT",
+            AppError::Domain(DomainError::Forbidden { .. }) => "FORBIDDEN",
+            AppError::Domain(DomainError::Unavailable { .. }) => "SERVICE_UNAVAILABLE",
+            AppError::Infrastructure(_) => "INFRASTRUCTURE_ERROR",
+            AppError::Application(_) => "APPLICATION_ERROR",
+            AppError::Validation(_) => "VALIDATION_ERROR",
+            AppError::PreconditionFailed(_) => "PRECONDITION_FAILED",
+            AppError::Unauthorized(_) => "UNAUTHORIZED",
+            AppError::TooManyRequests(_) => "RATE_LIMITED",
+            AppError::Timeout(_) => "TIMEOUT",
+            AppError::PayloadTooLarge(_) => "PAYLOAD_TOO_LARGE",
+        }
+    }
+}
+
+impl From<GraphqlError> for async_graphql::Error {
+    fn from(error: GraphqlError) -> Self {
+        let code = error.code();
+        async_graphql::Error::new(error.0.to_string()).extend_with(|_, e| e.set("code", code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_error_code_is_exposed_in_extensions() {
+        let error: async_graphql::Error = GraphqlError::from(DomainError::Conflict {
+            message: "stale".to_string(),
+        })
+        .into();
+
+        let extensions = serde_json::to_value(&error.extensions).unwrap();
+        assert_eq!(extensions["code"], "CONFLICT");
+        assert!(error.message.contains("stale"));
+    }
+}
@@ -0,0 +1,12 @@
+//! GraphQL 表現層 - 以 async-graphql 提供與 HTTP API 相同的用例
+//!
+//! 同一請求中的用戶查詢經由 `DataLoader` 合併成一次批次載入；
+//! 查詢深度與複雜度受 `SchemaLimits` 限制，錯誤在 `extensions.code` 帶有與 HTTP 相同的代碼。
+
+pub mod error;
+pub mod loader;
+pub mod schema;
+pub mod server;
+
+pub use schema::{build_schema, AppSchema, SchemaLimits};
+pub use server::{router, GRAPHQL_PATH};
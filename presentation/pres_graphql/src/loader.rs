@@ -0,0 +1,46 @@
+use std::{collections::HashMap, sync::Arc};
+
+use application::{
+    bus::HasBus,
+    use_cases::get_users::{GetUsersQuery, MAX_BATCH_SIZE},
+};
+use async_graphql::dataloader::{DataLoader, Loader};
+use contracts::{
+    ports::{User, UserId},
+    CallerContext,
+};
+
+use crate::error::GraphqlError;
+
+/// 以 `GetUsersQuery` 批次載入用戶
+///
+/// 每個請求建立一個，因此批次與快取都以該請求的呼叫者身份授權。
+pub struct UserLoader {
+    bus: Arc<dyn HasBus>,
+    caller: CallerContext,
+}
+
+impl UserLoader {
+    /// 建立綁定呼叫者的 `DataLoader`，單批不超過用例允許的數量
+    pub fn data_loader(bus: Arc<dyn HasBus>, caller: CallerContext) -> DataLoader<Self> {
+        DataLoader::new(Self { bus, caller }, tokio::spawn).max_batch_size(MAX_BATCH_SIZE)
+    }
+}
+
+impl Loader<UserId> for UserLoader {
+    type Value = User;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[UserId]) -> Result<HashMap<UserId, User>, Self::Error> {
+        let users = self
+            .bus
+            .bus()
+            .send(&self.caller, GetUsersQuery { ids: keys.to_vec() })
+            .await
+            .map_err(GraphqlError)?;
+        Ok(users
+            .into_iter()
+            .map(|user| (user.id.clone(), user))
+            .collect())
+    }
+}
@@ -0,0 +1,373 @@
+use std::{sync::Arc, time::SystemTime};
+
+use application::{
+    bus::HasBus,
+    use_cases::{
+        create_user::CreateUserCmd,
+        delete_user::DeleteUserCmd,
+        list_users::{ListUsersQuery, MAX_PAGE_SIZE},
+        restore_user::RestoreUserCmd,
+        update_user::UpdateUserCmd,
+    },
+};
+use async_graphql::{
+    dataloader::DataLoader, Context, EmptySubscription, InputObject, Object, Result, Schema, ID,
+};
+use contracts::{
+    ports::{Email, User, UserId, UserName},
+    CallerContext,
+};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use crate::{error::GraphqlError, loader::UserLoader};
+
+/// 應用的 GraphQL schema
+pub type AppSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+/// 查詢深度與複雜度上限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchemaLimits {
+    pub max_depth: usize,
+    pub max_complexity: usize,
+}
+
+impl Default for SchemaLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 10,
+            max_complexity: 1000,
+        }
+    }
+}
+
+/// 建立 schema；匯流排作為共用資料，呼叫者與 `DataLoader` 於每個請求另外放入
+pub fn build_schema(bus: Arc<dyn HasBus>, limits: SchemaLimits) -> AppSchema {
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(bus)
+        .limit_depth(limits.max_depth)
+        .limit_complexity(limits.max_complexity)
+        .finish()
+}
+
+/// 單一請求的執行資料
+pub fn request_data(
+    request: async_graphql::Request,
+    bus: Arc<dyn HasBus>,
+    caller: CallerContext,
+) -> async_graphql::Request {
+    request
+        .data(UserLoader::data_loader(bus, caller.clone()))
+        .data(caller)
+}
+
+fn bus<'a>(ctx: &'a Context<'_>) -> &'a Arc<dyn HasBus> {
+    ctx.data_unchecked::<Arc<dyn HasBus>>()
+}
+
+fn caller<'a>(ctx: &'a Context<'_>) -> &'a CallerContext {
+    ctx.data_unchecked::<CallerContext>()
+}
+
+fn parse_id(id: &ID) -> Result<UserId> {
+    Ok(UserId::parse(id).map_err(GraphqlError::from)?)
+}
+
+fn parse_email(email: Option<String>) -> Result<Option<Email>> {
+    Ok(email
+        .map(Email::parse)
+        .transpose()
+        .map_err(GraphqlError::from)?)
+}
+
+fn rfc3339(at: SystemTime) -> String {
+    OffsetDateTime::from(at)
+        .format(&Rfc3339)
+        .expect("RFC 3339 formatting of a valid timestamp cannot fail")
+}
+
+/// 用戶
+pub struct UserNode(User);
+
+#[Object(name = "User")]
+impl UserNode {
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    async fn name(&self) -> &str {
+        self.0.name.as_str()
+    }
+
+    async fn email(&self) -> Option<&str> {
+        self.0.email.as_ref().map(Email::as_str)
+    }
+
+    /// 樂觀鎖版本，作為修改時的 `expectedVersion`
+    async fn version(&self) -> u64 {
+        self.0.version
+    }
+
+    /// RFC 3339
+    async fn created_at(&self) -> Option<String> {
+        self.0.audit.map(|a| rfc3339(a.created_at))
+    }
+
+    /// RFC 3339
+    async fn updated_at(&self) -> Option<String> {
+        self.0.audit.map(|a| rfc3339(a.updated_at))
+    }
+
+    /// RFC 3339；未刪除時為 null
+    async fn deleted_at(&self) -> Option<String> {
+        self.0.deleted_at.map(rfc3339)
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// 依 ID 取得用戶，不存在時為 null；同一請求中的查詢會合併成一次批次載入
+    async fn user(&self, ctx: &Context<'_>, id: ID) -> Result<Option<UserNode>> {
+        let id = parse_id(&id)?;
+        let user = ctx
+            .data_unchecked::<DataLoader<UserLoader>>()
+            .load_one(id)
+            .await?;
+        Ok(user.map(UserNode))
+    }
+
+    /// 依建立時間分頁列出用戶
+    #[graphql(complexity = "limit.unwrap_or(50) as usize * child_complexity")]
+    async fn users(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(validator(minimum = 1, maximum = 100))] limit: Option<u32>,
+        #[graphql(default)] offset: u64,
+        #[graphql(default)] include_deleted: bool,
+    ) -> Result<Vec<UserNode>> {
+        let users = bus(ctx)
+            .bus()
+            .send(
+                caller(ctx),
+                ListUsersQuery {
+                    limit: limit.unwrap_or(50).min(MAX_PAGE_SIZE),
+                    offset,
+                    include_deleted,
+                },
+            )
+            .await
+            .map_err(GraphqlError)?;
+        Ok(users.into_iter().map(UserNode).collect())
+    }
+}
+
+#[derive(InputObject)]
+pub struct CreateUserInput {
+    pub name: String,
+    pub email: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct UpdateUserInput {
+    pub name: String,
+    /// 取代後的電子郵件，null 表示移除
+    pub email: Option<String>,
+    /// 預期的目前版本，不符時回傳 `CONFLICT`
+    pub expected_version: Option<u64>,
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn create_user(&self, ctx: &Context<'_>, input: CreateUserInput) -> Result<UserNode> {
+        let cmd = CreateUserCmd {
+            name: UserName::parse(input.name).map_err(GraphqlError::from)?,
+            email: parse_email(input.email)?,
+        };
+        let user = bus(ctx)
+            .bus()
+            .send(caller(ctx), cmd)
+            .await
+            .map_err(GraphqlError)?;
+        tracing::info!(user_id = %user.id, "User created over GraphQL");
+        Ok(UserNode(user))
+    }
+
+    async fn update_user(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        input: UpdateUserInput,
+    ) -> Result<UserNode> {
+        let cmd = UpdateUserCmd {
+            id: parse_id(&id)?,
+            name: UserName::parse(input.name).map_err(GraphqlError::from)?,
+            email: parse_email(input.email)?,
+            expected_version: input.expected_version,
+        };
+        let user = bus(ctx)
+            .bus()
+            .send(caller(ctx), cmd)
+            .await
+            .map_err(GraphqlError)?;
+        Ok(UserNode(user))
+    }
+
+    /// 軟刪除用戶，成功時回傳 true
+    async fn delete_user(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        expected_version: Option<u64>,
+    ) -> Result<bool> {
+        let cmd = DeleteUserCmd {
+            id: parse_id(&id)?,
+            expected_version,
+        };
+        bus(ctx)
+            .bus()
+            .send(caller(ctx), cmd)
+            .await
+            .map_err(GraphqlError)?;
+        Ok(true)
+    }
+
+    async fn restore_user(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        expected_version: Option<u64>,
+    ) -> Result<UserNode> {
+        let cmd = RestoreUserCmd {
+            id: parse_id(&id)?,
+            expected_version,
+        };
+        let user = bus(ctx)
+            .bus()
+            .send(caller(ctx), cmd)
+            .await
+            .map_err(GraphqlError)?;
+        Ok(UserNode(user))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use application::{
+        bus::{Bus, Handler},
+        use_cases::get_users::GetUsersQuery,
+    };
+    use async_trait::async_trait;
+    use contracts::DomainError;
+
+    struct TestState(Bus);
+
+    impl HasBus for TestState {
+        fn bus(&self) -> &Bus {
+            &self.0
+        }
+    }
+
+    /// 記錄批次次數，只回傳名為 Alice 的用戶
+    struct CountingLoader(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl Handler<GetUsersQuery> for CountingLoader {
+        async fn handle(
+            &self,
+            _ctx: &CallerContext,
+            query: GetUsersQuery,
+        ) -> Result<Vec<User>, DomainError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            assert_eq!(query.ids.len(), 2);
+            Ok(vec![User::new(
+                UserId::from_bytes([1; 16]),
+                UserName::parse("Alice").unwrap(),
+                None,
+            )])
+        }
+    }
+
+    struct ConflictingCreate;
+
+    #[async_trait]
+    impl Handler<CreateUserCmd> for ConflictingCreate {
+        async fn handle(
+            &self,
+            _ctx: &CallerContext,
+            _cmd: CreateUserCmd,
+        ) -> Result<User, DomainError> {
+            Err(DomainError::Conflict {
+                message: "duplicate".to_string(),
+            })
+        }
+    }
+
+    async fn execute(bus: Bus, limits: SchemaLimits, query: &str) -> async_graphql::Response {
+        let bus: Arc<dyn HasBus> = Arc::new(TestState(bus));
+        let schema = build_schema(bus.clone(), limits);
+        schema
+            .execute(request_data(query.into(), bus, CallerContext::system()))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_user_lookups_are_batched() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let bus = Bus::new().with_handler::<GetUsersQuery>(Arc::new(CountingLoader(calls.clone())));
+        let query = format!(
+            r#"{{ a: user(id: "{}") {{ name }} b: user(id: "{}") {{ name }} }}"#,
+            UserId::from_bytes([1; 16]),
+            UserId::from_bytes([2; 16]),
+        );
+
+        let response = execute(bus, SchemaLimits::default(), &query).await;
+
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({ "a": { "name": "Alice" }, "b": null })
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_domain_error_code_in_extensions() {
+        let bus = Bus::new().with_handler::<CreateUserCmd>(Arc::new(ConflictingCreate));
+
+        let response = execute(
+            bus,
+            SchemaLimits::default(),
+            r#"mutation { createUser(input: { name: "Bob" }) { id } }"#,
+        )
+        .await;
+
+        let error = serde_json::to_value(&response.errors[0]).unwrap();
+        assert_eq!(error["extensions"]["code"], "CONFLICT");
+    }
+
+    #[tokio::test]
+    async fn test_depth_and_complexity_limits() {
+        let limits = SchemaLimits {
+            max_depth: 1,
+            max_complexity: 1000,
+        };
+        let response = execute(Bus::new(), limits, r#"{ user(id: "x") { name } }"#).await;
+        assert!(response.errors[0].message.contains("nested too deep"));
+
+        let limits = SchemaLimits {
+            max_depth: 10,
+            max_complexity: 100,
+        };
+        let response = execute(Bus::new(), limits, "{ users(limit: 100) { id name } }").await;
+        assert!(response.errors[0].message.contains("too complex"));
+    }
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use application::bus::HasBus;
+use async_graphql::http::GraphiQLSource;
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{
+    response::{Html, IntoResponse},
+    routing::post,
+    Extension, Router,
+};
+use contracts::{CallerContext, Principal, TenantId};
+
+use crate::schema::{build_schema, request_data, AppSchema, SchemaLimits};
+
+/// GraphQL 端點路徑
+pub const GRAPHQL_PATH: &str = "/graphql";
+
+#[derive(Clone)]
+struct GraphqlState {
+    schema: AppSchema,
+    bus: Arc<dyn HasBus>,
+}
+
+/// `/graphql` 路由；`graphiql` 為 `true` 時另以 GET 提供 GraphiQL 介面
+///
+/// 呼叫者取自 HTTP 驗證中介層放入的 `Principal`，因此應合併在驗證層之內。
+pub fn router<St>(bus: Arc<dyn HasBus>, limits: SchemaLimits, graphiql: bool) -> Router<St>
+where
+    St: Clone + Send + Sync + 'static,
+{
+    let state = GraphqlState {
+        schema: build_schema(bus.clone(), limits),
+        bus,
+    };
+    let route = if graphiql {
+        post(graphql_handler).get(graphiql_handler)
+    } else {
+        post(graphql_handler)
+    };
+    Router::new()
+        .route(GRAPHQL_PATH, route)
+        .layer(Extension(state))
+}
+
+async fn graphql_handler(
+    Extension(state): Extension<GraphqlState>,
+    principal: Option<Extension<Principal>>,
+    tenant: Option<Extension<TenantId>>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    let caller = match principal {
+        Some(Extension(principal)) => CallerContext::authenticated(principal),
+        None => CallerContext::anonymous(),
+    };
+    let caller = match tenant {
+        Some(Extension(tenant)) => caller.with_tenant(tenant),
+        None => caller,
+    };
+    let request = request_data(request.into_inner(), state.bus, caller);
+    state.schema.execute(request).await.into()
+}
+
+async fn graphiql_handler() -> impl IntoResponse {
+    Html(GraphiQLSource::build().endpoint(GRAPHQL_PATH).finish())
+}
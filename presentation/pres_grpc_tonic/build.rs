@@ -0,0 +1,34 @@
+use std::{env, fs, path::PathBuf};
+
+use prost::Message as _;
+use protobuf::Message as _;
+
+const PROTOS: &[&str] = &["proto/user/v1/user.proto"];
+
+/// 以純 Rust 解析器產生描述子，再交給 tonic 產生程式碼；
+/// 描述子同時輸出供 gRPC reflection 使用，因此建置環境不需要 protoc
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let parsed = protobuf_parse::Parser::new()
+        .pure()
+        .include("proto")
+        .inputs(PROTOS)
+        .parse_and_typecheck()?;
+
+    // 含匯入的 google/protobuf/*.proto，reflection 才能解析完整型別
+    let mut descriptors = protobuf::descriptor::FileDescriptorSet::new();
+    descriptors.file = parsed.file_descriptors;
+    let encoded = descriptors.write_to_bytes()?;
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    fs::write(out_dir.join("descriptor.bin"), &encoded)?;
+
+    tonic_prost_build::configure()
+        .build_client(true)
+        .build_server(true)
+        .compile_fds(prost_types::FileDescriptorSet::decode(encoded.as_slice())?)?;
+
+    for proto in PROTOS {
+        println!("cargo:rerun-if-changed={proto}");
+    }
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use contracts::{AppError, DomainError};
+use tonic::{Code, Status};
+
+/// 應用錯誤轉為 gRPC 狀態，對應方式與 HTTP 的 `ApiError` 一致
+#[derive(Debug)]
+pub struct GrpcError(pub AppError);
+
+impl From<AppError> for GrpcError {
+    fn from(e: AppError) -> Self {
+        Self(e)
+    }
+}
+
+impl From<DomainError> for GrpcError {
+    fn from(e: DomainError) -> Self {
+        Self(AppError::Domain(e))
+    }
+}
+
+impl From<GrpcError> for Status {
+    fn from(error: GrpcError) -> Self {
+        let code = match &error.0 {
+            AppError::Domain(DomainError::ValidationError { .. }) => Code::InvalidArgument,
+            AppError::Domain(DomainError::NotFound { .. }) => Code::NotFound,
+            AppError::Domain(DomainError::BusinessRule { .. }) => Code::FailedPrecondition,
+            AppError::Domain(DomainError::InvalidOperation { .. }) => Code::FailedPrecondition,
+            // 樂觀鎖衝突，客戶端可重新讀取後重試
+            AppError::Domain(DomainError::Conflict { .. }) => Code::Aborted,
+            AppError::Domain(DomainError::Forbidden { .. }) => Code::PermissionDenied,
+            AppError::Domain(DomainError::Unavailable { .. }) => Code::Unavailable,
+            AppError::Infrastructure(_) | AppError::Application(_) => Code::Internal,
+            AppError::Validation(_) => Code::InvalidArgument,
+            AppError::PreconditionFailed(_) => Code::FailedPrecondition,
+            AppError::Unauthorized(_) => Code::Unauthenticated,
+            AppError::TooManyRequests(_) | AppError::PayloadTooLarge(_) => Code::ResourceExhausted,
+            AppError::Timeout(_) => Code::DeadlineExceeded,
+        };
+        Status::new(code, error.0.to_string())
+    }
+}
+
+/// 指標標籤使用的狀態碼名稱
+pub fn code_name(code: Code) -> &'static str {
+    match code {
+        Code::Ok => "OK",
+        Code::Cancelled => "CANCELLED",
+        Code::Unknown => "UNKNOWN",
+        Code::InvalidArgument => "INVALID_ARGUMENT",
+        Code::DeadlineExceeded => "DEADLINE_EXCEEDED",
+        Code::NotFound => "NOT_FOUND",
+        Code::AlreadyExists => "ALREADY_EXISTS",
+        Code::PermissionDenied => "PERMISSION_DENIED",
+        Code::ResourceExhausted => "RESOURCE_EXHAUSTED",
+        Code::FailedPrecondition => "FAILED_PRECONDITION",
+        Code::Aborted => "ABORTED",
+        Code::OutOfRange => "OUT_OF_RANGE",
+        Code::Unimplemented => "UNIMPLEMENTED",
+        Code::Internal => "INTERNAL",
+        Code::Unavailable => "UNAVAILABLE",
+        Code::DataLoss => "DATA_LOSS",
+        Code::Unauthenticated => "UNAUTHENTICATED",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_errors_map_to_grpc_codes() {
+        let status = |e: DomainError| Status::from(GrpcError::from(e)).code();
+
+        assert_eq!(
+            status(DomainError::NotFound {
+                message: "user".to_string()
+            }),
+            Code::NotFound
+        );
+        assert_eq!(
+            status(DomainError::Conflict {
+                message: "version".to_string()
+            }),
+            Code::Aborted
+        );
+        assert_eq!(
+            status(DomainError::Forbidden {
+                message: "users:create".to_string()
+            }),
+            Code::PermissionDenied
+        );
+        assert_eq!(
+            Status::from(GrpcError(AppError::Unauthorized("token".to_string()))).code(),
+            Code::Unauthenticated
+        );
+    }
+}
@@ -0,0 +1,18 @@
+//! gRPC 表現層 - 以 tonic 提供與 HTTP API 相同的用例
+//!
+//! 服務以 axum 路由的形式提供，可與 HTTP API 共用同一個連接埠與中介層（驗證、限流）。
+
+pub mod error;
+pub mod server;
+pub mod user_service;
+
+/// 由 `proto/` 產生的訊息與服務
+pub mod proto {
+    tonic::include_proto!("user.v1");
+
+    /// 完整的描述子集合，供 gRPC reflection 使用
+    pub const FILE_DESCRIPTOR_SET: &[u8] =
+        include_bytes!(concat!(env!("OUT_DIR"), "/descriptor.bin"));
+}
+
+pub use server::router;
@@ -0,0 +1,189 @@
+use application::bus::HasBus;
+use axum::Router;
+use contracts::DynObservability;
+use tonic::server::NamedService;
+
+use crate::{
+    proto::{self, user_service_server::UserServiceServer},
+    user_service::UserGrpcService,
+};
+
+/// gRPC 服務的 axum 路由，請求路徑為 `/<package>.<Service>/<Method>`
+///
+/// 回傳的路由可直接合併進 HTTP 路由，與 HTTP API 共用連接埠（h2c）與中介層。
+/// `reflection` 為 `true` 時一併提供 v1 與 v1alpha 的 reflection 服務。
+pub async fn router<S, St>(
+    state: S,
+    observability: DynObservability,
+    reflection: bool,
+) -> Result<Router<St>, tonic_reflection::server::Error>
+where
+    S: HasBus + 'static,
+    St: Clone + Send + Sync + 'static,
+{
+    let (health_reporter, health_service) = tonic_health::server::health_reporter();
+    health_reporter
+        .set_serving::<UserServiceServer<UserGrpcService<S>>>()
+        .await;
+
+    let user_service = UserServiceServer::new(UserGrpcService::new(state, observability));
+
+    let router = Router::new()
+        .route_service(&service_path(&user_service), user_service)
+        .route_service(&service_path(&health_service), health_service);
+
+    if !reflection {
+        return Ok(router);
+    }
+
+    let builder = || {
+        tonic_reflection::server::Builder::configure()
+            .register_encoded_file_descriptor_set(proto::FILE_DESCRIPTOR_SET)
+            .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+    };
+    let reflection_v1 = builder().build_v1()?;
+    let reflection_v1alpha = builder().build_v1alpha()?;
+
+    Ok(router
+        .route_service(&service_path(&reflection_v1), reflection_v1)
+        .route_service(&service_path(&reflection_v1alpha), reflection_v1alpha))
+}
+
+fn service_path<T: NamedService>(_: &T) -> String {
+    format!("/{}/{{*method}}", T::NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use application::{
+        bus::{Bus, Handler},
+        use_cases::{create_user::CreateUserCmd, get_user::GetUserQuery},
+    };
+    use async_trait::async_trait;
+    use contracts::{
+        ports::{DomainError, MockObservabilityPort, User, UserId},
+        CallerContext,
+    };
+    use tonic::Code;
+    use tonic_health::pb::{
+        health_check_response::ServingStatus, health_client::HealthClient, HealthCheckRequest,
+    };
+
+    use crate::proto::{user_service_client::UserServiceClient, CreateUserRequest, GetUserRequest};
+
+    #[derive(Clone)]
+    struct TestState(Arc<Bus>);
+
+    impl HasBus for TestState {
+        fn bus(&self) -> &Bus {
+            &self.0
+        }
+    }
+
+    struct CreatesUser;
+
+    #[async_trait]
+    impl Handler<CreateUserCmd> for CreatesUser {
+        async fn handle(
+            &self,
+            _ctx: &CallerContext,
+            cmd: CreateUserCmd,
+        ) -> Result<User, DomainError> {
+            Ok(User::new(UserId::from_bytes([7; 16]), cmd.name, cmd.email))
+        }
+    }
+
+    struct NothingFound;
+
+    #[async_trait]
+    impl Handler<GetUserQuery> for NothingFound {
+        async fn handle(
+            &self,
+            _ctx: &CallerContext,
+            query: GetUserQuery,
+        ) -> Result<User, DomainError> {
+            Err(DomainError::NotFound {
+                message: format!("User {}", query.id),
+            })
+        }
+    }
+
+    /// 以 h2c 啟動只含 gRPC 路由的伺服器，回傳位址；非預期的方法或狀態碼會讓指標回呼失敗
+    async fn serve() -> String {
+        let bus = Bus::new()
+            .with_handler::<CreateUserCmd>(Arc::new(CreatesUser))
+            .with_handler::<GetUserQuery>(Arc::new(NothingFound));
+        let mut observability = MockObservabilityPort::new();
+        observability
+            .expect_on_grpc_call()
+            .withf(|method, code, _| {
+                matches!(
+                    (method, code),
+                    ("/user.v1.UserService/CreateUser", "OK")
+                        | ("/user.v1.UserService/GetUser", "INVALID_ARGUMENT")
+                        | ("/user.v1.UserService/GetUser", "NOT_FOUND")
+                )
+            })
+            .returning(|_, _, _| ());
+
+        let app: Router = router(TestState(Arc::new(bus)), Arc::new(observability), true)
+            .await
+            .unwrap();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn test_user_service_maps_results_to_status_codes() {
+        let mut client = UserServiceClient::connect(serve().await).await.unwrap();
+
+        let user = client
+            .create_user(CreateUserRequest {
+                name: "Ada".to_string(),
+                email: Some("ada@example.com".to_string()),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(user.name, "Ada");
+        assert_eq!(user.email.as_deref(), Some("ada@example.com"));
+
+        let status = client
+            .get_user(GetUserRequest {
+                id: "not-a-uuid".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let status = client
+            .get_user(GetUserRequest { id: user.id })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_user_service_serving() {
+        let channel = tonic::transport::Endpoint::from_shared(serve().await)
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        let mut client = HealthClient::new(channel);
+
+        let response = client
+            .check(HealthCheckRequest {
+                service: crate::proto::user_service_server::SERVICE_NAME.to_string(),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.status(), ServingStatus::Serving);
+    }
+}
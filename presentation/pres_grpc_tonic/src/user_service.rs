@@ -0,0 +1,168 @@
+use std::{future::Future, time::Instant};
+
+use application::{
+    bus::HasBus,
+    use_cases::{
+        create_user::CreateUserCmd,
+        get_user::GetUserQuery,
+        list_users::{ListUsersQuery, MAX_PAGE_SIZE},
+    },
+};
+use contracts::{
+    ports::{Email, User as DomainUser, UserId, UserName},
+    CallerContext, DynObservability, Principal, TenantId,
+};
+use tonic::{Code, Request, Response, Status};
+
+use crate::{
+    error::{code_name, GrpcError},
+    proto::{self, user_service_server::UserService},
+};
+
+/// `limit` 為 0 時使用的每頁筆數，與 HTTP API 相同
+const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// 以匯流排實作的用戶 gRPC 服務
+pub struct UserGrpcService<S> {
+    state: S,
+    observability: DynObservability,
+}
+
+impl<S> UserGrpcService<S>
+where
+    S: HasBus + 'static,
+{
+    pub fn new(state: S, observability: DynObservability) -> Self {
+        Self {
+            state,
+            observability,
+        }
+    }
+
+    /// 記錄呼叫結果與耗時
+    async fn observe<T>(
+        &self,
+        method: &'static str,
+        call: impl Future<Output = Result<T, Status>>,
+    ) -> Result<Response<T>, Status> {
+        let started = Instant::now();
+        let result = call.await;
+        let code = result.as_ref().map_or_else(Status::code, |_| Code::Ok);
+        self.observability
+            .on_grpc_call(method, code_name(code), started.elapsed().as_secs_f64())
+            .await;
+        result.map(Response::new)
+    }
+}
+
+/// 由 HTTP 驗證中介層放入的 `Principal` 決定呼叫者，沒有則為匿名；
+/// 租戶中介層放入的 `TenantId` 決定租戶，沒有則為預設租戶
+fn caller<T>(request: &Request<T>) -> CallerContext {
+    let ctx = match request.extensions().get::<Principal>() {
+        Some(principal) => CallerContext::authenticated(principal.clone()),
+        None => CallerContext::anonymous(),
+    };
+    match request.extensions().get::<TenantId>() {
+        Some(tenant) => ctx.with_tenant(tenant.clone()),
+        None => ctx,
+    }
+}
+
+#[tonic::async_trait]
+impl<S> UserService for UserGrpcService<S>
+where
+    S: HasBus + 'static,
+{
+    async fn create_user(
+        &self,
+        request: Request<proto::CreateUserRequest>,
+    ) -> Result<Response<proto::User>, Status> {
+        let caller = caller(&request);
+        let request = request.into_inner();
+        self.observe("/user.v1.UserService/CreateUser", async {
+            let cmd = CreateUserCmd {
+                name: UserName::parse(request.name).map_err(GrpcError::from)?,
+                email: request
+                    .email
+                    .map(Email::parse)
+                    .transpose()
+                    .map_err(GrpcError::from)?,
+            };
+            let user = self
+                .state
+                .bus()
+                .send(&caller, cmd)
+                .await
+                .map_err(GrpcError)?;
+            tracing::info!(user_id = %user.id, "User created over gRPC");
+            Ok(user.into())
+        })
+        .await
+    }
+
+    async fn get_user(
+        &self,
+        request: Request<proto::GetUserRequest>,
+    ) -> Result<Response<proto::User>, Status> {
+        let caller = caller(&request);
+        let request = request.into_inner();
+        self.observe("/user.v1.UserService/GetUser", async {
+            let id = UserId::parse(&request.id).map_err(GrpcError::from)?;
+            let user = self
+                .state
+                .bus()
+                .send(&caller, GetUserQuery { id })
+                .await
+                .map_err(GrpcError)?;
+            Ok(user.into())
+        })
+        .await
+    }
+
+    async fn list_users(
+        &self,
+        request: Request<proto::ListUsersRequest>,
+    ) -> Result<Response<proto::ListUsersResponse>, Status> {
+        let caller = caller(&request);
+        let request = request.into_inner();
+        self.observe("/user.v1.UserService/ListUsers", async {
+            let limit = match request.limit {
+                0 => DEFAULT_PAGE_SIZE,
+                limit => limit.clamp(1, MAX_PAGE_SIZE),
+            };
+            let users = self
+                .state
+                .bus()
+                .send(
+                    &caller,
+                    ListUsersQuery {
+                        limit,
+                        offset: request.offset,
+                        include_deleted: request.include_deleted,
+                    },
+                )
+                .await
+                .map_err(GrpcError)?;
+            Ok(proto::ListUsersResponse {
+                users: users.into_iter().map(proto::User::from).collect(),
+                limit,
+                offset: request.offset,
+            })
+        })
+        .await
+    }
+}
+
+impl From<DomainUser> for proto::User {
+    fn from(user: DomainUser) -> Self {
+        proto::User {
+            id: user.id.to_string(),
+            name: user.name.as_str().to_owned(),
+            email: user.email.map(|email| email.as_str().to_owned()),
+            version: user.version,
+            created_at: user.audit.map(|a| a.created_at.into()),
+            updated_at: user.audit.map(|a| a.updated_at.into()),
+            deleted_at: user.deleted_at.map(Into::into),
+        }
+    }
+}
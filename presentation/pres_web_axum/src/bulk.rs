@@ -0,0 +1,436 @@
+//! 批次匯入與匯出的檔案格式
+//!
+//! 支援 NDJSON（每行一個 JSON 物件）與含標頭列的 CSV。匯入時以 [`RecordSplitter`]
+//! 將串流中的資料切成完整的紀錄，不需等到整個請求主體讀完；匯出時每批用戶各自編碼。
+//! 兩種格式的欄位相同：`id`、`name`、`email`，匯出另外帶有版本與時間戳記，
+//! 因此匯出的檔案可以直接再匯入另一個系統。
+
+use application::use_cases::import_users::ImportUserRow;
+use axum::http::{header::CONTENT_TYPE, HeaderMap};
+use contracts::{ports::User, AppError};
+use serde::Deserialize;
+
+use crate::dtos::UserResponse;
+
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+pub const CSV_CONTENT_TYPE: &str = "text/csv";
+
+/// 單筆紀錄的預設大小上限；匯入路由不限制整個主體，只限制每筆紀錄
+pub const DEFAULT_MAX_RECORD_BYTES: usize = 64 * 1024;
+
+/// 匯出檔的 CSV 欄位
+const CSV_COLUMNS: [&str; 7] = [
+    "id",
+    "name",
+    "email",
+    "version",
+    "created_at",
+    "updated_at",
+    "deleted_at",
+];
+
+/// 批次檔案格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkFormat {
+    #[default]
+    Ndjson,
+    Csv,
+}
+
+impl BulkFormat {
+    /// 依 `Content-Type` 判斷匯入檔的格式
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, AppError> {
+        let content_type = headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase());
+        match content_type.as_deref() {
+            Some(NDJSON_CONTENT_TYPE | "application/jsonl") => Ok(BulkFormat::Ndjson),
+            Some(CSV_CONTENT_TYPE) => Ok(BulkFormat::Csv),
+            _ => Err(AppError::Validation(format!(
+                "Content-Type must be {NDJSON_CONTENT_TYPE} or {CSV_CONTENT_TYPE}"
+            ))),
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            BulkFormat::Ndjson => NDJSON_CONTENT_TYPE,
+            BulkFormat::Csv => "text/csv; charset=utf-8",
+        }
+    }
+}
+
+/// 一筆完整的紀錄與其起始行號
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawRecord {
+    pub line: u64,
+    pub text: String,
+}
+
+/// 將分段抵達的位元組切成完整的紀錄
+///
+/// 以換行分隔紀錄；CSV 引號內的換行屬於欄位內容，不會切開紀錄。空白行會被略過但仍計入行號。
+/// 每次只掃描新加入的位元組；單筆紀錄（含尚未結束的紀錄）超過上限時回傳 413，
+/// 避免一行超長或未閉合引號的 CSV 讓整個上傳留在記憶體中。
+#[derive(Debug)]
+pub struct RecordSplitter {
+    format: BulkFormat,
+    buffer: Vec<u8>,
+    /// 緩衝區中已掃描過的位元組數
+    scanned: usize,
+    /// 掃描位置是否在 CSV 引號內
+    in_quotes: bool,
+    /// 緩衝區開頭的紀錄目前跨越的行數
+    lines: u64,
+    /// 緩衝區開頭所在的行號
+    line: u64,
+    max_record_bytes: usize,
+}
+
+impl RecordSplitter {
+    pub fn new(format: BulkFormat) -> Self {
+        Self {
+            format,
+            buffer: Vec::new(),
+            scanned: 0,
+            in_quotes: false,
+            lines: 1,
+            line: 1,
+            max_record_bytes: DEFAULT_MAX_RECORD_BYTES,
+        }
+    }
+
+    /// 單筆紀錄的大小上限（位元組）
+    pub fn with_max_record_bytes(mut self, bytes: usize) -> Self {
+        self.max_record_bytes = bytes;
+        self
+    }
+
+    /// 加入新的資料，回傳已完整的紀錄
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<RawRecord>, AppError> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut records = Vec::new();
+        let mut start = 0;
+        for i in self.scanned..self.buffer.len() {
+            match self.buffer[i] {
+                b'"' if self.format == BulkFormat::Csv => self.in_quotes = !self.in_quotes,
+                b'\n' if self.in_quotes => self.lines += 1,
+                b'\n' => {
+                    self.check_size(i - start)?;
+                    records.extend(record(&self.buffer[start..i], self.line)?);
+                    self.line += self.lines;
+                    self.lines = 1;
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        self.buffer.drain(..start);
+        self.scanned = self.buffer.len();
+        self.check_size(self.buffer.len())?;
+        Ok(records)
+    }
+
+    /// 輸入結束；回傳最後一筆沒有換行結尾的紀錄
+    pub fn finish(self) -> Result<Option<RawRecord>, AppError> {
+        self.check_size(self.buffer.len())?;
+        record(&self.buffer, self.line)
+    }
+
+    fn check_size(&self, len: usize) -> Result<(), AppError> {
+        if len > self.max_record_bytes {
+            return Err(AppError::PayloadTooLarge(format!(
+                "Record at line {} exceeds {} bytes",
+                self.line, self.max_record_bytes
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn record(bytes: &[u8], line: u64) -> Result<Option<RawRecord>, AppError> {
+    let bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+    let text = std::str::from_utf8(bytes)
+        .map_err(|_| AppError::Validation(format!("Line {line} is not valid UTF-8")))?;
+    Ok(Some(RawRecord {
+        line,
+        text: text.to_owned(),
+    }))
+}
+
+/// NDJSON 的一列；多餘的欄位（例如匯出的時間戳記）會被忽略
+#[derive(Deserialize)]
+struct NdjsonRow {
+    id: Option<String>,
+    name: String,
+    email: Option<String>,
+}
+
+/// 將紀錄解析成匯入列；格式錯誤只影響該列，以錯誤訊息回報
+#[derive(Debug, Default)]
+pub struct RowParser {
+    /// CSV 標頭列中各欄位的位置
+    columns: Option<CsvColumns>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CsvColumns {
+    id: Option<usize>,
+    name: usize,
+    email: Option<usize>,
+}
+
+impl RowParser {
+    /// 解析一筆紀錄；CSV 的標頭列回傳 `Ok(None)`
+    ///
+    /// 外層 `Err` 表示整個檔案無法處理（例如 CSV 缺少 `name` 欄位），
+    /// 內層 `Err` 為單列的錯誤訊息。
+    pub fn parse(
+        &mut self,
+        format: BulkFormat,
+        record: RawRecord,
+    ) -> Result<Option<Result<ImportUserRow, String>>, AppError> {
+        match format {
+            BulkFormat::Ndjson => Ok(Some(
+                serde_json::from_str::<NdjsonRow>(&record.text)
+                    .map(|row| ImportUserRow {
+                        line: record.line,
+                        id: row.id,
+                        name: row.name,
+                        email: row.email,
+                    })
+                    .map_err(|e| format!("Invalid JSON: {e}")),
+            )),
+            BulkFormat::Csv => self.parse_csv(record),
+        }
+    }
+
+    fn parse_csv(
+        &mut self,
+        record: RawRecord,
+    ) -> Result<Option<Result<ImportUserRow, String>>, AppError> {
+        let fields = match csv_fields(&record.text) {
+            Ok(fields) => fields,
+            Err(e) if self.columns.is_none() => {
+                return Err(AppError::Validation(format!("Invalid CSV header: {e}")))
+            }
+            Err(e) => return Ok(Some(Err(format!("Invalid CSV: {e}")))),
+        };
+
+        let Some(columns) = self.columns else {
+            let position = |name: &str| fields.iter().position(|f| f.trim() == name);
+            let name = position("name").ok_or_else(|| {
+                AppError::Validation("CSV header must contain a `name` column".to_string())
+            })?;
+            self.columns = Some(CsvColumns {
+                id: position("id"),
+                name,
+                email: position("email"),
+            });
+            return Ok(None);
+        };
+
+        // 空字串視為未提供
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|i| fields.get(i))
+                .filter(|value| !value.is_empty())
+                .cloned()
+        };
+        Ok(Some(Ok(ImportUserRow {
+            line: record.line,
+            id: field(columns.id),
+            name: fields.get(columns.name).cloned().unwrap_or_default(),
+            email: field(columns.email),
+        })))
+    }
+}
+
+fn csv_fields(text: &str) -> Result<Vec<String>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    match reader.records().next() {
+        Some(record) => Ok(record?.iter().map(str::to_owned).collect()),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// CSV 匯出檔的標頭列
+pub fn csv_header() -> Vec<u8> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(CSV_COLUMNS)
+        .expect("writing to a Vec cannot fail");
+    writer.into_inner().expect("writing to a Vec cannot fail")
+}
+
+/// 將一批用戶編碼成匯出檔的內容
+pub fn encode_users(format: BulkFormat, users: Vec<User>) -> Result<Vec<u8>, AppError> {
+    let encode_error = |e: &dyn std::fmt::Display| {
+        AppError::Application(format!("Failed to encode exported users: {e}"))
+    };
+    match format {
+        BulkFormat::Ndjson => {
+            let mut out = Vec::new();
+            for user in users {
+                serde_json::to_writer(&mut out, &UserResponse::from(user))
+                    .map_err(|e| encode_error(&e))?;
+                out.push(b'\n');
+            }
+            Ok(out)
+        }
+        BulkFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for user in users {
+                let user = UserResponse::from(user);
+                writer
+                    .write_record([
+                        user.id.to_string(),
+                        user.name.as_str().to_owned(),
+                        user.email
+                            .map(|e| e.as_str().to_owned())
+                            .unwrap_or_default(),
+                        user.version.to_string(),
+                        user.created_at.unwrap_or_default(),
+                        user.updated_at.unwrap_or_default(),
+                        user.deleted_at.unwrap_or_default(),
+                    ])
+                    .map_err(|e| encode_error(&e))?;
+            }
+            writer.into_inner().map_err(|e| encode_error(&e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::ports::{Email, UserId, UserName};
+
+    fn split(format: BulkFormat, chunks: &[&str]) -> Vec<RawRecord> {
+        let mut splitter = RecordSplitter::new(format);
+        let mut records = Vec::new();
+        for chunk in chunks {
+            records.extend(splitter.push(chunk.as_bytes()).unwrap());
+        }
+        records.extend(splitter.finish().unwrap());
+        records
+    }
+
+    #[test]
+    fn test_splitter_joins_chunks_and_tracks_lines() {
+        let records = split(
+            BulkFormat::Ndjson,
+            &["{\"name\":\"A", "da\"}\r\n\n{\"name\":", "\"Bob\"}"],
+        );
+        assert_eq!(
+            records,
+            vec![
+                RawRecord {
+                    line: 1,
+                    text: r#"{"name":"Ada"}"#.to_string(),
+                },
+                RawRecord {
+                    line: 3,
+                    text: r#"{"name":"Bob"}"#.to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_oversized_or_unterminated_records_are_rejected() {
+        let mut splitter = RecordSplitter::new(BulkFormat::Ndjson).with_max_record_bytes(8);
+        assert_eq!(splitter.push(b"{}\n").unwrap().len(), 1);
+        assert!(splitter.push(b"{\"name\":").unwrap().is_empty());
+        assert!(matches!(
+            splitter.push(b"\"Ada\"}"),
+            Err(AppError::PayloadTooLarge(_))
+        ));
+
+        // 未閉合的引號讓換行都屬於同一筆紀錄
+        let mut splitter = RecordSplitter::new(BulkFormat::Csv).with_max_record_bytes(8);
+        assert_eq!(splitter.push(b"name\n\"Ada\n").unwrap().len(), 1);
+        assert!(matches!(
+            splitter.push(b"Lovelace\n"),
+            Err(AppError::PayloadTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_csv_quoted_newlines_stay_in_one_record() {
+        let records = split(
+            BulkFormat::Csv,
+            &["name,email\n\"Ada\nLovelace\",", "ada@example.com\nBob,\n"],
+        );
+        let mut parser = RowParser::default();
+        let rows: Vec<_> = records
+            .into_iter()
+            .filter_map(|record| parser.parse(BulkFormat::Csv, record).unwrap())
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                Ok(ImportUserRow {
+                    line: 2,
+                    id: None,
+                    name: "Ada\nLovelace".to_string(),
+                    email: Some("ada@example.com".to_string()),
+                }),
+                Ok(ImportUserRow {
+                    line: 4,
+                    id: None,
+                    name: "Bob".to_string(),
+                    email: None,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_without_name_column_is_rejected() {
+        let mut parser = RowParser::default();
+        let header = RawRecord {
+            line: 1,
+            text: "id,email".to_string(),
+        };
+        assert!(parser.parse(BulkFormat::Csv, header).is_err());
+    }
+
+    #[test]
+    fn test_exported_csv_can_be_imported_again() {
+        let user = User::new(
+            UserId::from_u128(1),
+            UserName::parse("Ada, Countess").unwrap(),
+            Some(Email::parse("ada@example.com").unwrap()),
+        );
+        let mut file = csv_header();
+        file.extend(encode_users(BulkFormat::Csv, vec![user]).unwrap());
+
+        let file = String::from_utf8(file).unwrap();
+        let mut parser = RowParser::default();
+        let rows: Vec<_> = split(BulkFormat::Csv, &[&file])
+            .into_iter()
+            .filter_map(|record| parser.parse(BulkFormat::Csv, record).unwrap())
+            .collect();
+        assert_eq!(
+            rows,
+            vec![Ok(ImportUserRow {
+                line: 2,
+                id: Some(UserId::from_u128(1).to_string()),
+                name: "Ada, Countess".to_string(),
+                email: Some("ada@example.com".to_string()),
+            })]
+        );
+    }
+}
@@ -1,5 +1,8 @@
 pub mod request;
 pub mod response;
+pub mod v2;
+pub mod versioned;
 
 pub use request::*;
 pub use response::*;
+pub use versioned::*;
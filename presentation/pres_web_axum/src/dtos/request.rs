@@ -1,12 +1,73 @@
+use crate::bulk::BulkFormat;
+use contracts::ports::{Email, UserName};
 use serde::Deserialize;
-use validator::Validate;
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Deserialize, Debug, Validate)]
+/// 名稱與電子郵件在反序列化時即由值物件驗證
+#[derive(Deserialize, Debug, ToSchema)]
 pub struct CreateUserRequest {
-    #[validate(length(
-        min = 1,
-        max = 100,
-        message = "Name must be between 1 and 100 characters"
-    ))]
+    #[schema(value_type = String, min_length = 1, max_length = 100, example = "Alice")]
+    pub name: UserName,
+    #[serde(default)]
+    #[schema(value_type = Option<String>, format = Email, example = "alice@example.com")]
+    pub email: Option<Email>,
+}
+
+/// PUT 為完整取代，省略 `email` 即移除電子郵件
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct UpdateUserRequest {
+    #[schema(value_type = String, min_length = 1, max_length = 100, example = "Alice")]
+    pub name: UserName,
+    #[serde(default)]
+    #[schema(value_type = Option<String>, format = Email, example = "alice@example.com")]
+    pub email: Option<Email>,
+}
+
+/// GET /users 的查詢參數
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListUsersParams {
+    /// 每頁筆數，超出範圍時截到 1..=100
+    #[serde(default = "default_page_limit")]
+    #[param(default = 50, minimum = 1, maximum = 100)]
+    pub limit: u32,
+    #[serde(default)]
+    pub offset: u64,
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+fn default_page_limit() -> u32 {
+    50
+}
+
+/// POST /api-keys 的請求內容
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct CreateApiKeyRequest {
     pub name: String,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// 有效期限（秒）；省略表示永不過期
+    #[serde(default)]
+    pub expires_in_secs: Option<u64>,
+}
+
+/// POST /users/import 的查詢參數
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportUsersParams {
+    /// 只驗證並回報每列結果，不寫入
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// GET /users/export 的查詢參數
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportUsersParams {
+    #[serde(default)]
+    #[param(inline)]
+    pub format: BulkFormat,
+    #[serde(default)]
+    pub include_deleted: bool,
 }
@@ -1,23 +1,197 @@
-use contracts::ports::User as DomainUser;
+use application::use_cases::import_users::{ImportOutcome, ImportRowResult};
+use contracts::ports::{ApiKey, ApiKeyId, Email, User as DomainUser, UserId, UserName};
 use serde::Serialize;
+use std::time::SystemTime;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToSchema)]
 pub struct UserResponse {
-    pub id: String,
-    pub name: String,
+    #[schema(value_type = String, pattern = "^usr_[0-7][0-9a-hjkmnp-tv-z]{25}$", example = "usr_01h455vb4pex5vsknk084sn02q")]
+    pub id: UserId,
+    #[schema(value_type = String)]
+    pub name: UserName,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, format = Email)]
+    pub email: Option<Email>,
+    pub version: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = DateTime)]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = DateTime)]
+    pub updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = DateTime)]
+    pub deleted_at: Option<String>,
+}
+
+/// 以 RFC 3339 格式輸出時間
+pub(crate) fn rfc3339(at: SystemTime) -> String {
+    OffsetDateTime::from(at)
+        .format(&Rfc3339)
+        .expect("RFC 3339 formatting of a valid timestamp cannot fail")
 }
 
 impl From<DomainUser> for UserResponse {
     fn from(domain_user: DomainUser) -> Self {
         UserResponse {
-            id: domain_user.id.to_string(),
+            id: domain_user.id,
             name: domain_user.name,
+            email: domain_user.email,
+            version: domain_user.version,
+            created_at: domain_user.audit.map(|a| rfc3339(a.created_at)),
+            updated_at: domain_user.audit.map(|a| rfc3339(a.updated_at)),
+            deleted_at: domain_user.deleted_at.map(rfc3339),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct UserListResponse {
+    pub users: Vec<UserResponse>,
+    pub limit: u32,
+    pub offset: u64,
+}
+
+/// API 金鑰資訊（不含秘密）
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ApiKeyResponse {
+    #[schema(value_type = String, pattern = "^key_[0-7][0-9a-hjkmnp-tv-z]{25}$", example = "key_01h455vb4pex5vsknk084sn02q")]
+    pub id: ApiKeyId,
+    pub name: String,
+    pub scopes: Vec<String>,
+    #[schema(format = DateTime)]
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = DateTime)]
+    pub expires_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = DateTime)]
+    pub revoked_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = DateTime)]
+    pub last_used_at: Option<String>,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(key: ApiKey) -> Self {
+        ApiKeyResponse {
+            id: key.id,
+            name: key.name,
+            scopes: key.scopes,
+            created_at: rfc3339(key.created_at),
+            expires_at: key.expires_at.map(rfc3339),
+            revoked_at: key.revoked_at.map(rfc3339),
+            last_used_at: key.last_used_at.map(rfc3339),
+        }
+    }
+}
+
+/// 建立或輪替後的金鑰；`token` 只會在這個回應中出現一次
+#[derive(Serialize, Debug, ToSchema)]
+pub struct IssuedApiKeyResponse {
+    #[serde(flatten)]
+    pub key: ApiKeyResponse,
+    pub token: String,
+}
+
+/// 批次匯入的結果摘要與每列結果
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ImportReportResponse {
+    pub dry_run: bool,
+    pub total: u64,
+    pub imported: u64,
+    pub valid: u64,
+    pub already_exists: u64,
+    pub invalid: u64,
+    pub rows: Vec<ImportRowReport>,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportRowStatus {
+    Imported,
+    Valid,
+    AlreadyExists,
+    Invalid,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ImportRowReport {
+    /// 來源檔案中的行號（從 1 開始）
+    pub line: u64,
+    pub status: ImportRowStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, pattern = "^usr_[0-7][0-9a-hjkmnp-tv-z]{25}$", example = "usr_01h455vb4pex5vsknk084sn02q")]
+    pub id: Option<UserId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<ImportRowResult> for ImportRowReport {
+    fn from(result: ImportRowResult) -> Self {
+        let (status, id, error) = match result.outcome {
+            ImportOutcome::Imported(id) => (ImportRowStatus::Imported, Some(id), None),
+            ImportOutcome::Valid(id) => (ImportRowStatus::Valid, Some(id), None),
+            ImportOutcome::AlreadyExists(id) => (ImportRowStatus::AlreadyExists, Some(id), None),
+            ImportOutcome::Invalid(error) => (ImportRowStatus::Invalid, None, Some(error)),
+        };
+        ImportRowReport {
+            line: result.line,
+            status,
+            id,
+            error,
         }
     }
 }
 
-#[derive(Serialize, Debug)]
+impl ImportReportResponse {
+    /// 依行號排序並統計各狀態的列數
+    pub fn new(dry_run: bool, mut rows: Vec<ImportRowReport>) -> Self {
+        rows.sort_by_key(|row| row.line);
+        let count =
+            |status: ImportRowStatus| rows.iter().filter(|row| row.status == status).count() as u64;
+        ImportReportResponse {
+            dry_run,
+            total: rows.len() as u64,
+            imported: count(ImportRowStatus::Imported),
+            valid: count(ImportRowStatus::Valid),
+            already_exists: count(ImportRowStatus::AlreadyExists),
+            invalid: count(ImportRowStatus::Invalid),
+            rows,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, ToSchema)]
 pub struct SuccessResponse {
     pub success: bool,
     pub message: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::ports::AuditTimestamps;
+    use std::time::Duration;
+
+    #[test]
+    fn test_user_response_formats_timestamps() {
+        let at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let user = DomainUser::reconstitute(
+            UserId::from_bytes([1; 16]),
+            UserName::parse("A").unwrap(),
+            2,
+        )
+        .with_audit(AuditTimestamps {
+            created_at: at,
+            updated_at: at,
+        });
+
+        let response = UserResponse::from(user);
+
+        assert_eq!(response.created_at.as_deref(), Some("2023-11-14T22:13:20Z"));
+        assert_eq!(response.deleted_at, None);
+    }
+}
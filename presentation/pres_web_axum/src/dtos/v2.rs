@@ -0,0 +1,53 @@
+//! v2 的回應格式：欄位名稱改為 camelCase，其餘與 v1 相同
+
+use contracts::ports::{Email, User as DomainUser, UserId, UserName};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::response::rfc3339;
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = UserResponseV2)]
+pub struct UserResponse {
+    #[schema(value_type = String, pattern = "^usr_[0-7][0-9a-hjkmnp-tv-z]{25}$", example = "usr_01h455vb4pex5vsknk084sn02q")]
+    pub id: UserId,
+    #[schema(value_type = String)]
+    pub name: UserName,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, format = Email)]
+    pub email: Option<Email>,
+    pub version: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = DateTime)]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = DateTime)]
+    pub updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = DateTime)]
+    pub deleted_at: Option<String>,
+}
+
+impl From<DomainUser> for UserResponse {
+    fn from(domain_user: DomainUser) -> Self {
+        UserResponse {
+            id: domain_user.id,
+            name: domain_user.name,
+            email: domain_user.email,
+            version: domain_user.version,
+            created_at: domain_user.audit.map(|a| rfc3339(a.created_at)),
+            updated_at: domain_user.audit.map(|a| rfc3339(a.updated_at)),
+            deleted_at: domain_user.deleted_at.map(rfc3339),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = UserListResponseV2)]
+pub struct UserListResponse {
+    pub users: Vec<UserResponse>,
+    pub limit: u32,
+    pub offset: u64,
+}
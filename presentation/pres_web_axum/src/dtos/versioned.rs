@@ -0,0 +1,75 @@
+//! 依請求的 API 版本選擇回應 DTO，讓同一個處理器服務所有版本
+
+use contracts::ports::User as DomainUser;
+use serde::Serialize;
+
+use super::{v2, UserListResponse, UserResponse};
+use crate::versioning::ApiVersion;
+
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum UserBody {
+    V1(UserResponse),
+    V2(v2::UserResponse),
+}
+
+impl UserBody {
+    pub fn new(version: ApiVersion, user: DomainUser) -> Self {
+        match version {
+            ApiVersion::V1 => Self::V1(user.into()),
+            ApiVersion::V2 => Self::V2(user.into()),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum UserListBody {
+    V1(UserListResponse),
+    V2(v2::UserListResponse),
+}
+
+impl UserListBody {
+    pub fn new(version: ApiVersion, users: Vec<DomainUser>, limit: u32, offset: u64) -> Self {
+        match version {
+            ApiVersion::V1 => Self::V1(UserListResponse {
+                users: users.into_iter().map(UserResponse::from).collect(),
+                limit,
+                offset,
+            }),
+            ApiVersion::V2 => Self::V2(v2::UserListResponse {
+                users: users.into_iter().map(v2::UserResponse::from).collect(),
+                limit,
+                offset,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::ports::{AuditTimestamps, UserId, UserName};
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_v2_renames_fields_to_camel_case() {
+        let now = SystemTime::now();
+        let user = DomainUser::reconstitute(
+            UserId::from_bytes([1; 16]),
+            UserName::parse("A").unwrap(),
+            1,
+        )
+        .with_audit(AuditTimestamps {
+            created_at: now,
+            updated_at: now,
+        });
+
+        let v1 = serde_json::to_value(UserBody::new(ApiVersion::V1, user.clone())).unwrap();
+        let v2 = serde_json::to_value(UserBody::new(ApiVersion::V2, user)).unwrap();
+
+        assert!(v1.get("created_at").is_some());
+        assert!(v2.get("createdAt").is_some());
+        assert!(v2.get("created_at").is_none());
+    }
+}
@@ -1,6 +1,12 @@
-use axum::{http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::rejection::JsonRejection,
+    http::{header::WWW_AUTHENTICATE, HeaderValue, StatusCode},
+    response::IntoResponse,
+    Json,
+};
 use contracts::{AppError, DomainError};
 use serde::Serialize;
+use utoipa::ToSchema;
 
 #[derive(Debug)]
 pub struct ApiError(pub AppError);
@@ -11,15 +17,29 @@ impl From<AppError> for ApiError {
     }
 }
 
-#[derive(Serialize)]
-struct ErrBody<'a> {
-    code: &'a str,
-    message: String,
+/// 請求主體無法反序列化（包含值物件驗證失敗）時，以統一的錯誤格式回傳 400；
+/// 主體超過大小上限時回傳 413
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            return Self(AppError::PayloadTooLarge(rejection.body_text()));
+        }
+        Self(AppError::Validation(rejection.body_text()))
+    }
+}
+
+/// 錯誤回應的內容；`code` 為穩定的機器可讀代碼
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ErrorBody {
+    #[schema(example = "NOT_FOUND")]
+    pub code: &'static str,
+    pub message: String,
 }
 
-#[derive(Serialize)]
-struct ErrResp<'a> {
-    error: ErrBody<'a>,
+/// 所有錯誤回應共用的外層格式
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ErrorResponse {
+    pub error: ErrorBody,
 }
 
 impl IntoResponse for ApiError {
@@ -35,20 +55,38 @@ impl IntoResponse for ApiError {
             AppError::Domain(DomainError::InvalidOperation { .. }) => {
                 (StatusCode::BAD_REQUEST, "INVALID_OPERATION")
             }
+            AppError::Domain(DomainError::Conflict { .. }) => (StatusCode::CONFLICT, "CONFLICT"),
+            AppError::Domain(DomainError::Forbidden { .. }) => (StatusCode::FORBIDDEN, "FORBIDDEN"),
+            AppError::Domain(DomainError::Unavailable { .. }) => {
+                (StatusCode::SERVICE_UNAVAILABLE, "SERVICE_UNAVAILABLE")
+            }
             AppError::Infrastructure(_) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "INFRASTRUCTURE_ERROR")
             }
             AppError::Application(_) => (StatusCode::INTERNAL_SERVER_ERROR, "APPLICATION_ERROR"),
             AppError::Validation(_) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR"),
+            AppError::PreconditionFailed(_) => {
+                (StatusCode::PRECONDITION_FAILED, "PRECONDITION_FAILED")
+            }
+            AppError::Unauthorized(_) => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED"),
+            AppError::TooManyRequests(_) => (StatusCode::TOO_MANY_REQUESTS, "RATE_LIMITED"),
+            AppError::Timeout(_) => (StatusCode::GATEWAY_TIMEOUT, "TIMEOUT"),
+            AppError::PayloadTooLarge(_) => (StatusCode::PAYLOAD_TOO_LARGE, "PAYLOAD_TOO_LARGE"),
         };
 
-        let body = ErrResp {
-            error: ErrBody {
+        let body = ErrorResponse {
+            error: ErrorBody {
                 code,
                 message: self.0.to_string(),
             },
         };
 
-        (status, Json(body)).into_response()
+        let mut response = (status, Json(body)).into_response();
+        if status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        response
     }
 }
@@ -0,0 +1,114 @@
+//! 以 API 版本與實體版本號作為 ETag，支援 `If-Match` / `If-None-Match` 前置條件
+//!
+//! 不同 API 版本的表示法不同，ETag 帶有 API 版本（例如 `"v2-3"`），快取不會把 v1 的回應當成 v2 的。
+
+use axum::http::{header, HeaderMap, HeaderValue};
+use contracts::AppError;
+
+use crate::{error::ApiError, versioning::ApiVersion};
+
+/// 由 API 版本與實體版本號產生強 ETag，例如 `"v2-3"`
+pub fn etag_for(api: ApiVersion, version: u64) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}-{version}\"", api.as_str()))
+        .expect("ETag is always a valid header value")
+}
+
+/// 解析單一強 ETag，弱 ETag 或格式不符時回傳 `None`
+///
+/// 接受 `"v2-3"` 與舊格式 `"3"`（沒有 API 版本）。
+fn parse_etag(value: &str) -> Option<(Option<ApiVersion>, u64)> {
+    let tag = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    match tag.split_once('-') {
+        Some((api, version)) => Some((Some(ApiVersion::parse(api)?), version.parse().ok()?)),
+        None => Some((None, tag.parse().ok()?)),
+    }
+}
+
+/// 解析 `If-Match` 標頭
+///
+/// - 未提供或為 `*`：回傳 `None`（不做版本檢查）
+/// - 提供強 ETag：回傳預期的版本號；前置條件只比較實體版本，任何 API 版本的 ETag 與舊格式皆可
+/// - 其他格式（含弱 ETag）：依 RFC 9110 以強比較處理，必定不符，回傳 412
+pub fn if_match_version(headers: &HeaderMap) -> Result<Option<u64>, ApiError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value.to_str().unwrap_or_default().trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    parse_etag(value)
+        .map(|(_, version)| Some(version))
+        .ok_or_else(|| {
+            AppError::PreconditionFailed(format!("If-Match value {value} does not match")).into()
+        })
+}
+
+/// `If-None-Match` 是否與目前的表示法相符（相符時可回傳 304）；API 版本必須相同
+pub fn if_none_match_matches(headers: &HeaderMap, api: ApiVersion, version: u64) -> bool {
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.split(',').any(|tag| {
+                let tag = tag.trim();
+                tag == "*" || parse_etag(tag.trim_start_matches("W/")) == Some((Some(api), version))
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(name: header::HeaderName, value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn test_etag_for() {
+        assert_eq!(etag_for(ApiVersion::V2, 3), "\"v2-3\"");
+    }
+
+    #[test]
+    fn test_if_match_absent_or_wildcard() {
+        assert_eq!(if_match_version(&HeaderMap::new()).unwrap(), None);
+        assert_eq!(
+            if_match_version(&headers(header::IF_MATCH, "*")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_if_match_strong_etag() {
+        for value in ["\"v1-42\"", "\"v2-42\"", "\"42\""] {
+            assert_eq!(
+                if_match_version(&headers(header::IF_MATCH, value)).unwrap(),
+                Some(42),
+                "{value}"
+            );
+        }
+        assert!(if_match_version(&headers(header::IF_MATCH, "\"v9-42\"")).is_err());
+    }
+
+    #[test]
+    fn test_if_match_weak_etag_fails() {
+        let result = if_match_version(&headers(header::IF_MATCH, "W/\"42\""));
+        assert!(matches!(
+            result,
+            Err(ApiError(AppError::PreconditionFailed(_)))
+        ));
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let h = headers(header::IF_NONE_MATCH, "\"v1-1\", W/\"v1-2\"");
+        assert!(if_none_match_matches(&h, ApiVersion::V1, 2));
+        assert!(!if_none_match_matches(&h, ApiVersion::V1, 3));
+        // 其他 API 版本的表示法不同
+        assert!(!if_none_match_matches(&h, ApiVersion::V2, 2));
+    }
+}
@@ -0,0 +1,137 @@
+use std::time::{Duration, SystemTime};
+
+use crate::{
+    dtos::{ApiKeyResponse, CreateApiKeyRequest, IssuedApiKeyResponse},
+    error::{ApiError, ErrorResponse},
+    middleware::auth_middleware::CurrentCaller,
+};
+use application::{
+    bus::HasBus,
+    error::AppError,
+    use_cases::{
+        create_api_key::{CreateApiKeyCmd, IssuedApiKey},
+        revoke_api_key::RevokeApiKeyCmd,
+        rotate_api_key::RotateApiKeyCmd,
+    },
+};
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    http::StatusCode,
+    Json,
+};
+use contracts::ports::ApiKeyId;
+
+/// POST /api-keys - 建立 API 金鑰，回應中包含唯一一次可見的明文
+#[utoipa::path(
+    post,
+    path = "/v1/api-keys",
+    operation_id = "createApiKey",
+    tag = "api-keys",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, description = "API key created; the token is only shown once", body = IssuedApiKeyResponse),
+        (status = 400, description = "Invalid name or scopes", body = ErrorResponse),
+        (status = 403, description = "Caller may not manage API keys", body = ErrorResponse),
+    )
+)]
+pub async fn create_api_key_handler<S>(
+    State(app_state): State<S>,
+    CurrentCaller(caller): CurrentCaller,
+    payload: Result<Json<CreateApiKeyRequest>, JsonRejection>,
+) -> Result<(StatusCode, Json<IssuedApiKeyResponse>), ApiError>
+where
+    S: HasBus + Send + Sync + 'static,
+{
+    let Json(payload) = payload?;
+    let expires_at = payload
+        .expires_in_secs
+        .map(|secs| SystemTime::now() + Duration::from_secs(secs));
+
+    let issued = app_state
+        .bus()
+        .send(
+            &caller,
+            CreateApiKeyCmd {
+                name: payload.name,
+                scopes: payload.scopes,
+                expires_at,
+            },
+        )
+        .await?;
+
+    tracing::info!(api_key_id = %issued.key.id, "API key created");
+    Ok((StatusCode::CREATED, Json(issued_response(issued))))
+}
+
+/// POST /api-keys/{id}/rotate - 換發新的秘密，舊秘密立即失效
+#[utoipa::path(
+    post,
+    path = "/v1/api-keys/{id}/rotate",
+    operation_id = "rotateApiKey",
+    tag = "api-keys",
+    params(("id" = String, Path, description = "API key ID", example = "key_01h455vb4pex5vsknk084sn02q")),
+    responses(
+        (status = 200, description = "New token issued; the old one stops working", body = IssuedApiKeyResponse),
+        (status = 404, description = "API key not found", body = ErrorResponse),
+    )
+)]
+pub async fn rotate_api_key_handler<S>(
+    State(app_state): State<S>,
+    CurrentCaller(caller): CurrentCaller,
+    Path(id): Path<String>,
+) -> Result<Json<IssuedApiKeyResponse>, ApiError>
+where
+    S: HasBus + Send + Sync + 'static,
+{
+    let id = parse_api_key_id(&id)?;
+
+    let issued = app_state
+        .bus()
+        .send(&caller, RotateApiKeyCmd { id })
+        .await?;
+
+    tracing::info!(api_key_id = %issued.key.id, "API key rotated");
+    Ok(Json(issued_response(issued)))
+}
+
+/// DELETE /api-keys/{id} - 撤銷 API 金鑰
+#[utoipa::path(
+    delete,
+    path = "/v1/api-keys/{id}",
+    operation_id = "revokeApiKey",
+    tag = "api-keys",
+    params(("id" = String, Path, description = "API key ID", example = "key_01h455vb4pex5vsknk084sn02q")),
+    responses(
+        (status = 204, description = "API key revoked"),
+        (status = 404, description = "API key not found", body = ErrorResponse),
+    )
+)]
+pub async fn revoke_api_key_handler<S>(
+    State(app_state): State<S>,
+    CurrentCaller(caller): CurrentCaller,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError>
+where
+    S: HasBus + Send + Sync + 'static,
+{
+    let id = parse_api_key_id(&id)?;
+
+    app_state
+        .bus()
+        .send(&caller, RevokeApiKeyCmd { id: id.clone() })
+        .await?;
+
+    tracing::info!(api_key_id = %id, "API key revoked");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn issued_response(issued: IssuedApiKey) -> IssuedApiKeyResponse {
+    IssuedApiKeyResponse {
+        key: ApiKeyResponse::from(issued.key),
+        token: issued.token,
+    }
+}
+
+fn parse_api_key_id(id: &str) -> Result<ApiKeyId, ApiError> {
+    ApiKeyId::parse(id).map_err(|e| ApiError(AppError::Domain(e)))
+}
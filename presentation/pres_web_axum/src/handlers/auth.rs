@@ -0,0 +1,24 @@
+use axum::{response::IntoResponse, Json};
+use serde_json::json;
+
+use crate::middleware::auth_middleware::AuthenticatedPrincipal;
+
+/// /me - 回傳目前通過驗證的呼叫者
+#[utoipa::path(
+    get,
+    path = "/v1/me",
+    operation_id = "getMe",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Subject, issuer, scopes and roles of the caller"),
+        (status = 401, description = "Missing or invalid credentials", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn me_handler(principal: AuthenticatedPrincipal) -> impl IntoResponse {
+    Json(json!({
+        "subject": principal.subject,
+        "issuer": principal.issuer,
+        "scopes": principal.scopes,
+        "roles": principal.roles,
+    }))
+}
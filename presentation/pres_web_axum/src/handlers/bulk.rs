@@ -0,0 +1,425 @@
+use application::{
+    bus::HasBus,
+    use_cases::{
+        export_users::{ExportUsersQuery, UserExport},
+        import_users::{ImportUserRow, ImportUsersCmd},
+    },
+};
+use axum::{
+    body::{Body, Bytes},
+    extract::{Query, State},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+    Json,
+};
+use contracts::{AppError, CallerContext};
+use futures_util::{stream, Stream, StreamExt};
+
+use crate::{
+    bulk::{csv_header, encode_users, BulkFormat, RecordSplitter, RowParser},
+    dtos::{
+        ExportUsersParams, ImportReportResponse, ImportRowReport, ImportRowStatus,
+        ImportUsersParams,
+    },
+    error::{ApiError, ErrorResponse},
+    middleware::auth_middleware::CurrentCaller,
+};
+
+/// 每次送往匯入用例的列數；每批各自在一個交易中寫入
+const IMPORT_BATCH_ROWS: usize = 500;
+
+/// 匯出時每次從資料庫讀取的用戶數
+const EXPORT_BATCH_SIZE: u32 = 500;
+
+/// 讀取主體失敗；超過請求保護層設定的主體上限時回傳 413
+fn body_error(error: axum::Error) -> AppError {
+    let too_large =
+        std::iter::successors(Some(&error as &(dyn std::error::Error + 'static)), |e| {
+            e.source()
+        })
+        .any(|e| e.is::<http_body_util::LengthLimitError>());
+    if too_large {
+        AppError::PayloadTooLarge(error.to_string())
+    } else {
+        AppError::Validation(format!("Failed to read request body: {error}"))
+    }
+}
+
+/// POST /users/import - 從串流的 NDJSON 或 CSV 匯入用戶，回報每列結果
+///
+/// 請求主體邊讀邊解析，每累積一批就送出寫入，因此大型檔案不需完整載入記憶體；
+/// 單筆紀錄超過 [`DEFAULT_MAX_RECORD_BYTES`](crate::bulk::DEFAULT_MAX_RECORD_BYTES) 時回傳 413。
+/// 各批獨立提交：檔案中途出現無法處理的錯誤（例如非 UTF-8）時回傳 400，但之前的批次已寫入。
+#[utoipa::path(
+    post,
+    path = "/v1/users/import",
+    operation_id = "importUsers",
+    tag = "users",
+    params(ImportUsersParams),
+    request_body(
+        content(
+            (String = "application/x-ndjson", example = "{\"name\":\"Alice\",\"email\":\"alice@example.com\"}\n"),
+            (String = "text/csv", example = "id,name,email\n,Alice,alice@example.com\n"),
+        ),
+        description = "One user per line. CSV requires a header row with a `name` column; `id` and `email` are optional."
+    ),
+    responses(
+        (status = 200, description = "Per-row results", body = ImportReportResponse),
+        (status = 400, description = "Unsupported Content-Type or unreadable file", body = ErrorResponse),
+        (status = 403, description = "Caller may not create users", body = ErrorResponse),
+        (status = 413, description = "Request body or a single record exceeds the configured limit", body = ErrorResponse),
+    )
+)]
+pub async fn import_users_handler<S>(
+    State(app_state): State<S>,
+    CurrentCaller(caller): CurrentCaller,
+    Query(params): Query<ImportUsersParams>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Json<ImportReportResponse>, ApiError>
+where
+    S: HasBus + Send + Sync + 'static,
+{
+    let format = BulkFormat::from_headers(&headers)?;
+    let mut importer = Importer {
+        state: &app_state,
+        caller: &caller,
+        dry_run: params.dry_run,
+        parser: RowParser::default(),
+        pending: Vec::new(),
+        reports: Vec::new(),
+    };
+    let mut splitter = RecordSplitter::new(format);
+
+    let mut chunks = body.into_data_stream();
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.map_err(body_error)?;
+        for record in splitter.push(&chunk)? {
+            importer.push(format, record)?;
+        }
+        importer.flush_full().await?;
+    }
+    if let Some(record) = splitter.finish()? {
+        importer.push(format, record)?;
+    }
+    importer.flush().await?;
+
+    // 無法解析的列立即回報、有效的列在批次送出後才回報；`new` 依行號排回原本的順序
+    let report = ImportReportResponse::new(params.dry_run, importer.reports);
+    tracing::info!(
+        dry_run = report.dry_run,
+        total = report.total,
+        imported = report.imported,
+        invalid = report.invalid,
+        "Users imported"
+    );
+    Ok(Json(report))
+}
+
+/// 累積解析後的資料列並分批送出
+struct Importer<'a, S> {
+    state: &'a S,
+    caller: &'a CallerContext,
+    dry_run: bool,
+    parser: RowParser,
+    pending: Vec<ImportUserRow>,
+    reports: Vec<ImportRowReport>,
+}
+
+impl<S: HasBus> Importer<'_, S> {
+    fn push(&mut self, format: BulkFormat, record: crate::bulk::RawRecord) -> Result<(), AppError> {
+        let line = record.line;
+        match self.parser.parse(format, record)? {
+            Some(Ok(row)) => self.pending.push(row),
+            // 無法解析的列不送往用例，直接回報
+            Some(Err(error)) => self.reports.push(ImportRowReport {
+                line,
+                status: ImportRowStatus::Invalid,
+                id: None,
+                error: Some(error),
+            }),
+            None => {}
+        }
+        Ok(())
+    }
+
+    async fn flush_full(&mut self) -> Result<(), AppError> {
+        while self.pending.len() >= IMPORT_BATCH_ROWS {
+            let rest = self.pending.split_off(IMPORT_BATCH_ROWS);
+            let batch = std::mem::replace(&mut self.pending, rest);
+            self.send(batch).await?;
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<(), AppError> {
+        self.flush_full().await?;
+        let batch = std::mem::take(&mut self.pending);
+        if !batch.is_empty() {
+            self.send(batch).await?;
+        }
+        Ok(())
+    }
+
+    async fn send(&mut self, rows: Vec<ImportUserRow>) -> Result<(), AppError> {
+        let results = self
+            .state
+            .bus()
+            .send(
+                self.caller,
+                ImportUsersCmd {
+                    rows,
+                    dry_run: self.dry_run,
+                },
+            )
+            .await?;
+        self.reports
+            .extend(results.into_iter().map(ImportRowReport::from));
+        Ok(())
+    }
+}
+
+/// GET /users/export - 依 ID 順序串流匯出所有用戶
+///
+/// 每次只從資料庫讀取一批並立即寫出，不會將整個資料表載入記憶體。
+/// 匯出的欄位與 v1 用戶回應相同，且可以直接再匯入。
+#[utoipa::path(
+    get,
+    path = "/v1/users/export",
+    operation_id = "exportUsers",
+    tag = "users",
+    params(ExportUsersParams),
+    responses(
+        (status = 200, description = "All users, one per line",
+            content(
+                (String = "application/x-ndjson"),
+                (String = "text/csv"),
+            )),
+        (status = 403, description = "Caller may not list users", body = ErrorResponse),
+    )
+)]
+pub async fn export_users_handler<S>(
+    State(app_state): State<S>,
+    CurrentCaller(caller): CurrentCaller,
+    Query(params): Query<ExportUsersParams>,
+) -> Result<Response, ApiError>
+where
+    S: HasBus + Send + Sync + 'static,
+{
+    let export = app_state
+        .bus()
+        .send(
+            &caller,
+            ExportUsersQuery {
+                include_deleted: params.include_deleted,
+                batch_size: EXPORT_BATCH_SIZE,
+            },
+        )
+        .await?;
+
+    let format = params.format;
+    let header = match format {
+        BulkFormat::Ndjson => None,
+        BulkFormat::Csv => Some(Ok(Bytes::from(csv_header()))),
+    };
+    let body = stream::iter(header).chain(export_stream(format, export));
+
+    let filename = match format {
+        BulkFormat::Ndjson => "users.ndjson",
+        BulkFormat::Csv => "users.csv",
+    };
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        Body::from_stream(body),
+    )
+        .into_response())
+}
+
+/// 逐批讀取並編碼；讀取失敗時中斷串流，用戶端會收到不完整的回應
+fn export_stream(
+    format: BulkFormat,
+    export: UserExport,
+) -> impl Stream<Item = Result<Bytes, AppError>> {
+    stream::unfold(Some(export), move |export| async move {
+        let mut export = export?;
+        match export.next_batch().await {
+            Ok(Some(users)) => {
+                let chunk = encode_users(format, users).map(Bytes::from);
+                Some((chunk, Some(export)))
+            }
+            Ok(None) => None,
+            Err(e) => {
+                tracing::error!(error = %e, "User export aborted");
+                Some((Err(AppError::Domain(e)), None))
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application::{
+        bus::{Bus, Handler, Message},
+        use_cases::{
+            export_users::ExportUsersSvc,
+            import_users::{ImportOutcome, ImportRowResult},
+        },
+    };
+    use async_trait::async_trait;
+    use axum::{
+        body::to_bytes,
+        http::{Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use contracts::{
+        ports::{User, UserId, UserName},
+        DomainError, UserRepository,
+    };
+    use infra_memory::InMemoryUserRepository;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[derive(Clone)]
+    struct TestState(Arc<Bus>);
+
+    impl HasBus for TestState {
+        fn bus(&self) -> &Bus {
+            &self.0
+        }
+    }
+
+    fn state<M: Message>(handler: impl Handler<M> + 'static) -> TestState {
+        TestState(Arc::new(Bus::new().with_handler::<M>(Arc::new(handler))))
+    }
+
+    /// 名稱為 "bad" 的列視為不合法，其餘依 dry run 回報
+    struct ReportsRows;
+
+    #[async_trait]
+    impl Handler<ImportUsersCmd> for ReportsRows {
+        async fn handle(
+            &self,
+            _ctx: &CallerContext,
+            cmd: ImportUsersCmd,
+        ) -> Result<Vec<ImportRowResult>, DomainError> {
+            Ok(cmd
+                .rows
+                .into_iter()
+                .map(|row| ImportRowResult {
+                    line: row.line,
+                    outcome: match row.name.as_str() {
+                        "bad" => ImportOutcome::Invalid("bad name".to_string()),
+                        _ if cmd.dry_run => {
+                            ImportOutcome::Valid(UserId::from_u128(row.line as u128))
+                        }
+                        _ => ImportOutcome::Imported(UserId::from_u128(row.line as u128)),
+                    },
+                })
+                .collect())
+        }
+    }
+
+    async fn import(content_type: &str, uri: &str, body: &'static str) -> (StatusCode, String) {
+        let app = Router::new()
+            .route("/users/import", post(import_users_handler::<TestState>))
+            .with_state(state(ReportsRows));
+        let response = app
+            .oneshot(
+                Request::post(uri)
+                    .header(header::CONTENT_TYPE, content_type)
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_import_reports_parse_and_validation_errors_by_line() {
+        let (status, body) = import(
+            "application/x-ndjson",
+            "/users/import?dry_run=true",
+            "{\"name\":\"Ada\"}\nnot json\n{\"name\":\"bad\"}",
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let report: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(report["dry_run"], true);
+        assert_eq!(report["valid"], 1);
+        assert_eq!(report["invalid"], 2);
+        let statuses: Vec<_> = report["rows"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| (row["line"].as_u64().unwrap(), row["status"].clone()))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (1, "valid".into()),
+                (2, "invalid".into()),
+                (3, "invalid".into())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_requires_a_supported_content_type() {
+        let (status, _) = import("application/json", "/users/import", "[]").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_export_streams_all_batches_as_csv() {
+        let repo = InMemoryUserRepository::new();
+        for id in 1..=(EXPORT_BATCH_SIZE as u128 + 1) {
+            repo.save(&User::new(
+                UserId::from_u128(id),
+                UserName::parse(format!("User {id}")).unwrap(),
+                None,
+            ))
+            .await
+            .unwrap();
+        }
+        let app = Router::new()
+            .route("/users/export", get(export_users_handler::<TestState>))
+            .with_state(state(ExportUsersSvc::new(
+                Arc::new(repo),
+                Arc::new(application::authorization::AllowAllPolicy),
+            )));
+
+        let response = app
+            .oneshot(
+                Request::get("/users/export?format=csv")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        let lines: Vec<_> = body.lines().collect();
+        assert_eq!(lines.len(), EXPORT_BATCH_SIZE as usize + 2);
+        assert!(lines[0].starts_with("id,name,email,version"));
+        assert!(lines[1].contains("User 1,"));
+    }
+}
@@ -0,0 +1,210 @@
+use std::{convert::Infallible, time::Duration};
+
+use application::{
+    bus::HasBus,
+    use_cases::subscribe_user_events::SubscribeUserEventsQuery,
+    user_events::{EventId, StreamedEvent, UserEventSubscription},
+};
+use axum::{
+    extract::State,
+    http::{HeaderMap, HeaderName},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use contracts::AppError;
+use futures_util::{stream, Stream, StreamExt};
+use serde_json::json;
+
+use crate::{dtos::response::rfc3339, error::ApiError, middleware::auth_middleware::CurrentCaller};
+
+/// 客戶端重新連線時帶入的最後事件 ID（EventSource 會自動送出）
+const LAST_EVENT_ID: HeaderName = HeaderName::from_static("last-event-id");
+
+/// 無法從 `Last-Event-ID` 續傳時最先送出的事件
+const RESET_EVENT: &str = "reset";
+
+/// 沒有事件時送出註解，避免代理伺服器關閉閒置連線
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// GET /events/users - 以 Server-Sent Events 推送用戶變更
+///
+/// 事件 ID 為 `{epoch}-{seq}`，序號在本實例內遞增；帶 `Last-Event-ID` 重新連線時從重播緩衝補送之後的事件。
+/// ID 屬於其他實例或重新啟動前的 epoch，或之後的事件已被擠出緩衝時，先送出 `reset` 事件，
+/// 客戶端應重新載入用戶列表。訂閱者跟不上時伺服器結束串流，客戶端重新連線即可續傳。
+#[utoipa::path(
+    get,
+    path = "/events/users",
+    operation_id = "streamUserEvents",
+    tag = "users",
+    params(
+        ("Last-Event-ID" = Option<String>, Header, description = "ID of the last event received before reconnecting"),
+    ),
+    responses(
+        (status = 200, description = "Stream of `user.created`, `user.renamed`, `user.email_changed`, `user.deleted` and `user.restored` events, preceded by a `reset` event when the stream cannot resume from Last-Event-ID",
+            content_type = "text/event-stream", body = String),
+        (status = 400, description = "Invalid Last-Event-ID", body = crate::error::ErrorResponse),
+        (status = 403, description = "Caller may not list users", body = crate::error::ErrorResponse),
+    )
+)]
+pub async fn user_events_handler<S>(
+    State(app_state): State<S>,
+    CurrentCaller(caller): CurrentCaller,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError>
+where
+    S: HasBus + Send + Sync + 'static,
+{
+    let last_event_id = headers
+        .get(LAST_EVENT_ID)
+        .map(|value| {
+            value
+                .to_str()
+                .ok()
+                .and_then(|id| id.trim().parse::<EventId>().ok())
+                .ok_or_else(|| AppError::Validation("Invalid Last-Event-ID header".to_string()))
+        })
+        .transpose()?;
+
+    let subscription = app_state
+        .bus()
+        .send(&caller, SubscribeUserEventsQuery { last_event_id })
+        .await?;
+
+    Ok(Sse::new(event_stream(subscription))
+        .keep_alive(KeepAlive::new().interval(KEEP_ALIVE_INTERVAL)))
+}
+
+fn event_stream(
+    subscription: UserEventSubscription,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    let reset = subscription.resync().map(|head| {
+        Ok(Event::default()
+            .id(head.to_string())
+            .event(RESET_EVENT)
+            .data(json!({ "reason": "Last-Event-ID is no longer available" }).to_string()))
+    });
+    let events = stream::unfold(subscription, |mut subscription| async move {
+        let event = subscription.next().await?;
+        Some((Ok(sse_event(&event)), subscription))
+    });
+    stream::iter(reset).chain(events)
+}
+
+fn sse_event(event: &StreamedEvent) -> Event {
+    let message = &event.message;
+    Event::default()
+        .id(event.id.to_string())
+        .event(&message.event_type)
+        .data(
+            json!({
+                "user_id": message.aggregate_id,
+                "occurred_at": rfc3339(message.occurred_at),
+                "data": message.payload,
+            })
+            .to_string(),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use application::{
+        authorization::AllowAllPolicy, bus::Bus,
+        use_cases::subscribe_user_events::SubscribeUserEventsSvc, user_events::UserEventStream,
+    };
+    use axum::{body::Body, http::Request, routing::get, Router};
+    use contracts::{DomainEvent, TenantId, UserId};
+    use tower::ServiceExt;
+
+    #[derive(Clone)]
+    struct TestState(Arc<Bus>);
+
+    impl HasBus for TestState {
+        fn bus(&self) -> &Bus {
+            &self.0
+        }
+    }
+
+    fn deleted(id: u8) -> DomainEvent {
+        DomainEvent::UserDeleted {
+            user_id: UserId::from_bytes([id; 16]),
+        }
+    }
+
+    async fn first_frame(events: Arc<UserEventStream>, last_event_id: &str) -> String {
+        let bus = Bus::new().with_handler(Arc::new(SubscribeUserEventsSvc::new(
+            events,
+            Arc::new(AllowAllPolicy),
+        )));
+        let app = Router::new()
+            .route("/events/users", get(user_events_handler::<TestState>))
+            .with_state(TestState(Arc::new(bus)));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/events/users")
+                    .header(LAST_EVENT_ID, last_event_id)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+        let mut body = response.into_body().into_data_stream();
+        let frame = body.next().await.unwrap().unwrap();
+        String::from_utf8(frame.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_resumes_after_last_event_id() {
+        let events = Arc::new(UserEventStream::default());
+        let mut probe = events.subscribe(&TenantId::default(), None);
+        events.publish(&TenantId::default(), &[deleted(1), deleted(2)]);
+        let first = probe.next().await.unwrap().id;
+        let second = probe.next().await.unwrap().id;
+
+        let frame = first_frame(events, &first.to_string()).await;
+        assert!(frame.contains("event: user.deleted\n"), "{frame}");
+        assert!(frame.contains(&format!("id: {second}\n")), "{frame}");
+        assert!(frame.contains(&UserId::from_bytes([2; 16]).to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_last_event_id_sends_reset() {
+        let events = Arc::new(UserEventStream::default());
+        let mut probe = events.subscribe(&TenantId::default(), None);
+        events.publish(&TenantId::default(), &[deleted(1)]);
+        let head = probe.next().await.unwrap().id;
+
+        // 另一個 epoch 的 ID（例如重新啟動前）
+        let stale = EventId {
+            epoch: head.epoch.wrapping_add(1),
+            seq: 1,
+        };
+        let frame = first_frame(events, &stale.to_string()).await;
+        assert!(frame.contains("event: reset\n"), "{frame}");
+        assert!(frame.contains(&format!("id: {head}\n")), "{frame}");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_malformed_last_event_id() {
+        let bus = Bus::new().with_handler(Arc::new(SubscribeUserEventsSvc::new(
+            Arc::new(UserEventStream::default()),
+            Arc::new(AllowAllPolicy),
+        )));
+        let mut headers = HeaderMap::new();
+        headers.insert(LAST_EVENT_ID, "abc".parse().unwrap());
+
+        let result = user_events_handler(
+            State(TestState(Arc::new(bus))),
+            CurrentCaller(contracts::CallerContext::system()),
+            headers,
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiError(AppError::Validation(_)))));
+    }
+}
@@ -2,11 +2,27 @@ use axum::{http::StatusCode, response::IntoResponse, Json};
 use serde_json::json;
 
 /// /healthz/live - Liveness Probe
+#[utoipa::path(
+    get,
+    path = "/healthz/live",
+    operation_id = "live",
+    tag = "health",
+    security(()),
+    responses((status = 200, description = "Process is alive"))
+)]
 pub async fn live_handler() -> impl IntoResponse {
     (StatusCode::OK, Json(json!({ "status": "ok" })))
 }
 
 /// /healthz/ready - Readiness Probe
+#[utoipa::path(
+    get,
+    path = "/healthz/ready",
+    operation_id = "ready",
+    tag = "health",
+    security(()),
+    responses((status = 200, description = "Ready to serve traffic"))
+)]
 pub async fn ready_handler() -> impl IntoResponse {
     (StatusCode::OK, Json(json!({ "status": "ready" })))
 }
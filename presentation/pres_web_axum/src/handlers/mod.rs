@@ -1,8 +1,16 @@
+pub mod api_key;
+pub mod auth;
+pub mod bulk;
+pub mod events;
 pub mod health;
 pub mod main;
 pub mod user;
 
 // Re-export all handlers
+pub use api_key::*;
+pub use auth::*;
+pub use bulk::*;
+pub use events::*;
 pub use health::*;
 pub use main::*;
 pub use user::*;
@@ -1,65 +1,524 @@
 use crate::{
-    dtos::{CreateUserRequest, UserResponse},
-    error::ApiError,
+    dtos::{
+        CreateUserRequest, ListUsersParams, UpdateUserRequest, UserBody, UserListBody,
+        UserListResponse, UserResponse,
+    },
+    error::{ApiError, ErrorResponse},
+    etag::{etag_for, if_match_version, if_none_match_matches},
+    middleware::auth_middleware::CurrentCaller,
+    versioning::ApiVersion,
 };
-use application::{error::AppError, use_cases::create_user::CreateUserCmd};
-use axum::{extract::State, Json};
+use application::{
+    bus::HasBus,
+    error::AppError,
+    use_cases::{
+        create_user::CreateUserCmd, delete_user::DeleteUserCmd, get_user::GetUserQuery,
+        list_users::ListUsersQuery, restore_user::RestoreUserCmd, update_user::UpdateUserCmd,
+    },
+};
+use axum::{
+    extract::{rejection::JsonRejection, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use contracts::ports::{DomainError, UserId};
 
+/// POST /users - 建立用戶
+#[utoipa::path(
+    post,
+    path = "/v1/users",
+    operation_id = "createUser",
+    tag = "users",
+    request_body = CreateUserRequest,
+    responses(
+        (status = 200, description = "User created", body = UserResponse),
+        (status = 400, description = "Invalid name or email", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Caller may not create users", body = ErrorResponse),
+    )
+)]
 pub async fn create_user_handler<S>(
     State(app_state): State<S>,
-    Json(payload): Json<CreateUserRequest>,
-) -> Result<Json<UserResponse>, ApiError>
+    CurrentCaller(caller): CurrentCaller,
+    version: ApiVersion,
+    payload: Result<Json<CreateUserRequest>, JsonRejection>,
+) -> Result<Json<UserBody>, ApiError>
 where
-    S: application::use_cases::create_user::HasCreateUserUc + Send + Sync + 'static,
+    S: HasBus + Send + Sync + 'static,
 {
+    let Json(payload) = payload?;
     tracing::info!("Creating user with name: {}", payload.name);
 
     let user = app_state
-        .create_user_uc()
-        .exec(CreateUserCmd { name: payload.name })
-        .await
-        .map_err(AppError::Domain)?;
+        .bus()
+        .send(
+            &caller,
+            CreateUserCmd {
+                name: payload.name,
+                email: payload.email,
+            },
+        )
+        .await?;
 
     tracing::info!("User created with ID: {}", user.id);
-    Ok(Json(UserResponse::from(user)))
+    Ok(Json(UserBody::new(version, user)))
+}
+
+/// GET /users/{id} - 回傳用戶並附上 `ETag`；`If-None-Match` 相符時回傳 304
+#[utoipa::path(
+    get,
+    path = "/v1/users/{id}",
+    operation_id = "getUser",
+    tag = "users",
+    params(
+        ("id" = String, Path, description = "User ID", example = "usr_01h455vb4pex5vsknk084sn02q"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag from a previous response"),
+    ),
+    responses(
+        (status = 200, description = "User found", body = UserResponse,
+            headers(("ETag" = String, description = "API and entity version of the user, e.g. \"v2-3\""))),
+        (status = 304, description = "User unchanged since the given ETag"),
+        (status = 400, description = "Invalid user ID", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_user_handler<S>(
+    State(app_state): State<S>,
+    CurrentCaller(caller): CurrentCaller,
+    version: ApiVersion,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError>
+where
+    S: HasBus + Send + Sync + 'static,
+{
+    let user = app_state
+        .bus()
+        .send(
+            &caller,
+            GetUserQuery {
+                id: parse_user_id(&id)?,
+            },
+        )
+        .await?;
+
+    let etag = etag_for(version, user.version);
+    if if_none_match_matches(&headers, version, user.version) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
+
+    Ok(([(header::ETAG, etag)], Json(UserBody::new(version, user))).into_response())
+}
+
+/// PUT /users/{id} - 更新用戶；帶 `If-Match` 時版本不符回傳 412，否則並發衝突回傳 409
+#[utoipa::path(
+    put,
+    path = "/v1/users/{id}",
+    operation_id = "updateUser",
+    tag = "users",
+    params(
+        ("id" = String, Path, description = "User ID", example = "usr_01h455vb4pex5vsknk084sn02q"),
+        ("If-Match" = Option<String>, Header, description = "Expected ETag of the user"),
+    ),
+    request_body = UpdateUserRequest,
+    responses(
+        (status = 200, description = "User updated", body = UserResponse,
+            headers(("ETag" = String, description = "API and new entity version of the user, e.g. \"v2-4\""))),
+        (status = 400, description = "Invalid ID, name or email", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 409, description = "Concurrent modification", body = ErrorResponse),
+        (status = 412, description = "If-Match does not match the current version", body = ErrorResponse),
+    )
+)]
+pub async fn update_user_handler<S>(
+    State(app_state): State<S>,
+    CurrentCaller(caller): CurrentCaller,
+    version: ApiVersion,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    payload: Result<Json<UpdateUserRequest>, JsonRejection>,
+) -> Result<Response, ApiError>
+where
+    S: HasBus + Send + Sync + 'static,
+{
+    let id = parse_user_id(&id)?;
+    let expected_version = if_match_version(&headers)?;
+    let Json(payload) = payload?;
+
+    let user = app_state
+        .bus()
+        .send(
+            &caller,
+            UpdateUserCmd {
+                id,
+                name: payload.name,
+                email: payload.email,
+                expected_version,
+            },
+        )
+        .await
+        .map_err(|e| precondition_error(e, expected_version))?;
+
+    tracing::info!(user_id = %user.id, version = user.version, "User updated");
+    Ok((
+        [(header::ETAG, etag_for(version, user.version))],
+        Json(UserBody::new(version, user)),
+    )
+        .into_response())
+}
+
+/// GET /users - 依建立時間分頁列出用戶，預設不含已刪除者
+#[utoipa::path(
+    get,
+    path = "/v1/users",
+    operation_id = "listUsers",
+    tag = "users",
+    params(ListUsersParams),
+    responses(
+        (status = 200, description = "A page of users", body = UserListResponse),
+        (status = 403, description = "Caller may not list users", body = ErrorResponse),
+    )
+)]
+pub async fn list_users_handler<S>(
+    State(app_state): State<S>,
+    CurrentCaller(caller): CurrentCaller,
+    version: ApiVersion,
+    Query(params): Query<ListUsersParams>,
+) -> Result<Json<UserListBody>, ApiError>
+where
+    S: HasBus + Send + Sync + 'static,
+{
+    let limit = params
+        .limit
+        .clamp(1, application::use_cases::list_users::MAX_PAGE_SIZE);
+    let users = app_state
+        .bus()
+        .send(
+            &caller,
+            ListUsersQuery {
+                limit,
+                offset: params.offset,
+                include_deleted: params.include_deleted,
+            },
+        )
+        .await?;
+
+    Ok(Json(UserListBody::new(
+        version,
+        users,
+        limit,
+        params.offset,
+    )))
+}
+
+/// DELETE /users/{id} - 軟刪除用戶，保留期限內可還原
+#[utoipa::path(
+    delete,
+    path = "/v1/users/{id}",
+    operation_id = "deleteUser",
+    tag = "users",
+    params(
+        ("id" = String, Path, description = "User ID", example = "usr_01h455vb4pex5vsknk084sn02q"),
+        ("If-Match" = Option<String>, Header, description = "Expected ETag of the user"),
+    ),
+    responses(
+        (status = 204, description = "User soft-deleted"),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 412, description = "If-Match does not match the current version", body = ErrorResponse),
+    )
+)]
+pub async fn delete_user_handler<S>(
+    State(app_state): State<S>,
+    CurrentCaller(caller): CurrentCaller,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<StatusCode, ApiError>
+where
+    S: HasBus + Send + Sync + 'static,
+{
+    let id = parse_user_id(&id)?;
+    let expected_version = if_match_version(&headers)?;
+
+    app_state
+        .bus()
+        .send(
+            &caller,
+            DeleteUserCmd {
+                id: id.clone(),
+                expected_version,
+            },
+        )
+        .await
+        .map_err(|e| precondition_error(e, expected_version))?;
+
+    tracing::info!(user_id = %id, "User soft-deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /users/{id}/restore - 還原尚未被清除的軟刪除用戶
+#[utoipa::path(
+    post,
+    path = "/v1/users/{id}/restore",
+    operation_id = "restoreUser",
+    tag = "users",
+    params(
+        ("id" = String, Path, description = "User ID", example = "usr_01h455vb4pex5vsknk084sn02q"),
+        ("If-Match" = Option<String>, Header, description = "Expected ETag of the user"),
+    ),
+    responses(
+        (status = 200, description = "User restored", body = UserResponse,
+            headers(("ETag" = String, description = "API and new entity version of the user, e.g. \"v2-4\""))),
+        (status = 404, description = "User not found or already purged", body = ErrorResponse),
+        (status = 412, description = "If-Match does not match the current version", body = ErrorResponse),
+    )
+)]
+pub async fn restore_user_handler<S>(
+    State(app_state): State<S>,
+    CurrentCaller(caller): CurrentCaller,
+    version: ApiVersion,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError>
+where
+    S: HasBus + Send + Sync + 'static,
+{
+    let expected_version = if_match_version(&headers)?;
+
+    let user = app_state
+        .bus()
+        .send(
+            &caller,
+            RestoreUserCmd {
+                id: parse_user_id(&id)?,
+                expected_version,
+            },
+        )
+        .await
+        .map_err(|e| precondition_error(e, expected_version))?;
+
+    tracing::info!(user_id = %user.id, "User restored");
+    Ok((
+        [(header::ETAG, etag_for(version, user.version))],
+        Json(UserBody::new(version, user)),
+    )
+        .into_response())
+}
+
+/// 路徑中的 ID 在進入應用層前完成驗證
+fn parse_user_id(id: &str) -> Result<UserId, ApiError> {
+    UserId::parse(id).map_err(|e| ApiError(AppError::Domain(e)))
+}
+
+/// 帶 `If-Match` 時，版本衝突代表前置條件不成立（412），否則維持 409
+fn precondition_error(error: AppError, expected_version: Option<u64>) -> AppError {
+    match error {
+        AppError::Domain(DomainError::Conflict { message }) if expected_version.is_some() => {
+            AppError::PreconditionFailed(message)
+        }
+        other => other,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::dtos::CreateUserRequest;
-    use application::use_cases::create_user::{CreateUserUseCase, HasCreateUserUc};
+    use application::bus::{Bus, Handler, Message};
     use async_trait::async_trait;
-    use contracts::ports::{DomainError, User, UserId};
+    use axum::http::HeaderValue;
+    use axum::{body::Body, http::Request, routing::post, Router};
+    use contracts::{
+        ports::{DomainError, User, UserId, UserName},
+        CallerContext,
+    };
     use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[derive(Clone)]
+    struct TestState(Arc<Bus>);
+
+    impl HasBus for TestState {
+        fn bus(&self) -> &Bus {
+            &self.0
+        }
+    }
+
+    fn state<M: Message>(handler: impl Handler<M> + 'static) -> TestState {
+        TestState(Arc::new(Bus::new().with_handler::<M>(Arc::new(handler))))
+    }
+
+    struct CreatesTestUser;
+
+    #[async_trait]
+    impl Handler<CreateUserCmd> for CreatesTestUser {
+        async fn handle(
+            &self,
+            _ctx: &CallerContext,
+            _cmd: CreateUserCmd,
+        ) -> Result<User, DomainError> {
+            let id = UserId::from_bytes([7; 16]);
+            Ok(User::new(id, UserName::parse("Test User").unwrap(), None))
+        }
+    }
+
+    /// 呼叫者沒有權限的建立用例
+    struct ForbidsCreate;
+
+    #[async_trait]
+    impl Handler<CreateUserCmd> for ForbidsCreate {
+        async fn handle(
+            &self,
+            _ctx: &CallerContext,
+            _cmd: CreateUserCmd,
+        ) -> Result<User, DomainError> {
+            Err(DomainError::Forbidden {
+                message: "users:create".to_string(),
+            })
+        }
+    }
 
-    struct MockAppState;
+    /// 目前版本固定為 1 的更新用例
+    struct Versioned;
 
     #[async_trait]
-    impl CreateUserUseCase for MockAppState {
-        async fn exec(&self, _cmd: CreateUserCmd) -> Result<User, DomainError> {
-            let id = UserId::from_string("test-id".to_string());
-            User::new(id, "Test User".to_string())
+    impl Handler<UpdateUserCmd> for Versioned {
+        async fn handle(
+            &self,
+            _ctx: &CallerContext,
+            cmd: UpdateUserCmd,
+        ) -> Result<User, DomainError> {
+            let mut user = User::reconstitute(cmd.id, UserName::parse("Old").unwrap(), 1);
+            if let Some(expected) = cmd.expected_version {
+                user.ensure_version(expected)?;
+            }
+            user.update_name(cmd.name)?;
+            let now = std::time::SystemTime::now();
+            user.mark_persisted(contracts::ports::AuditTimestamps {
+                created_at: now,
+                updated_at: now,
+            });
+            Ok(user)
         }
     }
 
-    impl HasCreateUserUc for MockAppState {
-        fn create_user_uc(&self) -> Arc<dyn CreateUserUseCase> {
-            Arc::new(MockAppState)
+    async fn update_with_if_match(if_match: Option<&'static str>) -> Result<Response, ApiError> {
+        let mut headers = HeaderMap::new();
+        if let Some(value) = if_match {
+            headers.insert(header::IF_MATCH, HeaderValue::from_static(value));
         }
+        update_user_handler(
+            State(state(Versioned)),
+            CurrentCaller(CallerContext::system()),
+            ApiVersion::V1,
+            Path(UserId::from_bytes([1; 16]).to_string()),
+            headers,
+            Ok(Json(UpdateUserRequest {
+                name: UserName::parse("New").unwrap(),
+                email: None,
+            })),
+        )
+        .await
     }
 
     #[tokio::test]
     async fn test_create_user_handler_success() {
-        let app_state = MockAppState;
+        let app_state = state(CreatesTestUser);
         let request = CreateUserRequest {
-            name: "John Doe".to_string(),
+            name: UserName::parse("John Doe").unwrap(),
+            email: None,
+        };
+
+        let result = create_user_handler(
+            axum::extract::State(app_state),
+            CurrentCaller(CallerContext::system()),
+            ApiVersion::V1,
+            Ok(Json(request)),
+        )
+        .await;
+
+        let Ok(Json(UserBody::V1(user))) = result else {
+            panic!("expected a v1 user response");
         };
+        assert_eq!(user.name.as_str(), "Test User");
+    }
 
-        let result = create_user_handler(axum::extract::State(app_state), Json(request)).await;
+    #[tokio::test]
+    async fn test_update_user_with_matching_etag() {
+        let response = update_with_if_match(Some("\"v1-1\"")).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ETAG], "\"v1-2\"");
+    }
+
+    #[tokio::test]
+    async fn test_update_user_with_stale_etag_is_precondition_failed() {
+        let response = update_with_if_match(Some("\"0\"")).await.into_response();
+
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    }
+
+    #[tokio::test]
+    async fn test_update_user_without_if_match() {
+        let response = update_with_if_match(None).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_create_user_with_invalid_name_is_bad_request() {
+        let app = Router::new()
+            .route("/users", post(create_user_handler::<TestState>))
+            .with_state(state(CreatesTestUser));
+
+        let response = app
+            .oneshot(
+                Request::post("/users")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(r#"{"name":"   "}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_forbidden_caller_gets_403() {
+        let app = Router::new()
+            .route("/users", post(create_user_handler::<TestState>))
+            .with_state(state(ForbidsCreate));
+
+        let response = app
+            .oneshot(
+                Request::post("/users")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(r#"{"name":"Alice"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_update_user_with_invalid_id_is_bad_request() {
+        let response = update_user_handler(
+            State(state(Versioned)),
+            CurrentCaller(CallerContext::system()),
+            ApiVersion::V1,
+            Path("not-a-uuid".to_string()),
+            HeaderMap::new(),
+            Ok(Json(UpdateUserRequest {
+                name: UserName::parse("New").unwrap(),
+                email: None,
+            })),
+        )
+        .await
+        .into_response();
 
-        assert!(result.is_ok());
-        let response = result.unwrap();
-        assert_eq!(response.0.name, "Test User");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 }
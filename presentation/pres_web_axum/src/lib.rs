@@ -1,7 +1,11 @@
+pub mod bulk;
 pub mod dtos;
 pub mod error;
+pub mod etag;
 pub mod handlers;
 pub mod middleware;
+pub mod openapi;
+pub mod versioning;
 
 // Re-export for backward compatibility
 pub use handlers::*;
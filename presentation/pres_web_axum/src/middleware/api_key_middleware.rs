@@ -0,0 +1,182 @@
+// presentation/pres_web_axum/src/middleware/api_key_middleware.rs
+
+//! API 金鑰驗證
+//!
+//! 將 `Authorization: ApiKey <token>` 解析為 [`AuthenticatedPrincipal`]，供機器對機器呼叫使用。
+//! 與 JWT 驗證相同，沒有帶 API 金鑰的請求會直接放行。
+
+use application::{bus::HasBus, use_cases::authenticate_api_key::AuthenticateApiKeyQuery};
+use axum::{
+    extract::{Request, State},
+    http::header::AUTHORIZATION,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use contracts::{ApiKeyId, AppError, CallerContext, DomainError, TenantId};
+use serde_json::{Map, Value};
+
+use crate::{error::ApiError, middleware::auth_middleware::AuthenticatedPrincipal};
+
+/// `Authorization` 標頭中 API 金鑰的驗證方案名稱
+const API_KEY_SCHEME: &str = "ApiKey ";
+
+/// 以 API 金鑰通過驗證的請求，在請求擴展中帶有金鑰 ID 與所屬租戶（供限流與租戶解析使用）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedApiKey {
+    pub id: ApiKeyId,
+    pub tenant: TenantId,
+}
+
+/// 驗證 API 金鑰，成功時將 [`AuthenticatedPrincipal`] 與 [`AuthenticatedApiKey`] 放入請求擴展
+pub async fn api_key_auth_middleware<S>(
+    State(state): State<S>,
+    mut req: Request,
+    next: Next,
+) -> Response
+where
+    S: HasBus + Clone + Send + Sync + 'static,
+{
+    let token = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix(API_KEY_SCHEME))
+        .map(str::trim)
+        .map(str::to_string);
+
+    if let Some(token) = token {
+        let key = match state
+            .bus()
+            .send(
+                &CallerContext::anonymous(),
+                AuthenticateApiKeyQuery { token },
+            )
+            .await
+        {
+            Ok(key) => key,
+            Err(AppError::Domain(DomainError::NotFound { .. })) => {
+                return ApiError(AppError::Unauthorized("Invalid API key".to_string()))
+                    .into_response()
+            }
+            Err(e) => return ApiError(e).into_response(),
+        };
+
+        let mut claims = Map::new();
+        claims.insert("api_key_id".to_string(), Value::from(key.id.to_string()));
+        claims.insert("name".to_string(), Value::from(key.name.clone()));
+        claims.insert("tenant_id".to_string(), Value::from(key.tenant.to_string()));
+        AuthenticatedPrincipal {
+            subject: key.id.to_string(),
+            issuer: None,
+            scopes: key.scopes,
+            roles: Vec::new(),
+            claims,
+        }
+        .attach(req.extensions_mut());
+        req.extensions_mut().insert(AuthenticatedApiKey {
+            id: key.id,
+            tenant: key.tenant,
+        });
+    }
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application::bus::{Bus, Handler};
+    use async_trait::async_trait;
+    use axum::{body::Body, http::StatusCode, middleware, routing::get, Router};
+    use contracts::ApiKey;
+    use std::{sync::Arc, time::SystemTime};
+    use tower::ServiceExt;
+
+    const VALID: &str = "valid-token";
+
+    struct FakeAuthenticate;
+
+    #[async_trait]
+    impl Handler<AuthenticateApiKeyQuery> for FakeAuthenticate {
+        async fn handle(
+            &self,
+            _ctx: &CallerContext,
+            query: AuthenticateApiKeyQuery,
+        ) -> Result<ApiKey, DomainError> {
+            if query.token != VALID {
+                return Err(DomainError::NotFound {
+                    message: "Invalid API key".to_string(),
+                });
+            }
+            ApiKey::issue(
+                ApiKeyId::from_u128(9),
+                TenantId::default(),
+                "ci",
+                "hash".to_string(),
+                vec!["users:read".to_string()],
+                None,
+                SystemTime::now(),
+            )
+        }
+    }
+
+    #[derive(Clone)]
+    struct TestState(Arc<Bus>);
+
+    impl HasBus for TestState {
+        fn bus(&self) -> &Bus {
+            &self.0
+        }
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/me",
+                get(|principal: AuthenticatedPrincipal| async move {
+                    format!("{} {}", principal.subject, principal.scopes.join(","))
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                TestState(Arc::new(
+                    Bus::new().with_handler::<AuthenticateApiKeyQuery>(Arc::new(FakeAuthenticate)),
+                )),
+                api_key_auth_middleware::<TestState>,
+            ))
+    }
+
+    async fn call(authorization: Option<&str>) -> (StatusCode, String) {
+        let mut request = Request::builder().uri("/me");
+        if let Some(value) = authorization {
+            request = request.header(AUTHORIZATION, value);
+        }
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_api_key_resolves_to_principal() {
+        let (status, body) = call(Some(&format!("ApiKey {VALID}"))).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, format!("{} users:read", ApiKeyId::from_u128(9)));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_api_key_is_rejected_and_other_schemes_pass_through() {
+        assert_eq!(call(Some("ApiKey nope")).await.0, StatusCode::UNAUTHORIZED);
+        // 不是 API 金鑰的憑證交給其他驗證方式處理；這裡沒有 principal，由提取器回 401
+        assert_eq!(
+            call(Some("Bearer something")).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(call(None).await.0, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,760 @@
+// presentation/pres_web_axum/src/middleware/auth_middleware.rs
+
+//! JWT Bearer 驗證
+//!
+//! 中介層只負責驗證 `Authorization: Bearer <token>`：沒有帶 Bearer 憑證的請求會直接放行，
+//! 由需要身份的處理器透過 [`AuthenticatedPrincipal`] 提取器拒絕（401）。
+//! 簽章金鑰從 JWKS 載入並快取，遇到未知的 `kid` 時會重新載入以支援金鑰輪替。
+//! 同一時間只有一個請求重新載入，且兩次載入之間至少間隔 `jwks_min_refresh_interval`，
+//! 偽造的 `kid` 不會打爆 JWKS 端點。
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header::AUTHORIZATION, request::Parts},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use contracts::{AppError, CallerContext, Principal, TenantId};
+use jsonwebtoken::{
+    decode, decode_header,
+    jwk::{AlgorithmParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm},
+    Algorithm, DecodingKey, Validation,
+};
+use serde_json::{Map, Value};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::error::ApiError;
+
+/// 支援的簽章演算法
+const SUPPORTED_ALGORITHMS: [Algorithm; 3] = [Algorithm::RS256, Algorithm::ES256, Algorithm::HS256];
+
+type JwksFuture<'a> = Pin<Box<dyn Future<Output = Result<JwkSet, AppError>> + Send + 'a>>;
+
+/// JWKS 來源
+pub trait JwksSource: Send + Sync {
+    fn fetch(&self) -> JwksFuture<'_>;
+}
+
+/// 從本機檔案載入 JWKS（每次重新載入都會重新讀檔）
+#[derive(Debug, Clone)]
+pub struct FileJwksSource {
+    path: PathBuf,
+}
+
+impl FileJwksSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl JwksSource for FileJwksSource {
+    fn fetch(&self) -> JwksFuture<'_> {
+        Box::pin(async move {
+            let raw = tokio::fs::read(&self.path).await.map_err(|e| {
+                AppError::Application(format!("Failed to read JWKS {:?}: {e}", self.path))
+            })?;
+            serde_json::from_slice(&raw)
+                .map_err(|e| AppError::Application(format!("Invalid JWKS {:?}: {e}", self.path)))
+        })
+    }
+}
+
+/// 從 HTTP(S) 端點載入 JWKS
+#[derive(Debug, Clone)]
+pub struct HttpJwksSource {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl HttpJwksSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(5))
+                .build()
+                .unwrap_or_default(),
+            url: url.into(),
+        }
+    }
+}
+
+impl JwksSource for HttpJwksSource {
+    fn fetch(&self) -> JwksFuture<'_> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .get(&self.url)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(|e| AppError::Application(format!("Failed to fetch JWKS: {e}")))?;
+            response
+                .json()
+                .await
+                .map_err(|e| AppError::Application(format!("Invalid JWKS response: {e}")))
+        })
+    }
+}
+
+/// 固定的 JWKS（用於測試）
+#[derive(Debug, Clone)]
+pub struct StaticJwksSource(pub JwkSet);
+
+impl JwksSource for StaticJwksSource {
+    fn fetch(&self) -> JwksFuture<'_> {
+        let jwks = self.0.clone();
+        Box::pin(async move { Ok(jwks) })
+    }
+}
+
+/// JWT 驗證設定
+#[derive(Debug, Clone)]
+pub struct JwtSettings {
+    /// 期望的 `iss`；`None` 表示不檢查
+    pub issuer: Option<String>,
+    /// 接受的 `aud`；空集合表示不檢查
+    pub audiences: Vec<String>,
+    /// 檢查 `exp`/`nbf` 時容許的時鐘誤差
+    pub leeway: Duration,
+    /// 快取的金鑰在多久後重新載入
+    pub jwks_refresh_interval: Duration,
+    /// 兩次重新載入（不論成功與否）之間的最小間隔
+    pub jwks_min_refresh_interval: Duration,
+}
+
+impl Default for JwtSettings {
+    fn default() -> Self {
+        Self {
+            issuer: None,
+            audiences: Vec::new(),
+            leeway: Duration::from_secs(60),
+            jwks_refresh_interval: Duration::from_secs(300),
+            jwks_min_refresh_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+/// 通過驗證的呼叫者
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthenticatedPrincipal {
+    pub subject: String,
+    pub issuer: Option<String>,
+    pub scopes: Vec<String>,
+    pub roles: Vec<String>,
+    /// 完整的 JWT claims，供需要自訂 claim 的處理器使用
+    pub claims: Map<String, Value>,
+}
+
+impl AuthenticatedPrincipal {
+    fn from_claims(claims: Map<String, Value>) -> Result<Self, AppError> {
+        let subject = claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .ok_or_else(|| AppError::Unauthorized("Token has no subject".to_string()))?
+            .to_string();
+        let issuer = claims
+            .get("iss")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        // 兼容 OAuth2 的 `scope`（空白分隔字串）與 `scp`（陣列）兩種寫法
+        let scopes = match claims.get("scope").or_else(|| claims.get("scp")) {
+            Some(Value::String(scope)) => scope.split_whitespace().map(str::to_string).collect(),
+            Some(value) => string_list(value),
+            None => Vec::new(),
+        };
+        let roles = claims.get("roles").map(string_list).unwrap_or_default();
+
+        Ok(Self {
+            subject,
+            issuer,
+            scopes,
+            roles,
+            claims,
+        })
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for AuthenticatedPrincipal {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthenticatedPrincipal>()
+            .cloned()
+            .ok_or_else(|| AppError::Unauthorized("Missing bearer token".to_string()).into())
+    }
+}
+
+impl AuthenticatedPrincipal {
+    /// 放入請求擴展；同時放入 `Principal`，讓不依賴本 crate 的轉接器（例如 gRPC）取得呼叫者
+    pub(crate) fn attach(self, extensions: &mut axum::http::Extensions) {
+        extensions.insert(Principal::from(self.clone()));
+        extensions.insert(self);
+    }
+}
+
+impl From<AuthenticatedPrincipal> for Principal {
+    fn from(principal: AuthenticatedPrincipal) -> Self {
+        Principal::new(principal.subject)
+            .with_roles(principal.roles)
+            .with_scopes(principal.scopes)
+    }
+}
+
+/// 傳給用例的呼叫者上下文；沒有通過驗證的請求視為匿名呼叫者
+///
+/// 租戶取自租戶中介層放入的 [`TenantId`]，沒有則為預設租戶。
+#[derive(Debug, Clone)]
+pub struct CurrentCaller(pub CallerContext);
+
+impl<S: Send + Sync> FromRequestParts<S> for CurrentCaller {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let ctx = match parts.extensions.get::<AuthenticatedPrincipal>() {
+            Some(principal) => CallerContext::authenticated(principal.clone().into()),
+            None => CallerContext::anonymous(),
+        };
+        let ctx = match parts.extensions.get::<TenantId>() {
+            Some(tenant) => ctx.with_tenant(tenant.clone()),
+            None => ctx,
+        };
+        Ok(Self(ctx))
+    }
+}
+
+struct CachedKey {
+    key: DecodingKey,
+    algorithm: Algorithm,
+}
+
+#[derive(Default)]
+struct KeyCache {
+    by_kid: HashMap<String, Arc<CachedKey>>,
+    /// 沒有 `kid` 的金鑰，只在 JWKS 僅有單一金鑰時使用
+    anonymous: Option<Arc<CachedKey>>,
+    fetched_at: Option<Instant>,
+    /// 最近一次嘗試載入的時間，含失敗
+    attempted_at: Option<Instant>,
+}
+
+impl KeyCache {
+    fn lookup(&self, kid: Option<&str>) -> Option<Arc<CachedKey>> {
+        match kid {
+            Some(kid) => self.by_kid.get(kid).cloned(),
+            None => self.anonymous.clone(),
+        }
+    }
+
+    fn is_stale(&self, max_age: Duration) -> bool {
+        self.fetched_at.is_none_or(|at| at.elapsed() >= max_age)
+    }
+
+    fn attempted_within(&self, interval: Duration) -> bool {
+        self.attempted_at.is_some_and(|at| at.elapsed() < interval)
+    }
+}
+
+/// JWT 驗證器 - 持有 JWKS 快取
+pub struct JwtAuthenticator {
+    source: Arc<dyn JwksSource>,
+    settings: JwtSettings,
+    cache: RwLock<KeyCache>,
+    /// 讓同一時間只有一個請求重新載入 JWKS
+    refresh: Mutex<()>,
+}
+
+impl JwtAuthenticator {
+    pub fn new(source: Arc<dyn JwksSource>, settings: JwtSettings) -> Self {
+        Self {
+            source,
+            settings,
+            cache: RwLock::new(KeyCache::default()),
+            refresh: Mutex::new(()),
+        }
+    }
+
+    /// 重新載入 JWKS；載入失敗時保留舊的金鑰
+    pub async fn refresh_keys(&self) -> Result<(), AppError> {
+        let fetched = self.source.fetch().await;
+        self.cache.write().await.attempted_at = Some(Instant::now());
+        let jwks = fetched?;
+        let mut by_kid = HashMap::new();
+        let mut without_kid = Vec::new();
+        for jwk in &jwks.keys {
+            let Some(cached) = cached_key(jwk) else {
+                tracing::warn!(kid = ?jwk.common.key_id, "Ignoring unsupported JWK");
+                continue;
+            };
+            match &jwk.common.key_id {
+                Some(kid) => {
+                    by_kid.insert(kid.clone(), Arc::new(cached));
+                }
+                None => without_kid.push(Arc::new(cached)),
+            }
+        }
+
+        let mut cache = self.cache.write().await;
+        cache.anonymous = if without_kid.len() == 1 && by_kid.is_empty() {
+            without_kid.pop()
+        } else {
+            None
+        };
+        cache.by_kid = by_kid;
+        cache.fetched_at = Some(Instant::now());
+        Ok(())
+    }
+
+    /// 驗證 token 並回傳呼叫者
+    pub async fn authenticate(&self, token: &str) -> Result<AuthenticatedPrincipal, AppError> {
+        let header = decode_header(token).map_err(unauthorized)?;
+        if !SUPPORTED_ALGORITHMS.contains(&header.alg) {
+            return Err(AppError::Unauthorized(format!(
+                "Unsupported algorithm {:?}",
+                header.alg
+            )));
+        }
+
+        let key = self.key_for(header.kid.as_deref()).await?;
+        // 以金鑰宣告的演算法為準，防止以 HS256 冒用 RSA 公鑰等演算法混淆攻擊
+        if key.algorithm != header.alg {
+            return Err(AppError::Unauthorized(
+                "Token algorithm does not match key".to_string(),
+            ));
+        }
+
+        let claims = decode::<Map<String, Value>>(token, &key.key, &self.validation(key.algorithm))
+            .map_err(unauthorized)?
+            .claims;
+        AuthenticatedPrincipal::from_claims(claims)
+    }
+
+    async fn key_for(&self, kid: Option<&str>) -> Result<Arc<CachedKey>, AppError> {
+        let (key, stale) = {
+            let cache = self.cache.read().await;
+            (
+                cache.lookup(kid),
+                cache.is_stale(self.settings.jwks_refresh_interval),
+            )
+        };
+
+        // 已有金鑰時不等待其他請求的載入，先用過期的金鑰
+        let _refresh = match key {
+            Some(key) if !stale => return Ok(key),
+            Some(key) => match self.refresh.try_lock() {
+                Ok(guard) => guard,
+                Err(_) => return Ok(key),
+            },
+            None => self.refresh.lock().await,
+        };
+
+        {
+            // 等待期間其他請求可能已經載入；最近剛載入過時不再請求端點
+            let cache = self.cache.read().await;
+            let key = cache.lookup(kid);
+            let fresh = !cache.is_stale(self.settings.jwks_refresh_interval);
+            if (key.is_some() && fresh)
+                || cache.attempted_within(self.settings.jwks_min_refresh_interval)
+            {
+                return key.ok_or_else(unknown_key);
+            }
+        }
+
+        if let Err(e) = self.refresh_keys().await {
+            tracing::warn!(error = %e, "Failed to refresh JWKS, using cached keys");
+        }
+        self.cache.read().await.lookup(kid).ok_or_else(unknown_key)
+    }
+
+    fn validation(&self, algorithm: Algorithm) -> Validation {
+        let mut validation = Validation::new(algorithm);
+        validation.leeway = self.settings.leeway.as_secs();
+        validation.set_required_spec_claims(&["exp", "sub"]);
+        if let Some(issuer) = &self.settings.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        if self.settings.audiences.is_empty() {
+            validation.validate_aud = false;
+        } else {
+            validation.set_audience(&self.settings.audiences);
+        }
+        validation
+    }
+}
+
+fn cached_key(jwk: &Jwk) -> Option<CachedKey> {
+    let inferred = match &jwk.algorithm {
+        AlgorithmParameters::RSA(_) => Algorithm::RS256,
+        AlgorithmParameters::EllipticCurve(params) if params.curve == EllipticCurve::P256 => {
+            Algorithm::ES256
+        }
+        AlgorithmParameters::OctetKey(_) => Algorithm::HS256,
+        _ => return None,
+    };
+    let algorithm = match jwk.common.key_algorithm {
+        None => inferred,
+        Some(KeyAlgorithm::RS256) if inferred == Algorithm::RS256 => Algorithm::RS256,
+        Some(KeyAlgorithm::ES256) if inferred == Algorithm::ES256 => Algorithm::ES256,
+        Some(KeyAlgorithm::HS256) if inferred == Algorithm::HS256 => Algorithm::HS256,
+        Some(_) => return None,
+    };
+    let key = DecodingKey::from_jwk(jwk).ok()?;
+    Some(CachedKey { key, algorithm })
+}
+
+fn unauthorized(error: jsonwebtoken::errors::Error) -> AppError {
+    AppError::Unauthorized(format!("Invalid token: {error}"))
+}
+
+fn unknown_key() -> AppError {
+    AppError::Unauthorized("Unknown signing key".to_string())
+}
+
+/// 驗證 Bearer token，成功時將 [`AuthenticatedPrincipal`] 放入請求擴展
+pub async fn jwt_auth_middleware(
+    State(authenticator): State<Arc<JwtAuthenticator>>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let token = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .map(str::to_string);
+
+    if let Some(token) = token {
+        match authenticator.authenticate(&token).await {
+            Ok(principal) => principal.attach(req.extensions_mut()),
+            Err(e) => return ApiError(e).into_response(),
+        }
+    }
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, routing::get, Router};
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use ring::{
+        rand::SystemRandom,
+        signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING},
+    };
+    use serde_json::json;
+    use std::{
+        sync::Mutex,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+    use tower::ServiceExt;
+
+    const RSA_PRIVATE_KEY: &[u8] = include_bytes!("../../tests/fixtures/rs256_private.pem");
+    const RSA_JWKS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/jwks.json");
+    const RSA_KID: &str = "rsa-test-1";
+
+    /// 可在測試中替換內容的 JWKS 來源，並記錄載入次數
+    #[derive(Default)]
+    struct SwappableJwks {
+        keys: Mutex<Vec<Value>>,
+        fetches: Mutex<u32>,
+        delay: Duration,
+    }
+
+    impl JwksSource for SwappableJwks {
+        fn fetch(&self) -> JwksFuture<'_> {
+            *self.fetches.lock().unwrap() += 1;
+            let jwks = json!({ "keys": *self.keys.lock().unwrap() });
+            Box::pin(async move {
+                tokio::time::sleep(self.delay).await;
+                Ok(serde_json::from_value(jwks).unwrap())
+            })
+        }
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    fn claims() -> Value {
+        json!({
+            "sub": "user-42",
+            "iss": "https://issuer.test",
+            "aud": "users-api",
+            "exp": now() + 300,
+            "scope": "users:read users:write",
+            "roles": ["admin"],
+        })
+    }
+
+    fn settings() -> JwtSettings {
+        JwtSettings {
+            issuer: Some("https://issuer.test".to_string()),
+            audiences: vec!["users-api".to_string()],
+            leeway: Duration::from_secs(5),
+            ..JwtSettings::default()
+        }
+    }
+
+    fn sign(alg: Algorithm, kid: &str, key: &EncodingKey, claims: &Value) -> String {
+        let mut header = Header::new(alg);
+        header.kid = Some(kid.to_string());
+        encode(&header, claims, key).unwrap()
+    }
+
+    fn hs256_jwk(kid: &str, secret: &[u8]) -> Value {
+        json!({ "kty": "oct", "kid": kid, "alg": "HS256", "k": URL_SAFE_NO_PAD.encode(secret) })
+    }
+
+    /// 產生 ES256 金鑰對，回傳簽章金鑰與對應的 JWK
+    fn es256_key(kid: &str) -> (EncodingKey, Value) {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+        let pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+            .unwrap();
+        // 未壓縮的公鑰格式：0x04 || x || y
+        let point = pair.public_key().as_ref();
+        let jwk = json!({
+            "kty": "EC",
+            "crv": "P-256",
+            "kid": kid,
+            "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+            "y": URL_SAFE_NO_PAD.encode(&point[33..]),
+        });
+        (EncodingKey::from_ec_der(pkcs8.as_ref()), jwk)
+    }
+
+    fn authenticator_with(keys: Vec<Value>) -> (JwtAuthenticator, Arc<SwappableJwks>) {
+        let source = Arc::new(SwappableJwks::default());
+        *source.keys.lock().unwrap() = keys;
+        (JwtAuthenticator::new(source.clone(), settings()), source)
+    }
+
+    #[tokio::test]
+    async fn test_accepts_rs256_token_from_jwks_file() {
+        let authenticator =
+            JwtAuthenticator::new(Arc::new(FileJwksSource::new(RSA_JWKS_PATH)), settings());
+        let key = EncodingKey::from_rsa_pem(RSA_PRIVATE_KEY).unwrap();
+        let token = sign(Algorithm::RS256, RSA_KID, &key, &claims());
+
+        let principal = authenticator.authenticate(&token).await.unwrap();
+
+        assert_eq!(principal.subject, "user-42");
+        assert_eq!(principal.issuer.as_deref(), Some("https://issuer.test"));
+        assert!(principal.has_scope("users:write"));
+        assert_eq!(principal.roles, vec!["admin".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_accepts_es256_and_hs256_tokens() {
+        let (es_key, es_jwk) = es256_key("ec-1");
+        let secret = b"a-very-secret-shared-key-for-tests";
+        let (authenticator, _) = authenticator_with(vec![es_jwk, hs256_jwk("hmac-1", secret)]);
+
+        let es_token = sign(Algorithm::ES256, "ec-1", &es_key, &claims());
+        let hs_token = sign(
+            Algorithm::HS256,
+            "hmac-1",
+            &EncodingKey::from_secret(secret),
+            &claims(),
+        );
+
+        assert!(authenticator.authenticate(&es_token).await.is_ok());
+        assert!(authenticator.authenticate(&hs_token).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_wrong_issuer_audience_and_expired_tokens() {
+        let secret = b"shared-secret";
+        let (authenticator, _) = authenticator_with(vec![hs256_jwk("k", secret)]);
+        let key = EncodingKey::from_secret(secret);
+
+        let mut wrong_issuer = claims();
+        wrong_issuer["iss"] = json!("https://evil.test");
+        let mut wrong_audience = claims();
+        wrong_audience["aud"] = json!("other-api");
+        let mut expired = claims();
+        expired["exp"] = json!(now() - 60);
+        let mut within_leeway = claims();
+        within_leeway["exp"] = json!(now() - 2);
+
+        for rejected in [wrong_issuer, wrong_audience, expired] {
+            let token = sign(Algorithm::HS256, "k", &key, &rejected);
+            assert!(matches!(
+                authenticator.authenticate(&token).await,
+                Err(AppError::Unauthorized(_))
+            ));
+        }
+        let token = sign(Algorithm::HS256, "k", &key, &within_leeway);
+        assert!(authenticator.authenticate(&token).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_algorithm_confusion() {
+        // 以 RSA 公鑰的 JWK 內容當作 HMAC 密鑰簽章，不得通過驗證
+        let jwks: Value =
+            serde_json::from_str(&std::fs::read_to_string(RSA_JWKS_PATH).unwrap()).unwrap();
+        let (authenticator, _) = authenticator_with(jwks["keys"].as_array().unwrap().clone());
+        let forged_secret = jwks["keys"][0]["n"].as_str().unwrap().as_bytes().to_vec();
+        let token = sign(
+            Algorithm::HS256,
+            RSA_KID,
+            &EncodingKey::from_secret(&forged_secret),
+            &claims(),
+        );
+
+        assert!(matches!(
+            authenticator.authenticate(&token).await,
+            Err(AppError::Unauthorized(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_kid_triggers_key_rotation() {
+        let (old_key, old_jwk) = es256_key("old");
+        let (new_key, new_jwk) = es256_key("new");
+        let (authenticator, source) = authenticator_with(vec![old_jwk]);
+
+        let old_token = sign(Algorithm::ES256, "old", &old_key, &claims());
+        assert!(authenticator.authenticate(&old_token).await.is_ok());
+
+        // 簽發方輪替金鑰；快取過期前仍須能驗證新 kid
+        *source.keys.lock().unwrap() = vec![new_jwk];
+        authenticator.cache.write().await.attempted_at =
+            Some(Instant::now() - settings().jwks_min_refresh_interval);
+        let new_token = sign(Algorithm::ES256, "new", &new_key, &claims());
+
+        assert!(authenticator.authenticate(&new_token).await.is_ok());
+        assert_eq!(*source.fetches.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_kid_refresh_is_throttled() {
+        let (authenticator, source) = authenticator_with(vec![hs256_jwk("k", b"secret")]);
+        authenticator.refresh_keys().await.unwrap();
+
+        let token = sign(
+            Algorithm::HS256,
+            "forged",
+            &EncodingKey::from_secret(b"secret"),
+            &claims(),
+        );
+        for _ in 0..3 {
+            assert!(authenticator.authenticate(&token).await.is_err());
+        }
+
+        assert_eq!(*source.fetches.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_unknown_kids_share_one_refresh() {
+        let source = Arc::new(SwappableJwks {
+            delay: Duration::from_millis(50),
+            ..SwappableJwks::default()
+        });
+        let authenticator = Arc::new(JwtAuthenticator::new(source.clone(), settings()));
+        let token = sign(
+            Algorithm::HS256,
+            "forged",
+            &EncodingKey::from_secret(b"secret"),
+            &claims(),
+        );
+
+        let attempts = (0..5).map(|_| {
+            let authenticator = authenticator.clone();
+            let token = token.clone();
+            tokio::spawn(async move { authenticator.authenticate(&token).await })
+        });
+        for attempt in futures_util::future::join_all(attempts).await {
+            assert!(attempt.unwrap().is_err());
+        }
+
+        assert_eq!(*source.fetches.lock().unwrap(), 1);
+    }
+
+    async fn whoami(principal: AuthenticatedPrincipal) -> String {
+        principal.subject
+    }
+
+    fn router(authenticator: JwtAuthenticator) -> Router {
+        Router::new()
+            .route("/me", get(whoami))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(authenticator),
+                jwt_auth_middleware,
+            ))
+    }
+
+    async fn call(router: Router, authorization: Option<String>) -> Response {
+        let mut request = Request::builder().uri("/me");
+        if let Some(value) = authorization {
+            request = request.header(AUTHORIZATION, value);
+        }
+        router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_middleware_injects_principal() {
+        let secret = b"shared-secret";
+        let (authenticator, _) = authenticator_with(vec![hs256_jwk("k", secret)]);
+        let token = sign(
+            Algorithm::HS256,
+            "k",
+            &EncodingKey::from_secret(secret),
+            &claims(),
+        );
+
+        let response = call(router(authenticator), Some(format!("Bearer {token}"))).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_missing_or_invalid_token_is_unauthorized() {
+        let (authenticator, _) = authenticator_with(vec![hs256_jwk("k", b"secret")]);
+        let router = router(authenticator);
+
+        for authorization in [None, Some("Bearer not-a-jwt".to_string())] {
+            let response = call(router.clone(), authorization).await;
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            assert_eq!(response.headers()["www-authenticate"], "Bearer");
+        }
+    }
+}
@@ -0,0 +1,243 @@
+// presentation/pres_web_axum/src/middleware/cors.rs
+
+//! 跨來源資源共用（CORS）
+//!
+//! 允許的來源可以是完整來源（`https://app.example.com`）、萬用子網域
+//! （`https://*.example.com`，比對任意層子網域但不含基底網域本身）或 `*`。
+//! 回應一律回傳請求的 `Origin` 而非 `*`，因此搭配 `Vary: Origin` 可安全地被快取。
+//!
+//! 此層必須放在驗證層之外，預檢請求不帶憑證，應在驗證前直接回應。
+
+use std::{sync::Arc, time::Duration};
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+/// 允許的來源樣式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OriginPattern {
+    /// 任何來源
+    Any,
+    /// 完整比對，例如 `https://app.example.com:8443`
+    Exact(String),
+    /// `scheme://*.domain[:port]`，`prefix` 為 `scheme://`，`suffix` 為 `.domain[:port]`
+    Subdomain { prefix: String, suffix: String },
+}
+
+impl OriginPattern {
+    /// 解析設定中的來源；只接受 http 與 https，且不可帶路徑
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let raw = raw.trim();
+        if raw == "*" {
+            return Ok(OriginPattern::Any);
+        }
+        let origin = raw.to_ascii_lowercase();
+        let (scheme, authority) = origin
+            .split_once("://")
+            .ok_or_else(|| format!("Origin {raw:?} must include a scheme"))?;
+        if scheme != "http" && scheme != "https" {
+            return Err(format!("Origin {raw:?} must use http or https"));
+        }
+        if authority.is_empty() || authority.contains('/') {
+            return Err(format!("Origin {raw:?} must not include a path"));
+        }
+        if HeaderValue::from_str(&origin).is_err() {
+            return Err(format!("Origin {raw:?} is not a valid header value"));
+        }
+        match authority.strip_prefix('*') {
+            Some(suffix) if suffix.starts_with('.') && suffix.len() > 1 && !suffix.contains('*') => {
+                Ok(OriginPattern::Subdomain {
+                    prefix: format!("{scheme}://"),
+                    suffix: suffix.to_owned(),
+                })
+            }
+            Some(_) => Err(format!(
+                "Origin {raw:?} may only use a wildcard as the leftmost label, e.g. https://*.example.com"
+            )),
+            None if authority.contains('*') => Err(format!(
+                "Origin {raw:?} may only use a wildcard as the leftmost label, e.g. https://*.example.com"
+            )),
+            None => Ok(OriginPattern::Exact(origin)),
+        }
+    }
+
+    pub fn matches(&self, origin: &HeaderValue) -> bool {
+        let Ok(origin) = origin.to_str() else {
+            return false;
+        };
+        let origin = origin.to_ascii_lowercase();
+        match self {
+            OriginPattern::Any => true,
+            OriginPattern::Exact(expected) => origin == *expected,
+            OriginPattern::Subdomain { prefix, suffix } => origin
+                .strip_prefix(prefix.as_str())
+                .and_then(|rest| rest.strip_suffix(suffix.as_str()))
+                .is_some_and(is_subdomain),
+        }
+    }
+}
+
+/// 一或多個以 `.` 分隔的 DNS 標籤
+fn is_subdomain(labels: &str) -> bool {
+    !labels.is_empty()
+        && labels.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// CORS 政策
+#[derive(Debug, Clone)]
+pub struct CorsPolicy {
+    origins: Vec<OriginPattern>,
+    methods: Option<Vec<Method>>,
+    headers: Option<Vec<HeaderName>>,
+    expose_headers: Vec<HeaderName>,
+    allow_credentials: bool,
+    max_age: Option<Duration>,
+}
+
+impl CorsPolicy {
+    pub fn new(origins: Vec<OriginPattern>) -> Self {
+        Self {
+            origins,
+            methods: Some(Vec::new()),
+            headers: Some(Vec::new()),
+            expose_headers: Vec::new(),
+            allow_credentials: false,
+            max_age: None,
+        }
+    }
+
+    /// 預檢允許的方法；`None` 表示允許預檢請求所要求的任何方法
+    pub fn with_methods(mut self, methods: Option<Vec<Method>>) -> Self {
+        self.methods = methods;
+        self
+    }
+
+    /// 預檢允許的請求標頭；`None` 表示允許預檢請求所要求的任何標頭
+    pub fn with_headers(mut self, headers: Option<Vec<HeaderName>>) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// 瀏覽器可讀取的回應標頭
+    pub fn with_expose_headers(mut self, headers: Vec<HeaderName>) -> Self {
+        self.expose_headers = headers;
+        self
+    }
+
+    /// 是否允許瀏覽器附帶 cookie 與 `Authorization`
+    pub fn with_credentials(mut self, allow: bool) -> Self {
+        self.allow_credentials = allow;
+        self
+    }
+
+    /// 預檢結果的快取時間；`None` 時由瀏覽器決定
+    pub fn with_max_age(mut self, max_age: Option<Duration>) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    pub fn layer(self) -> CorsLayer {
+        let origins = Arc::new(self.origins);
+        let layer = CorsLayer::new()
+            .allow_origin(AllowOrigin::predicate(move |origin, _| {
+                origins.iter().any(|pattern| pattern.matches(origin))
+            }))
+            .allow_methods(match self.methods {
+                Some(methods) => AllowMethods::list(methods),
+                None => AllowMethods::mirror_request(),
+            })
+            .allow_headers(match self.headers {
+                Some(headers) => AllowHeaders::list(headers),
+                None => AllowHeaders::mirror_request(),
+            })
+            .expose_headers(self.expose_headers)
+            .allow_credentials(self.allow_credentials);
+        match self.max_age {
+            Some(max_age) => layer.max_age(max_age),
+            None => layer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        extract::Request,
+        http::{header, StatusCode},
+        routing::get,
+        Router,
+    };
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_origin_patterns() {
+        let exact = OriginPattern::parse("https://App.example.com").unwrap();
+        assert!(exact.matches(&HeaderValue::from_static("https://app.example.com")));
+        assert!(!exact.matches(&HeaderValue::from_static("http://app.example.com")));
+
+        let wildcard = OriginPattern::parse("https://*.example.com").unwrap();
+        assert!(wildcard.matches(&HeaderValue::from_static("https://acme.example.com")));
+        assert!(wildcard.matches(&HeaderValue::from_static("https://a.b.example.com")));
+        assert!(!wildcard.matches(&HeaderValue::from_static("https://example.com")));
+        assert!(!wildcard.matches(&HeaderValue::from_static("https://evil.com/.example.com")));
+        assert!(!wildcard.matches(&HeaderValue::from_static("https://acme.example.com:8443")));
+
+        for invalid in [
+            "example.com",
+            "ftp://example.com",
+            "https://example.com/",
+            "https://a.*.example.com",
+            "https://*example.com",
+        ] {
+            assert!(OriginPattern::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preflight_echoes_matching_origin() {
+        let app = Router::new().route("/users", get(|| async { "ok" })).layer(
+            CorsPolicy::new(vec![OriginPattern::parse("https://*.example.com").unwrap()])
+                .with_methods(Some(vec![Method::GET, Method::POST]))
+                .with_headers(Some(vec![header::AUTHORIZATION, header::CONTENT_TYPE]))
+                .with_credentials(true)
+                .with_max_age(Some(Duration::from_secs(600)))
+                .layer(),
+        );
+        let preflight = |origin: &'static str| {
+            Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/users")
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(preflight("https://acme.example.com"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://acme.example.com"
+        );
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+        assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "600");
+
+        let response = app.oneshot(preflight("https://example.org")).await.unwrap();
+        assert!(response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+}
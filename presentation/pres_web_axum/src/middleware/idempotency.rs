@@ -0,0 +1,570 @@
+// presentation/pres_web_axum/src/middleware/idempotency.rs
+
+//! 冪等鍵
+//!
+//! 帶有 `Idempotency-Key` 標頭的 POST 請求只會執行一次：第一次請求的指紋（方法、路徑與主體的
+//! SHA-256）與回應會被記錄，之後以相同鍵重試時直接重播記錄的回應並加上 `Idempotent-Replayed: true`。
+//! 相同的鍵搭配不同的請求、或原請求仍在處理中時回傳 409。
+//! 5xx 回應不會被記錄，用戶端可以用同一個鍵重試；超過上限的回應照常串流回傳但不記錄。
+//!
+//! 鍵以呼叫者身份區隔，因此此層必須放在驗證中介層之內；未驗證的請求無法區隔呼叫者，
+//! 帶有此標頭時回傳 401。未啟用驗證的部署可以改為允許匿名呼叫者，這些請求共用每個租戶
+//! 一個的 `anon` 範圍。串流上傳的路由（例如批次匯入）無法緩衝主體，列為豁免路由後直接放行。
+
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{MatchedPath, Request, State},
+    http::{HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use contracts::{
+    AppError, DomainError, DynIdempotencyStore, IdempotencyClaim, StoredResponse, TenantId,
+};
+use futures_util::{stream, StreamExt};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::ApiError, middleware::auth_middleware::AuthenticatedPrincipal,
+    versioning::unversioned_route,
+};
+
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// 冪等鍵的最大長度
+const MAX_KEY_LENGTH: usize = 255;
+
+/// 冪等鍵中介層的狀態
+pub struct IdempotencyGuard {
+    store: DynIdempotencyStore,
+    ttl: Duration,
+    lock_timeout: Duration,
+    max_request_bytes: usize,
+    max_response_bytes: usize,
+    exempt_routes: Vec<String>,
+    allow_anonymous: bool,
+}
+
+impl IdempotencyGuard {
+    pub fn new(store: DynIdempotencyStore) -> Self {
+        Self {
+            store,
+            ttl: Duration::from_secs(24 * 60 * 60),
+            lock_timeout: Duration::from_secs(60),
+            max_request_bytes: 1024 * 1024,
+            max_response_bytes: 1024 * 1024,
+            exempt_routes: Vec::new(),
+            allow_anonymous: false,
+        }
+    }
+
+    /// 記錄保留的時間，過期後同一個鍵視為新請求
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// 處理中的請求鎖定鍵的時間；逾時後視為放棄，可被重新認領
+    pub fn with_lock_timeout(mut self, lock_timeout: Duration) -> Self {
+        self.lock_timeout = lock_timeout;
+        self
+    }
+
+    /// 計算指紋時緩衝的請求主體上限
+    pub fn with_max_request_bytes(mut self, bytes: usize) -> Self {
+        self.max_request_bytes = bytes;
+        self
+    }
+
+    /// 可記錄的回應主體上限；超過時回應照常回傳但不記錄
+    pub fn with_max_response_bytes(mut self, bytes: usize) -> Self {
+        self.max_response_bytes = bytes;
+        self
+    }
+
+    /// 不套用冪等鍵的路由（不含版本前綴的樣板，結尾的 `*` 代表前綴比對），用於串流上傳
+    pub fn with_exempt_routes(mut self, routes: Vec<String>) -> Self {
+        self.exempt_routes = routes;
+        self
+    }
+
+    /// 允許未驗證的呼叫者使用冪等鍵
+    ///
+    /// 匿名請求無法區隔呼叫者，同一個租戶內的匿名呼叫者共用鍵的命名空間，
+    /// 只適合沒有驗證、所有呼叫者本來就能看到相同資料的部署。
+    pub fn with_anonymous_callers(mut self, allow: bool) -> Self {
+        self.allow_anonymous = allow;
+        self
+    }
+
+    fn is_exempt(&self, req: &Request) -> bool {
+        let route = req
+            .extensions()
+            .get::<MatchedPath>()
+            .map_or_else(|| req.uri().path(), |path| path.as_str());
+        let route = unversioned_route(route);
+        self.exempt_routes
+            .iter()
+            .any(|exempt| match exempt.strip_suffix('*') {
+                Some(prefix) => route.starts_with(prefix),
+                None => exempt == route,
+            })
+    }
+
+    async fn release(&self, key: &str) {
+        if let Err(e) = self.store.release(key).await {
+            tracing::warn!(error = %e, "Failed to release idempotency key");
+        }
+    }
+}
+
+fn conflict(message: &str) -> Response {
+    ApiError(AppError::Domain(DomainError::Conflict {
+        message: message.to_string(),
+    }))
+    .into_response()
+}
+
+fn unavailable(error: AppError) -> Response {
+    tracing::error!(error = %error, "Idempotency store unavailable");
+    ApiError(AppError::Domain(DomainError::Unavailable {
+        message: "Idempotency store unavailable".to_string(),
+    }))
+    .into_response()
+}
+
+/// 以 `方法 路徑?查詢` 與主體計算請求指紋
+fn fingerprint(method: &Method, path_and_query: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b" ");
+    hasher.update(path_and_query.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+/// 緩衝到上限為止的回應主體
+enum BufferedBody {
+    Complete(Bytes),
+    /// 超過上限；已讀取的部分接回尚未讀取的串流
+    TooLarge(Body),
+}
+
+async fn buffer_body(body: Body, limit: usize) -> Result<BufferedBody, axum::Error> {
+    let mut data = body.into_data_stream();
+    let mut chunks = Vec::new();
+    let mut len = 0;
+    while let Some(chunk) = data.next().await {
+        let chunk = chunk?;
+        len += chunk.len();
+        chunks.push(chunk);
+        if len > limit {
+            let head = stream::iter(chunks.into_iter().map(Ok));
+            return Ok(BufferedBody::TooLarge(Body::from_stream(head.chain(data))));
+        }
+    }
+    Ok(BufferedBody::Complete(Bytes::from(chunks.concat())))
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let mut response = Response::new(Body::from(stored.body));
+    *response.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let headers = response.headers_mut();
+    for (name, value) in stored.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            headers.append(name, value);
+        }
+    }
+    headers.insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+    response
+}
+
+/// 依 `Idempotency-Key` 執行或重播 POST 請求；沒有此標頭的請求直接放行
+pub async fn idempotency_middleware(
+    State(guard): State<Arc<IdempotencyGuard>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if req.method() != Method::POST || guard.is_exempt(&req) {
+        return next.run(req).await;
+    }
+    let Some(key) = req.headers().get(IDEMPOTENCY_KEY) else {
+        return next.run(req).await;
+    };
+    let key = match key.to_str() {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key.to_owned(),
+        _ => {
+            return ApiError(AppError::Validation(format!(
+                "Idempotency-Key must be 1 to {MAX_KEY_LENGTH} visible ASCII characters"
+            )))
+            .into_response()
+        }
+    };
+
+    // 不同租戶與呼叫者可以使用相同的鍵；匿名呼叫者無法區隔，共用鍵會互相重播回應
+    let scope = match req.extensions().get::<AuthenticatedPrincipal>() {
+        Some(principal) => format!("sub:{}", principal.subject),
+        None if guard.allow_anonymous => "anon".to_string(),
+        None => {
+            return ApiError(AppError::Unauthorized(
+                "Idempotency-Key requires an authenticated caller".to_string(),
+            ))
+            .into_response()
+        }
+    };
+    let key = match req.extensions().get::<TenantId>() {
+        Some(tenant) => format!("tenant:{tenant}:{scope}:{key}"),
+        None => format!("{scope}:{key}"),
+    };
+
+    let (parts, body) = req.into_parts();
+    let body = match to_bytes(body, guard.max_request_bytes).await {
+        Ok(body) => body,
+        Err(_) => {
+            return ApiError(AppError::PayloadTooLarge(format!(
+                "Request body exceeds {} bytes",
+                guard.max_request_bytes
+            )))
+            .into_response()
+        }
+    };
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or_else(|| parts.uri.path());
+    let fingerprint = fingerprint(&parts.method, path_and_query, &body);
+
+    match guard
+        .store
+        .claim(&key, &fingerprint, guard.lock_timeout)
+        .await
+    {
+        Ok(IdempotencyClaim::Acquired) => {}
+        Ok(IdempotencyClaim::Existing {
+            fingerprint: stored,
+            ..
+        }) if stored != fingerprint => {
+            return conflict("Idempotency-Key was already used with a different request");
+        }
+        Ok(IdempotencyClaim::Existing { response: None, .. }) => {
+            return conflict("A request with this Idempotency-Key is still being processed");
+        }
+        Ok(IdempotencyClaim::Existing {
+            response: Some(stored),
+            ..
+        }) => return replay(stored),
+        Err(e) => return unavailable(e),
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    // 伺服器錯誤不記錄，讓用戶端可以重試
+    if response.status().is_server_error() {
+        guard.release(&key).await;
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match buffer_body(body, guard.max_response_bytes).await {
+        Ok(BufferedBody::Complete(body)) => body,
+        Ok(BufferedBody::TooLarge(body)) => {
+            tracing::warn!(
+                limit = guard.max_response_bytes,
+                "Response too large to record for idempotent replay"
+            );
+            guard.release(&key).await;
+            return Response::from_parts(parts, body);
+        }
+        Err(e) => {
+            guard.release(&key).await;
+            return ApiError(AppError::Application(format!(
+                "Failed to read response body: {e}"
+            )))
+            .into_response();
+        }
+    };
+
+    let stored = StoredResponse {
+        status: parts.status.as_u16(),
+        headers: parts
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((name.as_str().to_owned(), value.to_str().ok()?.to_owned()))
+            })
+            .collect(),
+        body: body.to_vec(),
+    };
+    if let Err(e) = guard.store.complete(&key, stored, guard.ttl).await {
+        tracing::warn!(error = %e, "Failed to record idempotent response");
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::header::CONTENT_TYPE, middleware, routing::post, Router};
+    use contracts::IdempotencyStore;
+    use infra_memory::InMemoryIdempotencyStore;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    fn app(calls: Arc<AtomicUsize>, status: StatusCode) -> Router {
+        app_with(
+            IdempotencyGuard::new(Arc::new(InMemoryIdempotencyStore::new())),
+            calls,
+            status,
+        )
+    }
+
+    fn app_with(guard: IdempotencyGuard, calls: Arc<AtomicUsize>, status: StatusCode) -> Router {
+        let handler = move |body: String| {
+            let calls = calls.clone();
+            async move {
+                let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                (
+                    status,
+                    [(CONTENT_TYPE, "application/json")],
+                    format!(r#"{{"call":{n},"body":{body:?}}}"#),
+                )
+            }
+        };
+        Router::new()
+            .route("/users", post(handler.clone()))
+            .route("/users/import", post(handler))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(guard),
+                idempotency_middleware,
+            ))
+    }
+
+    fn principal(subject: &str) -> AuthenticatedPrincipal {
+        AuthenticatedPrincipal {
+            subject: subject.to_string(),
+            issuer: None,
+            scopes: Vec::new(),
+            roles: Vec::new(),
+            claims: Default::default(),
+        }
+    }
+
+    fn request_to(uri: &str, key: Option<&str>, body: &str) -> Request {
+        let mut builder = Request::builder().method(Method::POST).uri(uri);
+        if let Some(key) = key {
+            builder = builder.header(IDEMPOTENCY_KEY, key);
+        }
+        let mut request = builder.body(Body::from(body.to_string())).unwrap();
+        request.extensions_mut().insert(principal("alice"));
+        request
+    }
+
+    fn request(key: Option<&str>, body: &str) -> Request {
+        request_to("/users", key, body)
+    }
+
+    async fn body_text(response: Response) -> String {
+        String::from_utf8(to_bytes(response.into_body(), 1024).await.unwrap().to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_retry_replays_stored_response() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), StatusCode::CREATED);
+
+        let first = app
+            .clone()
+            .oneshot(request(Some("abc"), "ada"))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::CREATED);
+        assert!(!first.headers().contains_key(IDEMPOTENT_REPLAYED));
+        let first_body = body_text(first).await;
+
+        let retry = app
+            .clone()
+            .oneshot(request(Some("abc"), "ada"))
+            .await
+            .unwrap();
+        assert_eq!(retry.status(), StatusCode::CREATED);
+        assert_eq!(retry.headers()[IDEMPOTENT_REPLAYED], "true");
+        assert_eq!(retry.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(body_text(retry).await, first_body);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // 沒有冪等鍵的請求照常執行
+        app.oneshot(request(None, "ada")).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_key_reuse_with_different_body_conflicts() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), StatusCode::CREATED);
+
+        app.clone()
+            .oneshot(request(Some("abc"), "ada"))
+            .await
+            .unwrap();
+        let reused = app.oneshot(request(Some("abc"), "bob")).await.unwrap();
+
+        assert_eq!(reused.status(), StatusCode::CONFLICT);
+        assert!(body_text(reused).await.contains("CONFLICT"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_are_not_recorded() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), StatusCode::SERVICE_UNAVAILABLE);
+
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(request(Some("abc"), "ada"))
+                .await
+                .unwrap();
+            assert!(!response.headers().contains_key(IDEMPOTENT_REPLAYED));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_keys_are_scoped_per_caller_and_in_flight_requests_conflict() {
+        let store = Arc::new(InMemoryIdempotencyStore::new());
+        let guard = IdempotencyGuard::new(store.clone());
+        // alice 的原請求仍在處理中
+        store
+            .claim(
+                "sub:alice:abc",
+                &fingerprint(&Method::POST, "/users", b"ada"),
+                Duration::from_secs(60),
+            )
+            .await
+            .unwrap();
+        let app = Router::new()
+            .route("/users", post(|| async { StatusCode::CREATED }))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(guard),
+                idempotency_middleware,
+            ));
+
+        let response = app
+            .clone()
+            .oneshot(request(Some("abc"), "ada"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let mut bob = request(Some("abc"), "ada");
+        bob.extensions_mut().insert(principal("bob"));
+        let response = app.oneshot(bob).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_callers_cannot_use_keys() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), StatusCode::CREATED);
+
+        let mut anonymous = request(Some("abc"), "ada");
+        anonymous
+            .extensions_mut()
+            .remove::<AuthenticatedPrincipal>();
+        let response = app.clone().oneshot(anonymous).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let mut anonymous = request(None, "ada");
+        anonymous
+            .extensions_mut()
+            .remove::<AuthenticatedPrincipal>();
+        let response = app.oneshot(anonymous).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_scope_is_separate_from_authenticated_callers() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let guard = IdempotencyGuard::new(Arc::new(InMemoryIdempotencyStore::new()))
+            .with_anonymous_callers(true);
+        let app = app_with(guard, calls.clone(), StatusCode::CREATED);
+        let anonymous = || {
+            let mut request = request(Some("abc"), "ada");
+            request.extensions_mut().remove::<AuthenticatedPrincipal>();
+            request
+        };
+
+        let first = body_text(app.clone().oneshot(anonymous()).await.unwrap()).await;
+        let replay = app.clone().oneshot(anonymous()).await.unwrap();
+        assert_eq!(replay.headers()[IDEMPOTENT_REPLAYED], "true");
+        assert_eq!(body_text(replay).await, first);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // 已驗證的呼叫者使用相同的鍵不會重播匿名請求的回應
+        let response = app.oneshot(request(Some("abc"), "ada")).await.unwrap();
+        assert!(response.headers().get(IDEMPOTENT_REPLAYED).is_none());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_request_is_rejected() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let guard = IdempotencyGuard::new(Arc::new(InMemoryIdempotencyStore::new()))
+            .with_max_request_bytes(4);
+        let app = app_with(guard, calls.clone(), StatusCode::CREATED);
+
+        let response = app.oneshot(request(Some("abc"), "too long")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_response_passes_through_unrecorded() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let guard = IdempotencyGuard::new(Arc::new(InMemoryIdempotencyStore::new()))
+            .with_max_response_bytes(8);
+        let app = app_with(guard, calls.clone(), StatusCode::CREATED);
+
+        let first = app
+            .clone()
+            .oneshot(request(Some("abc"), "ada"))
+            .await
+            .unwrap();
+        assert_eq!(body_text(first).await, r#"{"call":1,"body":"ada"}"#);
+
+        // 未記錄且鍵已釋放，重試會再次執行
+        let retry = app.oneshot(request(Some("abc"), "ada")).await.unwrap();
+        assert_eq!(retry.status(), StatusCode::CREATED);
+        assert!(!retry.headers().contains_key(IDEMPOTENT_REPLAYED));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_exempt_routes_are_not_buffered() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let guard = IdempotencyGuard::new(Arc::new(InMemoryIdempotencyStore::new()))
+            .with_max_request_bytes(4)
+            .with_exempt_routes(vec!["/users/import".to_string()]);
+        let app = app_with(guard, calls.clone(), StatusCode::CREATED);
+
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(request_to("/users/import", Some("abc"), "a long stream"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,7 +1,13 @@
 // presentation/pres_web_axum/src/middleware/mod.rs
 
+pub mod api_key_middleware;
+pub mod auth_middleware;
+pub mod cors;
+pub mod idempotency;
+pub mod rate_limit;
+pub mod request_limits;
 pub mod telemetry_middleware;
+pub mod tenant;
 
 // Potentially other middlewares can be added here later
-// pub mod auth_middleware;
 // pub mod logging_middleware;
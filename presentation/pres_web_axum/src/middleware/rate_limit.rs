@@ -0,0 +1,458 @@
+// presentation/pres_web_axum/src/middleware/rate_limit.rs
+
+//! 限流
+//!
+//! 依設定的策略表限流：每條策略對應一組路由（與 HTTP 方法），並決定以什麼作為配額單位
+//! （來源 IP、受信任代理轉送的 IP、API 金鑰或用戶）。第一條相符的策略生效。
+//! 回應帶有 `RateLimit-*` 標頭，被拒絕時回傳 429 與 `Retry-After`。
+//! 健康檢查端點永遠不限流。
+//!
+//! 限流層必須放在驗證中介層之內，才能看到 [`AuthenticatedApiKey`] 與 [`AuthenticatedPrincipal`]。
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use axum::{
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    http::{header::RETRY_AFTER, HeaderMap, HeaderName, HeaderValue, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use contracts::{AppError, DynObservability, DynRateLimitStore, RateLimitDecision, RateLimitQuota};
+
+use crate::{
+    error::ApiError,
+    middleware::{
+        api_key_middleware::AuthenticatedApiKey, auth_middleware::AuthenticatedPrincipal,
+    },
+    versioning::unversioned_route,
+};
+
+/// 健康檢查路由的前綴，這些路由不受任何策略限制
+const EXEMPT_ROUTE_PREFIX: &str = "/healthz/";
+
+const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+const RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+const RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+const RATELIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+const RATELIMIT_POLICY: HeaderName = HeaderName::from_static("ratelimit-policy");
+
+/// 配額的計算單位
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitKeyKind {
+    /// 連線的對端 IP
+    Ip,
+    /// 受信任代理寫入 `X-Forwarded-For` 的用戶端 IP
+    ForwardedIp,
+    /// 已驗證的 API 金鑰；沒有金鑰時退回用戶端 IP
+    ApiKey,
+    /// 已驗證的用戶（token subject）；未驗證時退回用戶端 IP
+    User,
+}
+
+impl RateLimitKeyKind {
+    fn label(self) -> &'static str {
+        match self {
+            RateLimitKeyKind::Ip => "ip",
+            RateLimitKeyKind::ForwardedIp => "forwarded_ip",
+            RateLimitKeyKind::ApiKey => "api_key",
+            RateLimitKeyKind::User => "user",
+        }
+    }
+}
+
+/// 單條限流策略
+///
+/// `route` 比對不含版本前綴的路由樣板（例如 `/users/{id}` 同時適用 `/v1/users/{id}`），
+/// 結尾的 `*` 代表前綴比對，單獨的 `*` 符合所有路由。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitPolicy {
+    pub route: String,
+    /// 留空表示所有方法
+    pub methods: Vec<Method>,
+    pub key: RateLimitKeyKind,
+    pub quota: RateLimitQuota,
+}
+
+impl RateLimitPolicy {
+    pub fn new(route: impl Into<String>, key: RateLimitKeyKind, quota: RateLimitQuota) -> Self {
+        Self {
+            route: route.into(),
+            methods: Vec::new(),
+            key,
+            quota,
+        }
+    }
+
+    pub fn with_methods(mut self, methods: Vec<Method>) -> Self {
+        self.methods = methods;
+        self
+    }
+
+    fn matches(&self, method: &Method, route: &str) -> bool {
+        let route_matches = match self.route.strip_suffix('*') {
+            Some(prefix) => route.starts_with(prefix),
+            None => self.route == route,
+        };
+        route_matches && (self.methods.is_empty() || self.methods.contains(method))
+    }
+
+    /// `RateLimit-Policy` 標頭值，例如 `100;w=60`
+    fn header_value(&self) -> String {
+        format!("{};w={}", self.quota.requests, self.quota.window.as_secs())
+    }
+}
+
+/// 依策略表限流的中介層狀態
+pub struct RateLimiter {
+    store: DynRateLimitStore,
+    observability: DynObservability,
+    policies: Vec<RateLimitPolicy>,
+    trusted_proxy_hops: usize,
+}
+
+impl RateLimiter {
+    pub fn new(store: DynRateLimitStore, observability: DynObservability) -> Self {
+        Self {
+            store,
+            observability,
+            policies: Vec::new(),
+            trusted_proxy_hops: 0,
+        }
+    }
+
+    /// 依序比對的策略表
+    pub fn with_policies(mut self, policies: Vec<RateLimitPolicy>) -> Self {
+        self.policies = policies;
+        self
+    }
+
+    /// 服務前方受信任的代理層數；為 0 時不採信 `X-Forwarded-For`
+    pub fn with_trusted_proxy_hops(mut self, hops: usize) -> Self {
+        self.trusted_proxy_hops = hops;
+        self
+    }
+
+    fn policy_for(&self, method: &Method, route: &str) -> Option<(usize, &RateLimitPolicy)> {
+        if route.starts_with(EXEMPT_ROUTE_PREFIX) {
+            return None;
+        }
+        self.policies
+            .iter()
+            .enumerate()
+            .find(|(_, policy)| policy.matches(method, route))
+    }
+
+    /// 計算請求的限流鍵；無法判斷來源時回傳 `None`（不限流）
+    fn key_for(&self, kind: RateLimitKeyKind, req: &Request) -> Option<String> {
+        let identity = match kind {
+            RateLimitKeyKind::Ip | RateLimitKeyKind::ForwardedIp => None,
+            RateLimitKeyKind::ApiKey => req
+                .extensions()
+                .get::<AuthenticatedApiKey>()
+                .map(|key| format!("key:{}", key.id)),
+            RateLimitKeyKind::User => req
+                .extensions()
+                .get::<AuthenticatedPrincipal>()
+                .map(|principal| format!("sub:{}", principal.subject)),
+        };
+        if identity.is_some() {
+            return identity;
+        }
+
+        let ip = match kind {
+            RateLimitKeyKind::Ip => peer_ip(req),
+            _ => self.client_ip(req),
+        };
+        ip.map(|ip| format!("ip:{ip}"))
+    }
+
+    /// 有受信任代理時取代理記錄的用戶端 IP，否則為對端 IP
+    fn client_ip(&self, req: &Request) -> Option<IpAddr> {
+        if self.trusted_proxy_hops > 0 {
+            if let Some(ip) = forwarded_ip(req.headers(), self.trusted_proxy_hops) {
+                return Some(ip);
+            }
+        }
+        peer_ip(req)
+    }
+}
+
+fn peer_ip(req: &Request) -> Option<IpAddr> {
+    req.extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+}
+
+/// 每層代理都會在 `X-Forwarded-For` 尾端加上它看到的來源，
+/// 因此從右數第 `hops` 個才是最外層受信任代理看到的用戶端；更左邊的值可能是偽造的。
+fn forwarded_ip(headers: &HeaderMap, hops: usize) -> Option<IpAddr> {
+    let hops_seen: Vec<&str> = headers
+        .get_all(X_FORWARDED_FOR)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|hop| !hop.is_empty())
+        .collect();
+    let index = hops_seen.len().checked_sub(hops)?;
+    hops_seen[index].parse().ok()
+}
+
+fn insert_headers(headers: &mut HeaderMap, policy: &RateLimitPolicy, decision: &RateLimitDecision) {
+    let reset_secs = ceil_secs(decision.reset_after);
+    headers.insert(RATELIMIT_LIMIT, HeaderValue::from(decision.limit));
+    headers.insert(RATELIMIT_REMAINING, HeaderValue::from(decision.remaining));
+    headers.insert(RATELIMIT_RESET, HeaderValue::from(reset_secs));
+    if let Ok(value) = HeaderValue::from_str(&policy.header_value()) {
+        headers.insert(RATELIMIT_POLICY, value);
+    }
+}
+
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+/// 依第一條相符的策略計數；計數儲存失敗時放行並記錄警告
+pub async fn rate_limit_middleware(
+    State(limiter): State<Arc<RateLimiter>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    // 各版本的同一路由共用策略與配額
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| unversioned_route(path.as_str()).to_owned())
+        .unwrap_or_else(|| unversioned_route(req.uri().path()).to_owned());
+    let Some((index, policy)) = limiter.policy_for(req.method(), &route) else {
+        return next.run(req).await;
+    };
+    let Some(key) = limiter.key_for(policy.key, &req) else {
+        tracing::debug!(route = %route, "Unable to determine rate limit key, skipping");
+        return next.run(req).await;
+    };
+
+    // 鍵包含策略序號，讓每條策略有各自的配額
+    let bucket = format!("{index}:{key}");
+    let decision = match limiter.store.hit(&bucket, policy.quota).await {
+        Ok(decision) => decision,
+        Err(e) => {
+            tracing::warn!(error = %e, "Rate limit store unavailable, allowing request");
+            return next.run(req).await;
+        }
+    };
+
+    if !decision.allowed {
+        limiter
+            .observability
+            .on_rate_limited(req.method().as_str(), &route, policy.key.label())
+            .await;
+        let retry_after = ceil_secs(decision.reset_after);
+        let mut response = ApiError(AppError::TooManyRequests(format!(
+            "Rate limit exceeded, retry in {retry_after}s"
+        )))
+        .into_response();
+        insert_headers(response.headers_mut(), policy, &decision);
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+        return response;
+    }
+
+    let mut response = next.run(req).await;
+    insert_headers(response.headers_mut(), policy, &decision);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use axum::{
+        body::Body,
+        http::StatusCode,
+        middleware,
+        routing::{get, post},
+        Router,
+    };
+    use contracts::{ApiKeyId, ObservabilityPort, TenantId};
+    use infra_memory::InMemoryRateLimitStore;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    #[derive(Default)]
+    struct CountingObservability {
+        rejected: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ObservabilityPort for CountingObservability {
+        async fn on_request_start(&self, _method: &str, _path: &str) {}
+        async fn on_request_end(&self, _method: &str, _path: &str, _status: u16, _latency: f64) {}
+        async fn on_rate_limited(&self, _method: &str, _path: &str, _key: &str) {
+            self.rejected.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn quota(requests: u32) -> RateLimitQuota {
+        RateLimitQuota::new(requests, Duration::from_secs(60))
+    }
+
+    fn app(limiter: RateLimiter) -> Router {
+        Router::new()
+            .route(
+                "/users",
+                get(|| async { "users" }).post(|| async { "created" }),
+            )
+            .route("/users/{id}", get(|| async { "user" }))
+            .route("/v2/users", post(|| async { "created" }))
+            .route("/healthz/live", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(limiter),
+                rate_limit_middleware,
+            ))
+    }
+
+    fn request(method: Method, uri: &str, peer: [u8; 4]) -> Request {
+        let mut req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((peer, 4000))));
+        req
+    }
+
+    #[tokio::test]
+    async fn test_policy_table_and_headers() {
+        let obs = Arc::new(CountingObservability::default());
+        let limiter = RateLimiter::new(Arc::new(InMemoryRateLimitStore::new()), obs.clone())
+            .with_policies(vec![
+                RateLimitPolicy::new("/users", RateLimitKeyKind::Ip, quota(1))
+                    .with_methods(vec![Method::POST]),
+                RateLimitPolicy::new("*", RateLimitKeyKind::Ip, quota(2)),
+            ]);
+        let app = app(limiter);
+
+        let first = app
+            .clone()
+            .oneshot(request(Method::POST, "/users", [10, 0, 0, 1]))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(first.headers()[RATELIMIT_LIMIT], "1");
+        assert_eq!(first.headers()[RATELIMIT_REMAINING], "0");
+        assert_eq!(first.headers()[RATELIMIT_POLICY], "1;w=60");
+
+        let rejected = app
+            .clone()
+            .oneshot(request(Method::POST, "/users", [10, 0, 0, 1]))
+            .await
+            .unwrap();
+        assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(rejected.headers().contains_key(RETRY_AFTER));
+        assert_eq!(obs.rejected.load(Ordering::SeqCst), 1);
+
+        // 版本前綴不影響策略比對，各版本共用配額
+        let other_version = app
+            .clone()
+            .oneshot(request(Method::POST, "/v2/users", [10, 0, 0, 1]))
+            .await
+            .unwrap();
+        assert_eq!(other_version.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // 其他路由落到預設策略，配額獨立計算
+        let other = app
+            .clone()
+            .oneshot(request(Method::GET, "/users/1", [10, 0, 0, 1]))
+            .await
+            .unwrap();
+        assert_eq!(other.status(), StatusCode::OK);
+        assert_eq!(other.headers()[RATELIMIT_LIMIT], "2");
+
+        // 不同來源 IP 各自計算
+        let another_client = app
+            .oneshot(request(Method::POST, "/users", [10, 0, 0, 2]))
+            .await
+            .unwrap();
+        assert_eq!(another_client.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_health_checks_are_never_limited() {
+        let limiter = RateLimiter::new(
+            Arc::new(InMemoryRateLimitStore::new()),
+            Arc::new(CountingObservability::default()),
+        )
+        .with_policies(vec![RateLimitPolicy::new(
+            "*",
+            RateLimitKeyKind::Ip,
+            quota(1),
+        )]);
+        let app = app(limiter);
+
+        for _ in 0..3 {
+            let response = app
+                .clone()
+                .oneshot(request(Method::GET, "/healthz/live", [10, 0, 0, 1]))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(!response.headers().contains_key(RATELIMIT_LIMIT));
+        }
+    }
+
+    #[test]
+    fn test_keys_by_forwarded_ip_api_key_and_user() {
+        let limiter = RateLimiter::new(
+            Arc::new(InMemoryRateLimitStore::new()),
+            Arc::new(CountingObservability::default()),
+        )
+        .with_trusted_proxy_hops(1);
+        let mut req = request(Method::GET, "/users", [10, 0, 0, 1]);
+        req.headers_mut().insert(
+            X_FORWARDED_FOR,
+            HeaderValue::from_static("6.6.6.6, 203.0.113.9"),
+        );
+
+        // 只採信最後一層受信任代理記錄的位址，忽略可偽造的左側值
+        assert_eq!(
+            limiter.key_for(RateLimitKeyKind::ForwardedIp, &req),
+            Some("ip:203.0.113.9".to_string())
+        );
+        assert_eq!(
+            limiter.key_for(RateLimitKeyKind::Ip, &req),
+            Some("ip:10.0.0.1".to_string())
+        );
+        assert_eq!(
+            limiter.key_for(RateLimitKeyKind::ApiKey, &req),
+            Some("ip:203.0.113.9".to_string())
+        );
+
+        req.extensions_mut().insert(AuthenticatedApiKey {
+            id: ApiKeyId::from_u128(3),
+            tenant: TenantId::default(),
+        });
+        assert_eq!(
+            limiter.key_for(RateLimitKeyKind::ApiKey, &req),
+            Some(format!("key:{}", ApiKeyId::from_u128(3)))
+        );
+
+        req.extensions_mut().insert(AuthenticatedPrincipal {
+            subject: "alice".to_string(),
+            issuer: None,
+            scopes: Vec::new(),
+            roles: Vec::new(),
+            claims: Default::default(),
+        });
+        assert_eq!(
+            limiter.key_for(RateLimitKeyKind::User, &req),
+            Some("sub:alice".to_string())
+        );
+    }
+}
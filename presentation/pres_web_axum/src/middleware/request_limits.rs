@@ -0,0 +1,465 @@
+// presentation/pres_web_axum/src/middleware/request_limits.rs
+
+//! 請求保護：逾時、主體大小上限與負載卸除
+//!
+//! - 逾時：處理器在期限內沒有產生回應時回傳 504；串流回應只計算到送出標頭為止。
+//! - 主體大小：`Content-Length` 超過上限時直接回傳 413，未宣告長度的主體在讀取超過上限時失敗。
+//! - 負載卸除：同時處理中的請求達到上限時立即回傳 503 與 `Retry-After`，不排隊等待。
+//!
+//! 每條路由可以覆寫逾時與主體上限（例如 SSE 與批次匯入不設逾時），比對規則與限流策略相同；
+//! 批次匯入的請求主體是長時間的串流，沒有覆寫時會在全域逾時後被中斷。
+//! 健康檢查端點不受任何限制。此層應放在驗證層之外，過載時不必先驗證憑證。
+//!
+//! gRPC 請求（`content-type: application/grpc`）的拒絕以 `grpc-status` 回傳：
+//! 逾時為 `DEADLINE_EXCEEDED`、主體過大為 `RESOURCE_EXHAUSTED`、過載為 `UNAVAILABLE`。
+
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request, State},
+    http::{
+        header::{CONTENT_LENGTH, CONTENT_TYPE, RETRY_AFTER},
+        HeaderName, HeaderValue, Method, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use contracts::{AppError, DomainError, DynObservability};
+use http_body_util::Limited;
+use tokio::sync::Semaphore;
+
+use crate::{error::ApiError, versioning::unversioned_route};
+
+/// 健康檢查路由的前綴，這些路由不受任何限制
+const EXEMPT_ROUTE_PREFIX: &str = "/healthz/";
+
+/// 負載卸除時建議用戶端等待的秒數
+const OVERLOADED_RETRY_AFTER_SECS: u64 = 1;
+
+const GRPC_CONTENT_TYPE: &str = "application/grpc";
+const GRPC_STATUS: HeaderName = HeaderName::from_static("grpc-status");
+const GRPC_MESSAGE: HeaderName = HeaderName::from_static("grpc-message");
+
+/// 請求被拒絕的原因，作為指標標籤
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectionReason {
+    Timeout,
+    Overloaded,
+    BodyTooLarge,
+}
+
+impl RejectionReason {
+    fn label(self) -> &'static str {
+        match self {
+            RejectionReason::Timeout => "timeout",
+            RejectionReason::Overloaded => "overloaded",
+            RejectionReason::BodyTooLarge => "body_too_large",
+        }
+    }
+
+    /// 對應的 gRPC 狀態碼
+    fn grpc_status(self) -> u16 {
+        match self {
+            RejectionReason::Timeout => 4,
+            RejectionReason::Overloaded => 14,
+            RejectionReason::BodyTooLarge => 8,
+        }
+    }
+
+    fn into_response(self, grpc: bool, message: String) -> Response {
+        if grpc {
+            // trailers-only 回應：HTTP 200，狀態放在標頭
+            let mut response = Response::new(Body::empty());
+            let headers = response.headers_mut();
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static(GRPC_CONTENT_TYPE));
+            headers.insert(GRPC_STATUS, HeaderValue::from(self.grpc_status()));
+            if let Ok(message) = HeaderValue::from_str(&message) {
+                headers.insert(GRPC_MESSAGE, message);
+            }
+            return response;
+        }
+        match self {
+            RejectionReason::Timeout => ApiError(AppError::Timeout(message)).into_response(),
+            RejectionReason::BodyTooLarge => {
+                ApiError(AppError::PayloadTooLarge(message)).into_response()
+            }
+            RejectionReason::Overloaded => {
+                let mut response = ApiError(AppError::Domain(DomainError::Unavailable { message }))
+                    .into_response();
+                response
+                    .headers_mut()
+                    .insert(RETRY_AFTER, HeaderValue::from(OVERLOADED_RETRY_AFTER_SECS));
+                response
+            }
+        }
+    }
+}
+
+/// 單條路由的覆寫設定
+///
+/// `route` 比對不含版本前綴的路由樣板，結尾的 `*` 代表前綴比對。
+/// 欄位為 `None` 時沿用全域設定；`Some(None)` 代表此路由不設限制。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteLimits {
+    pub route: String,
+    /// 留空表示所有方法
+    pub methods: Vec<Method>,
+    pub timeout: Option<Option<Duration>>,
+    pub max_body_bytes: Option<Option<usize>>,
+}
+
+impl RouteLimits {
+    pub fn new(route: impl Into<String>) -> Self {
+        Self {
+            route: route.into(),
+            methods: Vec::new(),
+            timeout: None,
+            max_body_bytes: None,
+        }
+    }
+
+    pub fn with_methods(mut self, methods: Vec<Method>) -> Self {
+        self.methods = methods;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_max_body_bytes(mut self, bytes: Option<usize>) -> Self {
+        self.max_body_bytes = Some(bytes);
+        self
+    }
+
+    fn matches(&self, method: &Method, route: &str) -> bool {
+        let route_matches = match self.route.strip_suffix('*') {
+            Some(prefix) => route.starts_with(prefix),
+            None => self.route == route,
+        };
+        route_matches && (self.methods.is_empty() || self.methods.contains(method))
+    }
+}
+
+/// 請求保護中介層的狀態
+pub struct RequestLimits {
+    observability: DynObservability,
+    timeout: Option<Duration>,
+    max_body_bytes: Option<usize>,
+    concurrency: Option<Arc<Semaphore>>,
+    routes: Vec<RouteLimits>,
+}
+
+impl RequestLimits {
+    pub fn new(observability: DynObservability) -> Self {
+        Self {
+            observability,
+            timeout: None,
+            max_body_bytes: None,
+            concurrency: None,
+            routes: Vec::new(),
+        }
+    }
+
+    /// 全域逾時；`None` 表示不設逾時
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 全域的請求主體上限；`None` 表示不限制
+    pub fn with_max_body_bytes(mut self, bytes: Option<usize>) -> Self {
+        self.max_body_bytes = bytes;
+        self
+    }
+
+    /// 同時處理的請求上限；`None` 表示不限制
+    pub fn with_max_concurrency(mut self, limit: Option<usize>) -> Self {
+        self.concurrency = limit.map(|limit| Arc::new(Semaphore::new(limit)));
+        self
+    }
+
+    /// 依序比對的路由覆寫，第一條相符的生效
+    pub fn with_routes(mut self, routes: Vec<RouteLimits>) -> Self {
+        self.routes = routes;
+        self
+    }
+
+    fn limits_for(&self, method: &Method, route: &str) -> (Option<Duration>, Option<usize>) {
+        let matched = self.routes.iter().find(|r| r.matches(method, route));
+        (
+            matched.and_then(|r| r.timeout).unwrap_or(self.timeout),
+            matched
+                .and_then(|r| r.max_body_bytes)
+                .unwrap_or(self.max_body_bytes),
+        )
+    }
+
+    async fn reject(&self, method: &Method, route: &str, reason: RejectionReason) {
+        self.observability
+            .on_request_rejected(method.as_str(), route, reason.label())
+            .await;
+    }
+}
+
+fn is_grpc(req: &Request) -> bool {
+    req.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(GRPC_CONTENT_TYPE))
+}
+
+fn content_length(req: &Request) -> Option<usize> {
+    req.headers()
+        .get(CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// 依路由套用主體上限、負載卸除與逾時
+pub async fn request_limits_middleware(
+    State(limits): State<Arc<RequestLimits>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| unversioned_route(path.as_str()).to_owned())
+        .unwrap_or_else(|| unversioned_route(req.uri().path()).to_owned());
+    if route.starts_with(EXEMPT_ROUTE_PREFIX) {
+        return next.run(req).await;
+    }
+    let method = req.method().clone();
+    let grpc = is_grpc(&req);
+    let (timeout, max_body_bytes) = limits.limits_for(&method, &route);
+
+    let req = match max_body_bytes {
+        Some(max) if content_length(&req).is_some_and(|len| len > max) => {
+            limits
+                .reject(&method, &route, RejectionReason::BodyTooLarge)
+                .await;
+            return RejectionReason::BodyTooLarge
+                .into_response(grpc, format!("Request body exceeds {max} bytes"));
+        }
+        Some(max) => req.map(|body| Body::new(Limited::new(body, max))),
+        None => req,
+    };
+
+    // 許可在回應標頭送出時釋放，長時間的串流不佔用名額
+    let _permit = match &limits.concurrency {
+        Some(semaphore) => match semaphore.clone().try_acquire_owned() {
+            Ok(permit) => Some(permit),
+            Err(_) => {
+                limits
+                    .reject(&method, &route, RejectionReason::Overloaded)
+                    .await;
+                return RejectionReason::Overloaded
+                    .into_response(grpc, "Server is overloaded, retry later".to_string());
+            }
+        },
+        None => None,
+    };
+
+    let response = match timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, next.run(req)).await {
+            Ok(response) => response,
+            Err(_) => {
+                limits
+                    .reject(&method, &route, RejectionReason::Timeout)
+                    .await;
+                return RejectionReason::Timeout.into_response(
+                    grpc,
+                    format!("Request did not complete within {}ms", timeout.as_millis()),
+                );
+            }
+        },
+        None => next.run(req).await,
+    };
+
+    // 未宣告長度的主體在處理器讀取時才超過上限
+    if max_body_bytes.is_some() && response.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        limits
+            .reject(&method, &route, RejectionReason::BodyTooLarge)
+            .await;
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use axum::{
+        body::Bytes,
+        middleware,
+        routing::{get, post},
+        Json, Router,
+    };
+    use contracts::ObservabilityPort;
+    use std::sync::Mutex;
+    use tower::ServiceExt;
+
+    #[derive(Default)]
+    struct RecordingObservability {
+        rejected: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl ObservabilityPort for RecordingObservability {
+        async fn on_request_start(&self, _method: &str, _path: &str) {}
+        async fn on_request_end(&self, _method: &str, _path: &str, _status: u16, _latency: f64) {}
+        async fn on_request_rejected(&self, _method: &str, path: &str, reason: &str) {
+            self.rejected
+                .lock()
+                .unwrap()
+                .push(format!("{path} {reason}"));
+        }
+    }
+
+    async fn slow() -> &'static str {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        "done"
+    }
+
+    fn app(limits: RequestLimits) -> Router {
+        Router::new()
+            .route("/slow", get(slow).post(slow))
+            .route("/events", get(slow))
+            .route(
+                "/echo",
+                post(|body: Bytes| async move { body.len().to_string() }),
+            )
+            .route(
+                "/json",
+                post(|payload: Result<Json<serde_json::Value>, _>| async move {
+                    payload.map(|_| "ok").map_err(ApiError::from)
+                }),
+            )
+            .route("/healthz/live", get(slow))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(limits),
+                request_limits_middleware,
+            ))
+    }
+
+    fn request(method: Method, uri: &str, body: Body) -> Request {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(body)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_use_route_overrides() {
+        let obs = Arc::new(RecordingObservability::default());
+        let app = app(RequestLimits::new(obs.clone())
+            .with_timeout(Some(Duration::from_millis(20)))
+            .with_routes(vec![RouteLimits::new("/events").with_timeout(None)]));
+
+        let response = app
+            .clone()
+            .oneshot(request(Method::GET, "/slow", Body::empty()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        for uri in ["/events", "/healthz/live"] {
+            let response = app
+                .clone()
+                .oneshot(request(Method::GET, uri, Body::empty()))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+        assert_eq!(*obs.rejected.lock().unwrap(), ["/slow timeout"]);
+    }
+
+    #[tokio::test]
+    async fn test_body_limit_rejects_declared_and_streamed_bodies() {
+        let obs = Arc::new(RecordingObservability::default());
+        let app = app(RequestLimits::new(obs.clone()).with_max_body_bytes(Some(4)));
+
+        let response = app
+            .clone()
+            .oneshot(request(Method::POST, "/echo", Body::from("1234")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(request(Method::POST, "/echo", Body::from("12345")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // 沒有 Content-Length 的串流主體在讀取時超過上限
+        let chunks = futures_util::stream::iter(
+            ["[1,", "2,3]"].map(|chunk| Ok::<_, std::io::Error>(Bytes::from(chunk))),
+        );
+        let mut req = request(Method::POST, "/json", Body::from_stream(chunks));
+        req.headers_mut().insert(
+            axum::http::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        let response = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            *obs.rejected.lock().unwrap(),
+            ["/echo body_too_large", "/json body_too_large"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_excess_concurrent_requests_are_shed() {
+        let obs = Arc::new(RecordingObservability::default());
+        let app = app(RequestLimits::new(obs.clone()).with_max_concurrency(Some(1)));
+
+        let first = tokio::spawn(
+            app.clone()
+                .oneshot(request(Method::GET, "/slow", Body::empty())),
+        );
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let shed = app
+            .clone()
+            .oneshot(request(Method::GET, "/slow", Body::empty()))
+            .await
+            .unwrap();
+
+        assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(shed.headers()[RETRY_AFTER], "1");
+        assert_eq!(first.await.unwrap().unwrap().status(), StatusCode::OK);
+        assert_eq!(*obs.rejected.lock().unwrap(), ["/slow overloaded"]);
+    }
+
+    #[tokio::test]
+    async fn test_grpc_rejections_use_grpc_status() {
+        let obs = Arc::new(RecordingObservability::default());
+        let app = app(RequestLimits::new(obs.clone())
+            .with_timeout(Some(Duration::from_millis(20)))
+            .with_max_body_bytes(Some(4)));
+        let grpc = |uri: &str, body: &'static str| {
+            let mut req = request(Method::POST, uri, Body::from(body));
+            req.headers_mut()
+                .insert(CONTENT_TYPE, HeaderValue::from_static("application/grpc"));
+            req.headers_mut()
+                .insert(CONTENT_LENGTH, HeaderValue::from(body.len()));
+            req
+        };
+
+        let response = app.clone().oneshot(grpc("/slow", "")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/grpc");
+        assert_eq!(response.headers()[GRPC_STATUS], "4");
+
+        let response = app.oneshot(grpc("/echo", "12345")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[GRPC_STATUS], "8");
+    }
+}
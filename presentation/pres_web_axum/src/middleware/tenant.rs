@@ -0,0 +1,409 @@
+// presentation/pres_web_axum/src/middleware/tenant.rs
+
+//! 租戶解析
+//!
+//! 依設定的來源（標頭、子網域或 JWT claim）決定請求所屬的租戶，放入請求擴充供
+//! [`CurrentCaller`](crate::middleware::auth_middleware::CurrentCaller)、gRPC 與 GraphQL 使用。
+//! 多個來源都有值時必須一致，避免以 A 租戶的 token 存取 B 租戶；
+//! 沒有任何來源且未設定預設租戶時回傳 400。
+//!
+//! 憑證綁定的租戶優先於標頭與子網域：API 金鑰一律屬於其租戶；設定了 JWT claim 來源時，
+//! 已驗證但沒有該 claim 的 token 回傳 403。沒有綁定租戶的已驗證呼叫者（未設定 claim 來源）
+//! 與未驗證的請求（設定了 claim 來源時）只能使用預設租戶，
+//! 標頭與子網域只能用來確認，不能替呼叫者選擇租戶。
+//!
+//! 此層必須放在驗證中介層之內，才能讀取 [`AuthenticatedPrincipal`] 與 [`AuthenticatedApiKey`]。
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::{header::HOST, HeaderName},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use contracts::{AppError, DomainError, TenantId};
+
+use crate::{
+    error::ApiError,
+    middleware::{
+        api_key_middleware::AuthenticatedApiKey, auth_middleware::AuthenticatedPrincipal,
+    },
+};
+
+pub const X_TENANT_ID: HeaderName = HeaderName::from_static("x-tenant-id");
+
+/// 租戶識別碼的來源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TenantSource {
+    /// 指定標頭的值
+    Header(HeaderName),
+    /// `Host` 在 `base_domain` 之下的第一層子網域，例如 `acme.example.com`
+    Subdomain { base_domain: String },
+    /// 已驗證 JWT 中的字串 claim
+    JwtClaim(String),
+}
+
+impl TenantSource {
+    fn label(&self) -> String {
+        match self {
+            TenantSource::Header(name) => format!("header {name}"),
+            TenantSource::Subdomain { .. } => "subdomain".to_string(),
+            TenantSource::JwtClaim(claim) => format!("claim {claim}"),
+        }
+    }
+
+    fn extract(&self, req: &Request) -> Option<String> {
+        match self {
+            TenantSource::Header(name) => req
+                .headers()
+                .get(name)
+                .map(|value| value.to_str().unwrap_or_default().trim().to_owned()),
+            TenantSource::Subdomain { base_domain } => {
+                let host = req
+                    .headers()
+                    .get(HOST)
+                    .and_then(|value| value.to_str().ok())
+                    .or_else(|| req.uri().host())?;
+                subdomain(host, base_domain)
+            }
+            TenantSource::JwtClaim(claim) => req
+                .extensions()
+                .get::<AuthenticatedPrincipal>()?
+                .claims
+                .get(claim)
+                .map(|value| value.as_str().unwrap_or_default().to_owned()),
+        }
+    }
+}
+
+/// 取出 `host`（可含連接埠）在 `base_domain` 之下的子網域；不在其下或就是基底網域時為 `None`
+fn subdomain(host: &str, base_domain: &str) -> Option<String> {
+    let host = host.rsplit_once(':').map_or(host, |(name, _)| name);
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    let base_domain = base_domain.trim_matches('.').to_ascii_lowercase();
+    host.strip_suffix(&base_domain)?
+        .strip_suffix('.')
+        .map(str::to_owned)
+}
+
+/// 租戶中介層的狀態
+pub struct TenantResolver {
+    sources: Vec<TenantSource>,
+    default_tenant: Option<TenantId>,
+}
+
+impl TenantResolver {
+    pub fn new(sources: Vec<TenantSource>) -> Self {
+        Self {
+            sources,
+            default_tenant: None,
+        }
+    }
+
+    /// 沒有任何來源指定租戶時使用的租戶；未設定時這類請求回傳 400
+    pub fn with_default_tenant(mut self, tenant: Option<TenantId>) -> Self {
+        self.default_tenant = tenant;
+        self
+    }
+
+    /// 是否以 JWT claim 綁定租戶；此時標頭與子網域不能替呼叫者選擇租戶
+    fn binds_jwt_claim(&self) -> bool {
+        self.sources
+            .iter()
+            .any(|source| matches!(source, TenantSource::JwtClaim(_)))
+    }
+
+    /// 呼叫者憑證所屬的租戶：API 金鑰的租戶，或設定了 claim 來源時 JWT 的 claim
+    fn credential_tenant(&self, req: &Request) -> Result<Option<(TenantId, String)>, AppError> {
+        if let Some(key) = req.extensions().get::<AuthenticatedApiKey>() {
+            return Ok(Some((key.tenant.clone(), "API key".to_string())));
+        }
+        let Some(source) = self
+            .sources
+            .iter()
+            .find(|source| matches!(source, TenantSource::JwtClaim(_)))
+        else {
+            return Ok(None);
+        };
+        if req.extensions().get::<AuthenticatedPrincipal>().is_none() {
+            return Ok(None);
+        }
+        let raw = source.extract(req).ok_or_else(|| {
+            AppError::Domain(DomainError::Forbidden {
+                message: format!("Token does not carry a tenant ({})", source.label()),
+            })
+        })?;
+        let tenant = TenantId::parse(raw).map_err(AppError::Domain)?;
+        Ok(Some((tenant, source.label())))
+    }
+
+    fn resolve(&self, req: &Request) -> Result<TenantId, AppError> {
+        let credential = self.credential_tenant(req)?;
+        let mut resolved = credential.clone();
+        for source in &self.sources {
+            if matches!(source, TenantSource::JwtClaim(_)) {
+                continue;
+            }
+            let Some(raw) = source.extract(req) else {
+                continue;
+            };
+            let tenant = TenantId::parse(raw).map_err(AppError::Domain)?;
+            match &resolved {
+                Some((existing, from)) if existing != &tenant => {
+                    return Err(AppError::Domain(DomainError::Forbidden {
+                        message: format!(
+                            "Tenant {tenant} from {} does not match tenant {existing} from {from}",
+                            source.label()
+                        ),
+                    }));
+                }
+                Some(_) => {}
+                None => resolved = Some((tenant, source.label())),
+            }
+        }
+
+        // 憑證沒有綁定租戶時，不能以標頭或子網域選擇預設租戶以外的租戶
+        let authenticated = req.extensions().get::<AuthenticatedPrincipal>().is_some();
+        if credential.is_none() && (authenticated || self.binds_jwt_claim()) {
+            if let Some((tenant, from)) = &resolved {
+                if self.default_tenant.as_ref() != Some(tenant) {
+                    return Err(if authenticated {
+                        AppError::Domain(DomainError::Forbidden {
+                            message: format!(
+                                "Tenant {tenant} from {from} is not bound to the caller's credential"
+                            ),
+                        })
+                    } else {
+                        AppError::Unauthorized(format!(
+                            "Tenant {tenant} from {from} requires authentication"
+                        ))
+                    });
+                }
+            }
+        }
+
+        resolved
+            .map(|(tenant, _)| tenant)
+            .or_else(|| self.default_tenant.clone())
+            .ok_or_else(|| AppError::Validation("Request does not identify a tenant".to_string()))
+    }
+}
+
+/// 解析租戶並放入請求擴充
+pub async fn tenant_middleware(
+    State(resolver): State<Arc<TenantResolver>>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    match resolver.resolve(&req) {
+        Ok(tenant) => {
+            req.extensions_mut().insert(tenant);
+            next.run(req).await
+        }
+        Err(e) => ApiError(e).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, middleware, routing::get, Extension, Router};
+    use serde_json::{json, Map};
+    use tower::ServiceExt;
+
+    fn app(resolver: TenantResolver) -> Router {
+        Router::new()
+            .route(
+                "/tenant",
+                get(|Extension(tenant): Extension<TenantId>| async move { tenant.to_string() }),
+            )
+            .layer(middleware::from_fn_with_state(
+                Arc::new(resolver),
+                tenant_middleware,
+            ))
+    }
+
+    fn principal(tenant: Option<&str>) -> AuthenticatedPrincipal {
+        let claims: Map<String, serde_json::Value> = tenant
+            .map(|tenant| ("tenant_id".to_string(), json!(tenant)))
+            .into_iter()
+            .collect();
+        AuthenticatedPrincipal {
+            subject: "alice".to_string(),
+            issuer: None,
+            scopes: Vec::new(),
+            roles: Vec::new(),
+            claims,
+        }
+    }
+
+    fn api_key(tenant: &str) -> AuthenticatedApiKey {
+        AuthenticatedApiKey {
+            id: contracts::ApiKeyId::from_u128(1),
+            tenant: TenantId::parse(tenant).unwrap(),
+        }
+    }
+
+    async fn call(app: &Router, req: Request) -> (StatusCode, String) {
+        let response = app.clone().oneshot(req).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[test]
+    fn test_subdomain_extraction() {
+        assert_eq!(
+            subdomain("acme.example.com:8080", "example.com").as_deref(),
+            Some("acme")
+        );
+        assert_eq!(
+            subdomain("ACME.Example.com.", ".example.com").as_deref(),
+            Some("acme")
+        );
+        assert_eq!(subdomain("example.com", "example.com"), None);
+        assert_eq!(subdomain("acme.notexample.com", "example.com"), None);
+    }
+
+    #[tokio::test]
+    async fn test_sources_must_agree_and_default_applies() {
+        let app = app(TenantResolver::new(vec![
+            TenantSource::JwtClaim("tenant_id".to_string()),
+            TenantSource::Header(X_TENANT_ID),
+            TenantSource::Subdomain {
+                base_domain: "example.com".to_string(),
+            },
+        ])
+        .with_default_tenant(Some(TenantId::default())));
+        let request = |host: &str, header: Option<&str>| {
+            let mut builder = Request::builder().uri("/tenant").header(HOST, host);
+            if let Some(tenant) = header {
+                builder = builder.header(X_TENANT_ID, tenant);
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        let mut req = request("acme.example.com", Some("acme"));
+        req.extensions_mut().insert(principal(Some("acme")));
+        assert_eq!(call(&app, req).await, (StatusCode::OK, "acme".to_string()));
+        assert_eq!(
+            call(&app, request("localhost", None)).await,
+            (StatusCode::OK, "default".to_string())
+        );
+
+        // 未驗證的請求不能自行選擇租戶
+        let (status, _) = call(&app, request("acme.example.com", Some("acme"))).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // 標頭與子網域不一致
+        let (status, _) = call(&app, request("acme.example.com", Some("globex"))).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // token 屬於 acme 卻要求 globex
+        let mut req = request("localhost", Some("globex"));
+        req.extensions_mut().insert(principal(Some("acme")));
+        let (status, _) = call(&app, req).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, _) = call(&app, request("localhost", Some("Not A Tenant"))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_token_without_tenant_claim_is_rejected() {
+        let app = app(TenantResolver::new(vec![
+            TenantSource::JwtClaim("tenant_id".to_string()),
+            TenantSource::Header(X_TENANT_ID),
+        ])
+        .with_default_tenant(Some(TenantId::default())));
+
+        for header in [Some("globex"), None] {
+            let mut builder = Request::builder().uri("/tenant");
+            if let Some(tenant) = header {
+                builder = builder.header(X_TENANT_ID, tenant);
+            }
+            let mut req = builder.body(Body::empty()).unwrap();
+            req.extensions_mut().insert(principal(None));
+            let (status, _) = call(&app, req).await;
+            assert_eq!(status, StatusCode::FORBIDDEN);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_key_is_bound_to_its_tenant() {
+        let app = app(TenantResolver::new(vec![
+            TenantSource::JwtClaim("tenant_id".to_string()),
+            TenantSource::Header(X_TENANT_ID),
+        ]));
+        let request = |header: Option<&str>| {
+            let mut builder = Request::builder().uri("/tenant");
+            if let Some(tenant) = header {
+                builder = builder.header(X_TENANT_ID, tenant);
+            }
+            let mut req = builder.body(Body::empty()).unwrap();
+            // API 金鑰驗證同時放入主體與金鑰；主體的 claim 不影響金鑰的租戶
+            req.extensions_mut().insert(principal(None));
+            req.extensions_mut().insert(api_key("acme"));
+            req
+        };
+
+        assert_eq!(
+            call(&app, request(None)).await,
+            (StatusCode::OK, "acme".to_string())
+        );
+        assert_eq!(
+            call(&app, request(Some("acme"))).await,
+            (StatusCode::OK, "acme".to_string())
+        );
+        let (status, _) = call(&app, request(Some("globex"))).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_header_cannot_pick_tenant_for_authenticated_callers() {
+        let app = app(TenantResolver::new(vec![TenantSource::Header(X_TENANT_ID)])
+            .with_default_tenant(Some(TenantId::default())));
+        let request = |tenant: &str| {
+            Request::builder()
+                .uri("/tenant")
+                .header(X_TENANT_ID, tenant)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // 沒有 claim 來源時 token 不綁定租戶，只能使用預設租戶
+        let mut req = request("globex");
+        req.extensions_mut().insert(principal(Some("globex")));
+        let (status, _) = call(&app, req).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let mut req = request("default");
+        req.extensions_mut().insert(principal(None));
+        assert_eq!(
+            call(&app, req).await,
+            (StatusCode::OK, "default".to_string())
+        );
+
+        // API 金鑰不論來源設定都綁定其租戶
+        let mut req = request("globex");
+        req.extensions_mut().insert(principal(None));
+        req.extensions_mut().insert(api_key("acme"));
+        let (status, _) = call(&app, req).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_missing_tenant_without_default_is_rejected() {
+        let app = app(TenantResolver::new(vec![TenantSource::Header(X_TENANT_ID)]));
+        let req = Request::builder()
+            .uri("/tenant")
+            .body(Body::empty())
+            .unwrap();
+
+        let (status, _) = call(&app, req).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,142 @@
+//=== OpenAPI Document ===//
+
+use axum::{routing::get, Json, Router};
+use utoipa::{
+    openapi::security::{Http, HttpAuthScheme, SecurityScheme},
+    Modify, OpenApi,
+};
+
+use crate::{dtos, error, handlers};
+
+/// 提交在 repo 中的規格檔路徑（相對於本 crate），前端依此產生 TypeScript 客戶端
+pub const SPEC_PATH: &str = "openapi.json";
+
+/// 由處理器與 DTO 產生的 OpenAPI 3.1 文件
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Axum Hexagonal API",
+        description = "Operations are documented under `/v1`. `/v2` serves the same operations with the `*V2` schemas; unversioned paths pick a version from `Accept: application/vnd.x.v2+json` and default to v1."
+    ),
+    paths(
+        handlers::user::create_user_handler,
+        handlers::user::list_users_handler,
+        handlers::user::get_user_handler,
+        handlers::user::update_user_handler,
+        handlers::user::delete_user_handler,
+        handlers::user::restore_user_handler,
+        handlers::bulk::import_users_handler,
+        handlers::bulk::export_users_handler,
+        handlers::events::user_events_handler,
+        handlers::api_key::create_api_key_handler,
+        handlers::api_key::rotate_api_key_handler,
+        handlers::api_key::revoke_api_key_handler,
+        handlers::auth::me_handler,
+        handlers::health::live_handler,
+        handlers::health::ready_handler,
+    ),
+    components(schemas(
+        dtos::CreateUserRequest,
+        dtos::UpdateUserRequest,
+        dtos::UserResponse,
+        dtos::UserListResponse,
+        dtos::ImportReportResponse,
+        dtos::ImportRowReport,
+        dtos::ImportRowStatus,
+        dtos::CreateApiKeyRequest,
+        dtos::ApiKeyResponse,
+        dtos::IssuedApiKeyResponse,
+        dtos::v2::UserResponse,
+        dtos::v2::UserListResponse,
+        error::ErrorResponse,
+        error::ErrorBody,
+    )),
+    modifiers(&BearerAuth),
+    security(("bearer_auth" = [])),
+    tags(
+        (name = "users", description = "User management"),
+        (name = "api-keys", description = "API key administration"),
+        (name = "auth", description = "Caller identity"),
+        (name = "health", description = "Liveness and readiness probes"),
+    )
+)]
+pub struct ApiDoc;
+
+/// JWT 與 API 金鑰都以 `Authorization: Bearer` 傳遞
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+        );
+    }
+}
+
+/// 產生與提交檔案相同格式的規格（含結尾換行）
+pub fn spec_json() -> String {
+    let mut json = ApiDoc::openapi()
+        .to_pretty_json()
+        .expect("OpenAPI document is always serializable");
+    json.push('\n');
+    json
+}
+
+/// GET /openapi.json - 回傳 OpenAPI 文件
+pub async fn openapi_handler() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// 提供 `/openapi.json`，啟用 `docs-ui` 功能時另外在 `/docs` 提供文件介面
+pub fn router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let router = Router::new().route("/openapi.json", get(openapi_handler));
+
+    #[cfg(feature = "docs-ui")]
+    let router = {
+        use utoipa_scalar::{Scalar, Servable};
+        router.merge(Scalar::with_url("/docs", ApiDoc::openapi()))
+    };
+
+    router
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 規格與程式碼不一致時失敗；以 `UPDATE_OPENAPI=1 cargo test -p pres_web_axum` 重新產生
+    #[test]
+    fn test_committed_spec_matches_code() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(SPEC_PATH);
+        let generated = spec_json();
+
+        if std::env::var_os("UPDATE_OPENAPI").is_some() {
+            std::fs::write(&path, &generated).expect("write openapi.json");
+            return;
+        }
+
+        let committed = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            committed == generated,
+            "{} is out of date; run `UPDATE_OPENAPI=1 cargo test -p pres_web_axum` and commit the result",
+            path.display()
+        );
+    }
+
+    #[test]
+    fn test_spec_is_openapi_3_1_with_error_envelope() {
+        let spec: serde_json::Value = serde_json::from_str(&spec_json()).unwrap();
+
+        assert!(spec["openapi"].as_str().unwrap().starts_with("3.1"));
+        assert_eq!(
+            spec["components"]["schemas"]["ErrorResponse"]["required"],
+            serde_json::json!(["error"])
+        );
+        assert!(spec["paths"]["/v1/users/{id}"]["put"].is_object());
+    }
+}
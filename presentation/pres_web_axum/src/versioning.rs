@@ -0,0 +1,319 @@
+//=== API Versioning ===//
+
+use std::{collections::HashMap, convert::Infallible, sync::Arc};
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{
+        header::{ACCEPT, LINK, VARY},
+        request::Parts,
+        HeaderMap, HeaderName, HeaderValue,
+    },
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+use time::{
+    format_description::{well_known::Rfc3339, BorrowedFormatItem},
+    macros::format_description,
+    OffsetDateTime, UtcOffset,
+};
+
+/// RFC 9745
+const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+/// RFC 8594
+const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// `Sunset` 使用的 HTTP-date（IMF-fixdate）格式
+const HTTP_DATE: &[BorrowedFormatItem<'static>] = format_description!(
+    "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+);
+
+/// 以 `Accept` 指定版本時使用的媒體類型前綴，例如 `application/vnd.x.v2+json`
+const VENDOR_MEDIA_TYPE_PREFIX: &str = "application/vnd.x.";
+
+/// API 版本
+///
+/// 同一組處理器掛載在每個版本的前綴下，處理器依版本選擇 DTO。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ApiVersion {
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    pub const ALL: [ApiVersion; 2] = [ApiVersion::V1, ApiVersion::V2];
+
+    /// 未指定版本的請求使用的版本
+    pub const DEFAULT: ApiVersion = ApiVersion::V1;
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "v1",
+            ApiVersion::V2 => "v2",
+        }
+    }
+
+    pub fn parse(version: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|v| v.as_str() == version)
+    }
+
+    /// 依 `Accept` 中的廠商媒體類型選擇版本，沒有或不認得時使用預設版本
+    pub fn negotiate(headers: &HeaderMap) -> Self {
+        headers
+            .get_all(ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|media_type| {
+                let essence = media_type.split(';').next()?.trim().to_ascii_lowercase();
+                let version = essence
+                    .strip_prefix(VENDOR_MEDIA_TYPE_PREFIX)?
+                    .strip_suffix("+json")?
+                    .to_owned();
+                Self::parse(&version)
+            })
+            .next()
+            .unwrap_or(Self::DEFAULT)
+    }
+}
+
+/// 由版本中介層放入請求；處理器直接以參數取得
+impl<S: Send + Sync> FromRequestParts<S> for ApiVersion {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<ApiVersion>()
+            .copied()
+            .unwrap_or(Self::DEFAULT))
+    }
+}
+
+/// 已棄用版本的回應標頭
+#[derive(Debug, Clone)]
+pub struct Deprecation {
+    deprecation: HeaderValue,
+    sunset: Option<HeaderValue>,
+    link: Option<HeaderValue>,
+}
+
+impl Deprecation {
+    /// 時間為 RFC 3339 格式；`link` 指向遷移說明
+    pub fn parse(
+        deprecated_at: &str,
+        sunset_at: Option<&str>,
+        link: Option<&str>,
+    ) -> Result<Self, String> {
+        let deprecated_at = parse_rfc3339("deprecated_at", deprecated_at)?;
+        let sunset_at = sunset_at
+            .map(|at| parse_rfc3339("sunset_at", at))
+            .transpose()?;
+        if sunset_at.is_some_and(|sunset_at| sunset_at < deprecated_at) {
+            return Err("sunset_at must not be earlier than deprecated_at".to_string());
+        }
+        let link = link
+            .map(|link| {
+                HeaderValue::try_from(format!("<{link}>; rel=\"deprecation\"; type=\"text/html\""))
+                    .map_err(|_| format!("Invalid deprecation link {link:?}"))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            deprecation: HeaderValue::try_from(format!("@{}", deprecated_at.unix_timestamp()))
+                .expect("a timestamp is a valid header value"),
+            sunset: sunset_at.map(|at| {
+                HeaderValue::try_from(
+                    at.format(HTTP_DATE)
+                        .expect("HTTP-date formatting of a valid timestamp cannot fail"),
+                )
+                .expect("an HTTP-date is a valid header value")
+            }),
+            link,
+        })
+    }
+
+    fn apply(&self, headers: &mut HeaderMap) {
+        headers.insert(DEPRECATION, self.deprecation.clone());
+        if let Some(sunset) = &self.sunset {
+            headers.insert(SUNSET, sunset.clone());
+        }
+        if let Some(link) = &self.link {
+            headers.append(LINK, link.clone());
+        }
+    }
+}
+
+fn parse_rfc3339(field: &str, at: &str) -> Result<OffsetDateTime, String> {
+    OffsetDateTime::parse(at, &Rfc3339)
+        .map(|at| at.to_offset(UtcOffset::UTC))
+        .map_err(|e| format!("{field} {at:?} is not an RFC 3339 timestamp: {e}"))
+}
+
+/// 各版本的棄用設定；未列出的版本視為仍受支援
+#[derive(Debug, Clone, Default)]
+pub struct VersionPolicies {
+    deprecations: HashMap<ApiVersion, Deprecation>,
+}
+
+impl VersionPolicies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_deprecation(mut self, version: ApiVersion, deprecation: Deprecation) -> Self {
+        self.deprecations.insert(version, deprecation);
+        self
+    }
+}
+
+/// 版本中介層的狀態；`fixed` 為 `None` 時由 `Accept` 協商
+#[derive(Clone)]
+struct VersionRouting {
+    fixed: Option<ApiVersion>,
+    policies: Arc<VersionPolicies>,
+}
+
+async fn api_version_middleware(
+    State(routing): State<VersionRouting>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let version = routing
+        .fixed
+        .unwrap_or_else(|| ApiVersion::negotiate(req.headers()));
+    req.extensions_mut().insert(version);
+
+    let mut response = next.run(req).await;
+    let headers = response.headers_mut();
+    if routing.fixed.is_none() {
+        headers.append(VARY, HeaderValue::from_static("accept"));
+    }
+    if let Some(deprecation) = routing.policies.deprecations.get(&version) {
+        deprecation.apply(headers);
+    }
+    response
+}
+
+/// 把 API 路由掛載到 `/v1`、`/v2`，並在原路徑保留依 `Accept` 協商版本的別名
+pub fn versioned<S>(api: Router<S>, policies: VersionPolicies) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let policies = Arc::new(policies);
+    let layered = |fixed: Option<ApiVersion>| {
+        api.clone().layer(middleware::from_fn_with_state(
+            VersionRouting {
+                fixed,
+                policies: policies.clone(),
+            },
+            api_version_middleware,
+        ))
+    };
+
+    ApiVersion::ALL
+        .into_iter()
+        .fold(layered(None), |router, version| {
+            router.nest(&format!("/{}", version.as_str()), layered(Some(version)))
+        })
+}
+
+/// 去掉路由樣板開頭的版本前綴，讓各版本共用以原路徑設定的策略
+pub fn unversioned_route(route: &str) -> &str {
+    ApiVersion::ALL
+        .into_iter()
+        .find_map(|version| {
+            route
+                .strip_prefix('/')?
+                .strip_prefix(version.as_str())
+                .filter(|rest| rest.starts_with('/'))
+        })
+        .unwrap_or(route)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get};
+    use tower::ServiceExt;
+
+    async fn echo(version: ApiVersion) -> &'static str {
+        version.as_str()
+    }
+
+    fn app() -> Router {
+        let deprecation = Deprecation::parse(
+            "2023-11-14T22:13:20Z",
+            Some("2023-11-15T22:13:20+00:00"),
+            Some("https://example.com/migrating-to-v2"),
+        )
+        .unwrap();
+        let policies = VersionPolicies::new().with_deprecation(ApiVersion::V1, deprecation);
+        versioned(Router::new().route("/users", get(echo)), policies)
+    }
+
+    async fn call(uri: &str, accept: Option<&str>) -> (String, HeaderMap) {
+        let mut request = Request::builder().uri(uri);
+        if let Some(accept) = accept {
+            request = request.header(ACCEPT, accept);
+        }
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let headers = response.headers().clone();
+        let body = axum::body::to_bytes(response.into_body(), 1024)
+            .await
+            .unwrap();
+        (String::from_utf8(body.to_vec()).unwrap(), headers)
+    }
+
+    #[tokio::test]
+    async fn test_path_prefix_selects_version() {
+        let (body, headers) = call("/v2/users", Some("application/vnd.x.v1+json")).await;
+        assert_eq!(body, "v2");
+        assert!(headers.get(DEPRECATION).is_none());
+
+        let (body, headers) = call("/v1/users", None).await;
+        assert_eq!(body, "v1");
+        assert_eq!(headers[DEPRECATION], "@1700000000");
+        assert_eq!(headers[SUNSET], "Wed, 15 Nov 2023 22:13:20 GMT");
+        assert_eq!(
+            headers[LINK],
+            "<https://example.com/migrating-to-v2>; rel=\"deprecation\"; type=\"text/html\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unversioned_path_negotiates_from_accept() {
+        let (body, headers) = call(
+            "/users",
+            Some("text/html, application/vnd.x.v2+json; q=0.9"),
+        )
+        .await;
+        assert_eq!(body, "v2");
+        assert_eq!(headers[VARY], "accept");
+
+        let (body, headers) = call("/users", Some("application/json")).await;
+        assert_eq!(body, "v1");
+        assert!(headers.contains_key(DEPRECATION));
+    }
+
+    #[test]
+    fn test_unversioned_route_strips_only_version_prefixes() {
+        assert_eq!(unversioned_route("/v1/users/{id}"), "/users/{id}");
+        assert_eq!(unversioned_route("/v2/users"), "/users");
+        assert_eq!(unversioned_route("/v1"), "/v1");
+        assert_eq!(unversioned_route("/v10/users"), "/v10/users");
+        assert_eq!(unversioned_route("/users"), "/users");
+    }
+
+    #[test]
+    fn test_deprecation_rejects_sunset_before_deprecation() {
+        assert!(
+            Deprecation::parse("2026-01-01T00:00:00Z", Some("2025-01-01T00:00:00Z"), None).is_err()
+        );
+        assert!(Deprecation::parse("not a date", None, None).is_err());
+    }
+}